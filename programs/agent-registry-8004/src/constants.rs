@@ -28,3 +28,144 @@ pub const SEED_AGENT: &[u8] = b"agent";
 /// Agent metadata entry PDA seed
 /// PDA: ["agent_meta", asset.key(), key_hash[0..16]]
 pub const SEED_AGENT_META: &[u8] = b"agent_meta";
+
+/// Agent operator delegation PDA seed
+/// PDA: ["agent_operator", asset.key(), operator.key()]
+pub const SEED_AGENT_OPERATOR: &[u8] = b"agent_operator";
+
+/// Pending two-step transfer PDA seed
+/// PDA: ["pending_transfer", asset.key()]
+pub const SEED_PENDING_TRANSFER: &[u8] = b"pending_transfer";
+
+/// Registry treasury PDA seed
+/// PDA: ["treasury", collection.key()]
+pub const SEED_TREASURY: &[u8] = b"treasury";
+
+/// Agent metadata chunk PDA seed
+/// PDA: ["agent_meta_chunk", asset.key(), key_hash[0..16], chunk_index (u16 LE)]
+pub const SEED_AGENT_META_CHUNK: &[u8] = b"agent_meta_chunk";
+
+/// Agent DID binding PDA seed
+/// PDA: ["agent_did", asset.key()]
+pub const SEED_AGENT_DID: &[u8] = b"agent_did";
+
+/// Agent id reverse index PDA seed
+/// PDA: ["agent_id", agent_id (u64 LE)]
+pub const SEED_AGENT_ID: &[u8] = b"agent_id";
+
+/// Collection transfer gate (Core Oracle account) PDA seed
+/// PDA: ["transfer_gate", collection.key()]
+pub const SEED_TRANSFER_GATE: &[u8] = b"transfer_gate";
+
+/// Agent category tags PDA seed
+/// PDA: ["agent_tags", asset.key()]
+pub const SEED_AGENT_TAGS: &[u8] = b"agent_tags";
+
+/// Per-tag agent counter PDA seed
+/// PDA: ["tag_counter", keccak256(tag)]
+pub const SEED_TAG_COUNTER: &[u8] = b"tag_counter";
+
+/// Owner reverse index page PDA seed
+/// PDA: ["owner_index", owner, page.to_le_bytes()]
+pub const SEED_OWNER_INDEX: &[u8] = b"owner_index";
+
+/// Endpoint ownership challenge PDA seed
+/// PDA: ["endpoint_challenge", asset.key(), key_hash[0..16]]
+pub const SEED_ENDPOINT_CHALLENGE: &[u8] = b"endpoint_challenge";
+
+/// Owner council PDA seed
+/// PDA: ["owner_council", asset.key()]
+pub const SEED_OWNER_COUNCIL: &[u8] = b"owner_council";
+
+/// Council proposal PDA seed
+/// PDA: ["council_proposal", asset.key(), proposal_id.to_le_bytes()]
+pub const SEED_COUNCIL_PROPOSAL: &[u8] = b"council_proposal";
+
+/// Registration referral PDA seed
+/// PDA: ["referral", asset.key()]
+pub const SEED_REFERRAL: &[u8] = b"referral";
+
+/// Registry attestor PDA seed
+/// PDA: ["attestor", collection.key(), attestor]
+pub const SEED_ATTESTOR: &[u8] = b"attestor";
+
+/// Agent verification PDA seed
+/// PDA: ["agent_verification", asset.key()]
+pub const SEED_AGENT_VERIFICATION: &[u8] = b"agent_verification";
+
+/// Deployment feature flags PDA seed
+/// PDA: ["registry_features"]
+pub const SEED_REGISTRY_FEATURES: &[u8] = b"registry_features";
+
+/// Per-client feedback index PDA seed
+/// PDA: ["client_index", asset.key(), client.key()]
+pub const SEED_CLIENT_INDEX: &[u8] = b"client_index";
+
+/// Per-tag aggregate reputation PDA seed
+/// PDA: ["tag_rep", agent_id.to_le_bytes(), keccak256(tag)]
+pub const SEED_TAG_REPUTATION: &[u8] = b"tag_rep";
+
+/// Per-registry reputation settings PDA seed
+/// PDA: ["reputation_config", collection.key()]
+pub const SEED_REPUTATION_CONFIG: &[u8] = b"reputation_config";
+
+/// Per-feedback client bond PDA seed
+/// PDA: ["feedback_bond", asset.key(), client.key(), feedback_index.to_le_bytes()]
+pub const SEED_FEEDBACK_BOND: &[u8] = b"feedback_bond";
+
+/// Per-feedback aggregated score record PDA seed
+/// PDA: ["feedback_score", asset.key(), client.key(), feedback_index.to_le_bytes()]
+pub const SEED_FEEDBACK_SCORE: &[u8] = b"feedback_score";
+
+/// Approved feedback aggregator PDA seed
+/// PDA: ["feedback_aggregator", collection.key(), aggregator]
+pub const SEED_FEEDBACK_AGGREGATOR: &[u8] = b"feedback_aggregator";
+
+/// Committed off-chain feedback Merkle root PDA seed
+/// PDA: ["feedback_root", collection.key(), root]
+pub const SEED_FEEDBACK_ROOT: &[u8] = b"feedback_root";
+
+/// Claimed feedback root leaf marker PDA seed
+/// PDA: ["feedback_claim", feedback_root.key(), leaf]
+pub const SEED_FEEDBACK_CLAIM: &[u8] = b"feedback_claim";
+
+/// Per-registry canonical tag counter PDA seed
+/// PDA: ["tag_registry", collection.key()]
+pub const SEED_TAG_REGISTRY: &[u8] = b"tag_registry";
+
+/// Canonical feedback tag PDA seed
+/// PDA: ["canonical_tag", collection.key(), keccak256(tag)]
+pub const SEED_CANONICAL_TAG: &[u8] = b"canonical_tag";
+
+/// Hidden feedback commitment PDA seed
+/// PDA: ["feedback_commit", asset.key(), client.key(), commitment]
+pub const SEED_FEEDBACK_COMMIT: &[u8] = b"feedback_commit";
+
+/// Per-epoch reputation checkpoint PDA seed
+/// PDA: ["rep_checkpoint", asset.key(), epoch.to_le_bytes()]
+pub const SEED_REPUTATION_CHECKPOINT: &[u8] = b"rep_checkpoint";
+
+/// Per-dimension score aggregate PDA seed
+/// PDA: ["dim_rep", asset.key()]
+pub const SEED_DIMENSION_REPUTATION: &[u8] = b"dim_rep";
+
+/// Per-tag feedback index page PDA seed
+/// PDA: ["tag_fb_index", asset.key(), keccak256(tag), page.to_le_bytes()]
+pub const SEED_TAG_FEEDBACK_INDEX: &[u8] = b"tag_fb_index";
+
+/// Client feedback permit nonce PDA seed
+/// PDA: ["permit_nonce", client]
+pub const SEED_PERMIT_NONCE: &[u8] = b"permit_nonce";
+
+/// EVM client feedback permit nonce PDA seed
+/// PDA: ["evm_permit_nonce", evm_address]
+pub const SEED_EVM_PERMIT_NONCE: &[u8] = b"evm_permit_nonce";
+
+/// Feedback moderation flag PDA seed
+/// PDA: ["feedback_flag", asset.key(), client, feedback_index.to_le_bytes()]
+pub const SEED_FEEDBACK_FLAG: &[u8] = b"feedback_flag";
+
+/// Core plugin kinds reported in AgentPluginUpdated events
+pub const PLUGIN_KIND_FREEZE_DELEGATE: u8 = 0;
+pub const PLUGIN_KIND_ROYALTIES: u8 = 1;
+pub const PLUGIN_KIND_ORACLE: u8 = 2;
@@ -1,7 +1,9 @@
 use anchor_lang::prelude::*;
 use mpl_core::accounts::BaseAssetV1;
+use mpl_core::types::UpdateAuthority;
 
 use crate::error::RegistryError;
+use crate::identity::state::AgentOperator;
 
 /// Read the authoritative owner from a Metaplex Core asset account.
 pub fn get_core_owner(asset_info: &AccountInfo) -> Result<Pubkey> {
@@ -13,9 +15,57 @@ pub fn get_core_owner(asset_info: &AccountInfo) -> Result<Pubkey> {
     Ok(asset.owner)
 }
 
+/// Read the collection a Metaplex Core asset belongs to (None when not in a collection).
+pub fn get_core_collection(asset_info: &AccountInfo) -> Result<Option<Pubkey>> {
+    require!(*asset_info.owner == mpl_core::ID, RegistryError::InvalidAsset);
+
+    let data = asset_info.try_borrow_data()?;
+    let asset = BaseAssetV1::from_bytes(&data).map_err(|_| RegistryError::InvalidAsset)?;
+
+    Ok(match asset.update_authority {
+        UpdateAuthority::Collection(collection) => Some(collection),
+        _ => None,
+    })
+}
+
 /// Verify that `expected_owner` currently owns the Core asset.
 pub fn verify_core_owner(asset_info: &AccountInfo, expected_owner: &Pubkey) -> Result<()> {
     let actual_owner = get_core_owner(asset_info)?;
     require!(actual_owner == *expected_owner, RegistryError::Unauthorized);
     Ok(())
 }
+
+/// Verify that `signer` is the live Core owner, or an unexpired operator granted by
+/// that owner whose scope covers `scope` (AgentOperator::SCOPE_* bits).
+pub fn verify_owner_or_operator(
+    asset_info: &AccountInfo,
+    signer: &Pubkey,
+    operator: Option<&AgentOperator>,
+    scope: u64,
+) -> Result<()> {
+    let owner = get_core_owner(asset_info)?;
+    if owner == *signer {
+        return Ok(());
+    }
+
+    let operator = operator.ok_or(RegistryError::Unauthorized)?;
+    require!(
+        operator.asset == asset_info.key()
+            && operator.operator == *signer
+            && operator.granted_by == owner
+            && operator.allows(scope, Clock::get()?.unix_timestamp),
+        RegistryError::Unauthorized
+    );
+    Ok(())
+}
+
+/// Check whether a Core asset account has been burned.
+/// Core leaves a single uninitialized key byte behind after BurnV1; a fully
+/// drained account is handed back to the system program with no data.
+pub fn is_core_asset_burned(asset_info: &AccountInfo) -> bool {
+    if *asset_info.owner == mpl_core::ID {
+        asset_info.data_len() <= 1
+    } else {
+        asset_info.data_is_empty()
+    }
+}
@@ -45,6 +45,64 @@ pub enum RegistryError {
     CollectionPointerAlreadySet = 6019,
     #[msg("Only agent creator can set collection pointer")]
     NotAgentCreator = 6020,
+    #[msg("Agent is frozen by the registry authority")]
+    AgentFrozen = 6021,
+    #[msg("Registration expiry is disabled for this registry")]
+    RegistrationExpiryDisabled = 6022,
+    #[msg("Registration period must be non-negative")]
+    InvalidRegistrationPeriod = 6023,
+    #[msg("Token-2022 agent backend is disabled for this registry")]
+    Token2022BackendDisabled = 6024,
+    #[msg("Missing or invalid treasury account")]
+    InvalidTreasury = 6025,
+    #[msg("Treasury balance would fall below rent-exempt minimum")]
+    InsufficientTreasuryBalance = 6026,
+    #[msg("Registry name exceeds 32 bytes")]
+    RegistryNameTooLong = 6027,
+    #[msg("No agent card committed")]
+    AgentCardNotCommitted = 6028,
+    #[msg("Agent card does not match commitment")]
+    AgentCardMismatch = 6029,
+    #[msg("Invalid DID (expected did:sol or did:web, max 128 bytes)")]
+    InvalidDid = 6030,
+    #[msg("Capabilities set undefined standardized bits")]
+    InvalidCapabilities = 6031,
+    #[msg("Invalid royalties (max 10000 bps, 1-5 creators summing to 100%)")]
+    InvalidRoyalties = 6032,
+    #[msg("Agent is soulbound and cannot be transferred")]
+    AgentSoulbound = 6033,
+    #[msg("Registry config account required for soulbound agents")]
+    MissingRegistryConfig = 6034,
+    #[msg("URI scheme not allowed by registry (allowlist: ipfs://, ar://, https://)")]
+    InvalidUriScheme = 6035,
+    #[msg("Invalid category tags (max 4 unique, lowercase a-z 0-9 '-', max 32 bytes)")]
+    InvalidTag = 6036,
+    #[msg("Tag counter accounts do not match old + new tags")]
+    TagCounterMismatch = 6037,
+    #[msg("Owner index page is full")]
+    OwnerIndexFull = 6038,
+    #[msg("Owner index page does not belong to this owner or asset")]
+    OwnerIndexMismatch = 6039,
+    #[msg("Registration bond is still in its cooldown period")]
+    BondLocked = 6040,
+    #[msg("Agent has no registration bond")]
+    NoRegistrationBond = 6041,
+    #[msg("Bond cooldown must be non-negative")]
+    InvalidBondCooldown = 6042,
+    #[msg("Owner is not on the registry allowlist")]
+    NotAllowlisted = 6043,
+    #[msg("Agent account already uses the current layout")]
+    AgentAlreadyMigrated = 6044,
+    #[msg("Unsupported agent account layout")]
+    UnsupportedAgentLayout = 6045,
+    #[msg("Invalid agent status transition")]
+    InvalidAgentStatus = 6046,
+    #[msg("Agent is suspended by the registry authority")]
+    AgentSuspended = 6047,
+    #[msg("Agent is retired or suspended and does not accept feedback")]
+    AgentInactive = 6048,
+    #[msg("Invalid agent name or symbol")]
+    InvalidAgentName = 6049,
 
     // ========== Reputation Errors (6050-6099) ==========
     #[msg("Score must be 0-100")]
@@ -73,6 +131,74 @@ pub enum RegistryError {
     AtomStatsNotInitialized = 6058,
     #[msg("ATOM already enabled for this agent")]
     AtomAlreadyEnabled = 6059,
+    #[msg("Agent requires a FeedbackAuth - use give_feedback_with_auth")]
+    FeedbackAuthRequired = 6062,
+    #[msg("FeedbackAuth does not match this agent, client or an authorized signer")]
+    InvalidFeedbackAuth = 6063,
+    #[msg("FeedbackAuth has expired")]
+    FeedbackAuthExpired = 6064,
+    #[msg("FeedbackAuth index limit reached")]
+    FeedbackAuthIndexLimitExceeded = 6065,
+    #[msg("Client index account missing or not the canonical PDA")]
+    InvalidClientIndex = 6066,
+    #[msg("Paid feedback is disabled, the agent has no wallet, or payment accounts mismatch")]
+    InvalidFeedbackPayment = 6067,
+    #[msg("Feedback payment is below the registry minimum")]
+    FeedbackPaymentTooLow = 6068,
+    #[msg("Tag reputation account missing or not the canonical PDA")]
+    InvalidTagReputation = 6069,
+    #[msg("Invalid response index")]
+    InvalidResponseIndex = 6070,
+    #[msg("Decay half-life must be non-negative")]
+    InvalidDecayHalfLife = 6071,
+    #[msg("No full half-life has elapsed or decay is disabled")]
+    DecayNotDue = 6072,
+    #[msg("Feedback cooldown for this client and agent has not elapsed")]
+    FeedbackCooldownActive = 6073,
+    #[msg("Feedback bond account missing or not the canonical PDA")]
+    InvalidFeedbackBond = 6074,
+    #[msg("Feedback bond is still in its challenge window or under dispute")]
+    FeedbackBondLocked = 6075,
+    #[msg("Feedback bond cannot be disputed or resolved in its current state")]
+    InvalidFeedbackBondState = 6076,
+    #[msg("Batch must hold 1-10 entries with their accounts in order")]
+    InvalidBatch = 6077,
+    #[msg("Registry or agent settings require give_feedback for this agent")]
+    BatchFeedbackUnsupported = 6078,
+    #[msg("Feedback entry is not covered by the committed root")]
+    InvalidFeedbackProof = 6079,
+    #[msg("Feedback entry was already claimed")]
+    FeedbackAlreadyClaimed = 6080,
+    #[msg("Invalid feedback root or all of its entries are claimed")]
+    InvalidFeedbackRoot = 6081,
+    #[msg("Tag is not a canonical tag of this registry")]
+    NonCanonicalTag = 6082,
+    #[msg("Canonical tag account does not match the tag")]
+    InvalidCanonicalTag = 6083,
+    #[msg("Feedback commitment does not match the revealed feedback")]
+    InvalidFeedbackCommitment = 6085,
+    #[msg("Feedback reveal needs the reveal delay to pass or the owner's consent")]
+    FeedbackRevealLocked = 6086,
+    #[msg("Invalid reputation checkpoint account")]
+    InvalidReputationCheckpoint = 6087,
+    #[msg("Dimension scores must be 0-100 or unrated (255)")]
+    InvalidDimensionScore = 6088,
+    #[msg("Invalid dimension reputation account")]
+    InvalidDimensionReputation = 6089,
+    #[msg("Tag feedback index page does not match the agent, tag or page order")]
+    TagFeedbackIndexMismatch = 6090,
+    #[msg("Tag feedback index page is full; create the next page")]
+    TagFeedbackIndexFull = 6091,
+    #[msg("Feedback permit expired or its nonce is not the next one")]
+    InvalidFeedbackPermit = 6092,
+    #[msg("Invalid feedback flag reason")]
+    InvalidFlagReason = 6093,
+    #[msg("Feedback flag was already counter-flagged")]
+    FeedbackAlreadyCounterFlagged = 6094,
+    #[msg("Feedback score record missing, not the canonical PDA or for another feedback")]
+    InvalidFeedbackScore = 6095,
+    #[msg("Registry or agent settings require give_feedback; relayed or claimed feedback is off")]
+    RelayedFeedbackUnsupported = 6096,
 
     // ========== Validation Errors (6100-6149) ==========
     #[msg("Request URI exceeds 250 bytes")]
@@ -99,6 +225,26 @@ pub enum RegistryError {
     KeyHashCollision = 6151,
     #[msg("Reserved metadata key - use dedicated instruction")]
     ReservedMetadataKey = 6152,
+    #[msg("Metadata value does not match the well-known key schema")]
+    InvalidMetadataValue = 6153,
+    #[msg("Metadata chunk index exceeds registry limit")]
+    ChunkIndexOutOfRange = 6154,
+    #[msg("Metadata chunk exceeds 1024 bytes")]
+    ChunkTooLarge = 6155,
+    #[msg("Metadata expiry must be in the future")]
+    InvalidMetadataExpiry = 6156,
+    #[msg("Metadata accounts do not match entries (max 8, canonical PDAs in order)")]
+    MetadataAccountMismatch = 6157,
+    #[msg("Endpoint challenge has expired")]
+    EndpointChallengeExpired = 6158,
+    #[msg("Unknown agent URI content type")]
+    InvalidUriContentType = 6159,
+    #[msg("Metadata entry uses the v0.6.0 layout - run migrate_metadata_entry first")]
+    MetadataEntryNotMigrated = 6160,
+    #[msg("Metadata entry already uses the current layout")]
+    MetadataEntryAlreadyMigrated = 6161,
+    #[msg("Unsupported metadata entry layout")]
+    UnsupportedMetadataLayout = 6162,
 
     // ========== Wallet Errors (6200-6249) ==========
     #[msg("Deadline has expired")]
@@ -113,6 +259,26 @@ pub enum RegistryError {
     // ========== Registry Errors (6250-6299) ==========
     #[msg("Root config already initialized")]
     RootAlreadyInitialized = 6251,
+    #[msg("Referral share must be at most 10000 basis points")]
+    InvalidReferralShare = 6252,
+    #[msg("Invalid referral: referrer/referral accounts missing or mismatched, or self-referral")]
+    InvalidReferral = 6253,
+    #[msg("Invalid session: scope must be non-zero and expiry in the future")]
+    InvalidSession = 6254,
+    #[msg("Royalties are enforced by the registry for this collection")]
+    RoyaltiesManagedByRegistry = 6255,
+    #[msg("Dispute window since the last feedback has not elapsed")]
+    DisputeWindowOpen = 6256,
+    #[msg("Dispute window must be non-negative")]
+    InvalidDisputeWindow = 6257,
+    #[msg("EVM signature does not recover to the claimed Ethereum address")]
+    InvalidEvmSignature = 6258,
+    #[msg("Unknown feedback index mode")]
+    InvalidFeedbackIndexMode = 6259,
+    #[msg("Config account already uses the current layout")]
+    ConfigAlreadyMigrated = 6260,
+    #[msg("Unsupported config account layout")]
+    UnsupportedConfigLayout = 6261,
 
     // ========== Anti-Gaming Errors (6300-6309) ==========
     #[msg("Self-feedback is not allowed - agent owner cannot give feedback to their own agent")]
@@ -120,6 +286,22 @@ pub enum RegistryError {
     #[msg("Self-validation is not allowed - agent owner cannot validate their own agent")]
     SelfValidationNotAllowed = 6301,
 
+    // ========== Council Errors (6310-6329) ==========
+    #[msg("Invalid council: 1-5 unique members, 1 <= threshold <= members (none to disable)")]
+    InvalidCouncil = 6310,
+    #[msg("Signer is not a member of the owner council")]
+    NotCouncilMember = 6311,
+    #[msg("Council or proposal does not belong to this asset's current owner")]
+    CouncilMismatch = 6312,
+    #[msg("Council proposal already executed or superseded by a council update")]
+    CouncilProposalStale = 6313,
+    #[msg("Council proposal does not match the requested action")]
+    CouncilActionMismatch = 6314,
+    #[msg("Council approval threshold not met")]
+    CouncilThresholdNotMet = 6315,
+    #[msg("Council member already approved this proposal")]
+    CouncilAlreadyApproved = 6316,
+
     // ========== CPI Errors (6400-6409) ==========
     #[msg("Invalid program ID for CPI call")]
     InvalidProgram = 6400,
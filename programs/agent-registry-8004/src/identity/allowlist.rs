@@ -0,0 +1,80 @@
+//! Registration allowlist for gated registries.
+//!
+//! A registry authority may store a Merkle root in `RegistryConfig.allowlist_root`.
+//! While it is non-zero, registering requires a proof that the owner wallet is a
+//! leaf of the tree. Pairs are hashed in sorted order so proofs carry no
+//! position bits (same convention as common EVM allowlist tooling).
+//!
+//! ```text
+//! leaf = keccak256(DOMAIN_ALLOWLIST_LEAF_V1 || owner)
+//! node = keccak256(min(a, b) || max(a, b))
+//! ```
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::keccak;
+
+/// Domain separator for allowlist leaves (exactly 16 bytes)
+pub const DOMAIN_ALLOWLIST_LEAF_V1: &[u8; 16] = b"8004_ALLOWLIST_1";
+
+/// Maximum Merkle proof depth
+pub const MAX_ALLOWLIST_PROOF_DEPTH: usize = 32;
+
+/// Leaf hash for an allowlisted wallet
+pub fn compute_allowlist_leaf(owner: &Pubkey) -> [u8; 32] {
+    keccak::hashv(&[DOMAIN_ALLOWLIST_LEAF_V1, owner.as_ref()]).0
+}
+
+/// Verify that `owner` is in the allowlist tree with root `root`
+pub fn verify_allowlist_proof(root: &[u8; 32], owner: &Pubkey, proof: &[[u8; 32]]) -> bool {
+    verify_sorted_proof(root, compute_allowlist_leaf(owner), proof)
+}
+
+/// Verify a sorted-pair Merkle proof for an already hashed leaf
+pub fn verify_sorted_proof(root: &[u8; 32], leaf: [u8; 32], proof: &[[u8; 32]]) -> bool {
+    if proof.len() > MAX_ALLOWLIST_PROOF_DEPTH {
+        return false;
+    }
+    let mut node = leaf;
+    for sibling in proof {
+        node = if node <= *sibling {
+            keccak::hashv(&[&node, sibling]).0
+        } else {
+            keccak::hashv(&[sibling, &node]).0
+        };
+    }
+    node == *root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+        if a <= b {
+            keccak::hashv(&[a, b]).0
+        } else {
+            keccak::hashv(&[b, a]).0
+        }
+    }
+
+    #[test]
+    fn test_verify_allowlist_proof() {
+        let owners: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        let leaves: Vec<[u8; 32]> = owners.iter().map(compute_allowlist_leaf).collect();
+        let left = node(&leaves[0], &leaves[1]);
+        let right = node(&leaves[2], &leaves[3]);
+        let root = node(&left, &right);
+
+        assert!(verify_allowlist_proof(&root, &owners[0], &[leaves[1], right]));
+        assert!(verify_allowlist_proof(&root, &owners[3], &[leaves[2], left]));
+        assert!(!verify_allowlist_proof(&root, &owners[0], &[leaves[2], right]));
+        assert!(!verify_allowlist_proof(&root, &Pubkey::new_unique(), &[leaves[1], right]));
+    }
+
+    #[test]
+    fn test_single_wallet_root_is_leaf() {
+        let owner = Pubkey::new_unique();
+        let root = compute_allowlist_leaf(&owner);
+        assert!(verify_allowlist_proof(&root, &owner, &[]));
+    }
+}
@@ -0,0 +1,101 @@
+//! Agent card commitment.
+//!
+//! The owner commits the keccak256 of the off-chain agent card referenced by
+//! `agent_uri`. Anyone can later prove the card content against chain state,
+//! either with the full card or, for cards too large for one transaction, with
+//! a single chunk and a Merkle proof against the committed chunk root.
+//!
+//! ```text
+//! card_hash  = keccak256(card bytes)
+//! leaf       = keccak256(DOMAIN_CARD_LEAF_V1 || chunk_index (u32 LE) || chunk)
+//! node       = keccak256(left || right)      (left/right from index bits)
+//! chunk_root = root of the leaf tree
+//! ```
+
+use anchor_lang::solana_program::keccak;
+
+/// Domain separator for card chunk leaves (exactly 16 bytes)
+pub const DOMAIN_CARD_LEAF_V1: &[u8; 16] = b"8004_CARD_LEAF_1";
+
+/// Maximum Merkle proof depth (2^32 chunks)
+pub const MAX_CARD_PROOF_DEPTH: usize = 32;
+
+/// keccak256 of the full agent card
+pub fn compute_card_hash(card: &[u8]) -> [u8; 32] {
+    keccak::hash(card).0
+}
+
+/// Leaf hash for a card chunk
+pub fn compute_card_leaf(chunk_index: u32, chunk: &[u8]) -> [u8; 32] {
+    keccak::hashv(&[DOMAIN_CARD_LEAF_V1, &chunk_index.to_le_bytes(), chunk]).0
+}
+
+/// Verify that `chunk` is leaf `chunk_index` of the tree with root `root`
+pub fn verify_card_chunk(
+    root: &[u8; 32],
+    chunk_index: u32,
+    chunk: &[u8],
+    proof: &[[u8; 32]],
+) -> bool {
+    if proof.len() > MAX_CARD_PROOF_DEPTH {
+        return false;
+    }
+    let mut node = compute_card_leaf(chunk_index, chunk);
+    let mut index = chunk_index as u64;
+    for sibling in proof {
+        node = if index & 1 == 0 {
+            keccak::hashv(&[&node, sibling]).0
+        } else {
+            keccak::hashv(&[sibling, &node]).0
+        };
+        index >>= 1;
+    }
+    // Every index bit must be consumed by the proof
+    index == 0 && node == *root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+        keccak::hashv(&[left, right]).0
+    }
+
+    #[test]
+    fn test_verify_card_chunk_four_leaves() {
+        let chunks: [&[u8]; 4] = [b"chunk-0", b"chunk-1", b"chunk-2", b"chunk-3"];
+        let leaves: Vec<[u8; 32]> = chunks
+            .iter()
+            .enumerate()
+            .map(|(i, c)| compute_card_leaf(i as u32, c))
+            .collect();
+        let left = node(&leaves[0], &leaves[1]);
+        let right = node(&leaves[2], &leaves[3]);
+        let root = node(&left, &right);
+
+        assert!(verify_card_chunk(&root, 0, chunks[0], &[leaves[1], right]));
+        assert!(verify_card_chunk(&root, 2, chunks[2], &[leaves[3], left]));
+        assert!(verify_card_chunk(&root, 3, chunks[3], &[leaves[2], left]));
+    }
+
+    #[test]
+    fn test_verify_card_chunk_rejects_tampering() {
+        let leaf0 = compute_card_leaf(0, b"a");
+        let leaf1 = compute_card_leaf(1, b"b");
+        let root = node(&leaf0, &leaf1);
+
+        assert!(verify_card_chunk(&root, 0, b"a", &[leaf1]));
+        // Wrong content, wrong index, wrong position
+        assert!(!verify_card_chunk(&root, 0, b"x", &[leaf1]));
+        assert!(!verify_card_chunk(&root, 1, b"a", &[leaf1]));
+        // Index bits beyond the proof depth
+        assert!(!verify_card_chunk(&root, 2, b"a", &[leaf1]));
+    }
+
+    #[test]
+    fn test_single_chunk_root_is_leaf() {
+        let root = compute_card_leaf(0, b"whole card");
+        assert!(verify_card_chunk(&root, 0, b"whole card", &[]));
+    }
+}
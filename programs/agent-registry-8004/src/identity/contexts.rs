@@ -1,5 +1,8 @@
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{Mint, TokenAccount};
 
 use super::state::*;
 use crate::constants::BPF_LOADER_UPGRADEABLE_ID;
@@ -28,6 +31,7 @@ pub struct SetMetadataPda<'info> {
     #[account(
         seeds = [b"agent", asset.key().as_ref()],
         bump = agent_account.bump,
+        constraint = !agent_account.frozen @ RegistryError::AgentFrozen,
     )]
     pub agent_account: Account<'info, AgentAccount>,
 
@@ -38,11 +42,25 @@ pub struct SetMetadataPda<'info> {
     )]
     pub asset: UncheckedAccount<'info>,
 
-    /// Owner must be the asset owner (verified in instruction)
+    /// Asset owner or approved operator (verified in instruction)
     #[account(mut)]
     pub owner: Signer<'info>,
 
     pub system_program: Program<'info, System>,
+
+    /// Optional operator delegation (required when signer is not the Core owner)
+    pub agent_operator: Option<Account<'info, AgentOperator>>,
+
+    /// Optional owner council (council-approved execution instead of owner/operator)
+    #[account(
+        seeds = [b"owner_council", asset.key().as_ref()],
+        bump = owner_council.bump
+    )]
+    pub owner_council: Option<Account<'info, OwnerCouncil>>,
+
+    /// Approved council proposal for this exact action (consumed on execution)
+    #[account(mut)]
+    pub council_proposal: Option<Account<'info, CouncilProposal>>,
 }
 
 /// Delete metadata PDA and recover rent
@@ -55,7 +73,9 @@ pub struct DeleteMetadataPda<'info> {
         mut,
         close = owner,
         seeds = [b"agent_meta", asset.key().as_ref(), key_hash.as_ref()],
-        bump = metadata_entry.bump
+        bump = metadata_entry.bump,
+        constraint = metadata_entry.to_account_info().data_len() == MetadataEntryPda::SPACE
+            @ RegistryError::MetadataEntryNotMigrated
     )]
     pub metadata_entry: Account<'info, MetadataEntryPda>,
 
@@ -72,190 +92,205 @@ pub struct DeleteMetadataPda<'info> {
     )]
     pub asset: UncheckedAccount<'info>,
 
-    /// Owner must be the asset owner (verified in instruction)
+    /// Asset owner or approved operator (verified in instruction)
     /// Receives rent back when PDA is closed
     #[account(mut)]
     pub owner: Signer<'info>,
+
+    /// Optional operator delegation (required when signer is not the Core owner)
+    pub agent_operator: Option<Account<'info, AgentOperator>>,
 }
 
-/// Set agent URI (owner only)
+/// Set or append a metadata chunk for an existing metadata entry
 #[derive(Accounts)]
-pub struct SetAgentUri<'info> {
-    /// Registry config for this collection
+#[instruction(key_hash: [u8; 16], chunk_index: u16)]
+pub struct SetMetadataChunk<'info> {
     #[account(
-        seeds = [b"registry_config", collection.key().as_ref()],
-        bump = registry_config.bump
+        init_if_needed,
+        payer = owner,
+        space = MetadataChunk::DISCRIMINATOR.len() + MetadataChunk::INIT_SPACE,
+        seeds = [
+            b"agent_meta_chunk",
+            asset.key().as_ref(),
+            key_hash.as_ref(),
+            &chunk_index.to_le_bytes()
+        ],
+        bump
     )]
-    pub registry_config: Account<'info, RegistryConfig>,
+    pub metadata_chunk: Account<'info, MetadataChunk>,
+
+    /// Parent metadata entry (must exist)
+    #[account(
+        seeds = [b"agent_meta", asset.key().as_ref(), key_hash.as_ref()],
+        bump = metadata_entry.bump,
+        constraint = metadata_entry.to_account_info().data_len() == MetadataEntryPda::SPACE
+            @ RegistryError::MetadataEntryNotMigrated
+    )]
+    pub metadata_entry: Account<'info, MetadataEntryPda>,
 
     #[account(
-        mut,
         seeds = [b"agent", asset.key().as_ref()],
         bump = agent_account.bump,
+        constraint = !agent_account.frozen @ RegistryError::AgentFrozen,
     )]
     pub agent_account: Account<'info, AgentAccount>,
 
-    /// Core asset for URI update
-    /// CHECK: Ownership verified in instruction
+    /// Registry config (provides the chunk cap)
     #[account(
-        mut,
-        constraint = asset.key() == agent_account.asset @ RegistryError::InvalidAsset
+        seeds = [b"registry_config", agent_account.collection.as_ref()],
+        bump = registry_config.bump
     )]
-    pub asset: UncheckedAccount<'info>,
+    pub registry_config: Account<'info, RegistryConfig>,
 
-    /// Collection account (required by Core for assets in collection)
-    /// CHECK: Verified via registry_config constraint
+    /// Core asset - verifies ownership
+    /// CHECK: Ownership verified in instruction
     #[account(
-        mut,
-        constraint = collection.key() == registry_config.collection @ RegistryError::InvalidCollection
+        constraint = asset.key() == agent_account.asset @ RegistryError::InvalidAsset
     )]
-    pub collection: UncheckedAccount<'info>,
+    pub asset: UncheckedAccount<'info>,
 
+    /// Asset owner or approved operator (verified in instruction)
     #[account(mut)]
     pub owner: Signer<'info>,
 
     pub system_program: Program<'info, System>,
 
-    /// Metaplex Core program
-    /// CHECK: Verified by address constraint
-    #[account(address = mpl_core::ID)]
-    pub mpl_core_program: UncheckedAccount<'info>,
+    /// Optional operator delegation (required when signer is not the Core owner)
+    pub agent_operator: Option<Account<'info, AgentOperator>>,
 }
 
-/// Sync owner after Core transfer
+/// Delete a metadata chunk and recover rent
 #[derive(Accounts)]
-pub struct SyncOwner<'info> {
+#[instruction(key_hash: [u8; 16], chunk_index: u16)]
+pub struct DeleteMetadataChunk<'info> {
     #[account(
         mut,
+        close = owner,
+        seeds = [
+            b"agent_meta_chunk",
+            asset.key().as_ref(),
+            key_hash.as_ref(),
+            &chunk_index.to_le_bytes()
+        ],
+        bump = metadata_chunk.bump
+    )]
+    pub metadata_chunk: Account<'info, MetadataChunk>,
+
+    /// Parent metadata entry (empty once deleted; a deleted parent was unlocked)
+    /// CHECK: PDA verified by seeds, lock state read in instruction
+    #[account(seeds = [b"agent_meta", asset.key().as_ref(), key_hash.as_ref()], bump)]
+    pub metadata_entry: UncheckedAccount<'info>,
+
+    #[account(
         seeds = [b"agent", asset.key().as_ref()],
-        bump = agent_account.bump
+        bump = agent_account.bump,
     )]
     pub agent_account: Account<'info, AgentAccount>,
 
-    /// Core asset - ownership is read from asset data
-    /// CHECK: Verified in instruction
+    /// Core asset - verifies ownership
+    /// CHECK: Ownership verified in instruction
     #[account(
         constraint = asset.key() == agent_account.asset @ RegistryError::InvalidAsset
     )]
     pub asset: UncheckedAccount<'info>,
+
+    /// Asset owner or approved operator (receives rent back)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Optional operator delegation (required when signer is not the Core owner)
+    pub agent_operator: Option<Account<'info, AgentOperator>>,
 }
 
-/// Get owner of agent (cached value - may be stale)
+/// Set the metadata chunk cap (registry authority only)
 #[derive(Accounts)]
-pub struct OwnerOf<'info> {
+pub struct SetMaxMetadataChunks<'info> {
     #[account(
-        seeds = [b"agent", asset.key().as_ref()],
-        bump = agent_account.bump
+        mut,
+        seeds = [b"registry_config", registry_config.collection.as_ref()],
+        bump = registry_config.bump,
+        has_one = authority @ RegistryError::Unauthorized
     )]
-    pub agent_account: Account<'info, AgentAccount>,
-
-    /// Core asset (for PDA derivation)
-    /// CHECK: Used for PDA derivation
-    pub asset: UncheckedAccount<'info>,
-}
+    pub registry_config: Account<'info, RegistryConfig>,
 
-/// Get authoritative Core owner (reads live from Metaplex Core)
-#[derive(Accounts)]
-pub struct CoreOwnerOf<'info> {
-    /// Core asset to read owner from
-    /// CHECK: Validated in instruction (must be MPL Core owned)
-    pub asset: UncheckedAccount<'info>,
+    pub authority: Signer<'info>,
 }
 
-/// Transfer agent with automatic owner sync
-/// Automatically resets agent_wallet to None on transfer
+/// Commit the agent card hash (owner or operator)
 #[derive(Accounts)]
-pub struct TransferAgent<'info> {
+pub struct CommitAgentCard<'info> {
     #[account(
         mut,
         seeds = [b"agent", asset.key().as_ref()],
-        bump = agent_account.bump
+        bump = agent_account.bump,
+        constraint = !agent_account.frozen @ RegistryError::AgentFrozen,
     )]
     pub agent_account: Account<'info, AgentAccount>,
 
-    /// Core asset to transfer
-    /// CHECK: Verified via agent_account constraint
+    /// Core asset - verifies ownership
+    /// CHECK: Ownership verified in instruction
     #[account(
-        mut,
         constraint = asset.key() == agent_account.asset @ RegistryError::InvalidAsset
     )]
     pub asset: UncheckedAccount<'info>,
 
-    /// Collection (required by Core transfer)
-    /// CHECK: Verified by Core CPI
-    #[account(mut)]
-    pub collection: UncheckedAccount<'info>,
-
-    /// Current owner (must sign)
-    #[account(mut)]
     pub owner: Signer<'info>,
 
-    /// New owner receiving the asset
-    /// CHECK: Can be any account
-    pub new_owner: UncheckedAccount<'info>,
-
-    /// Metaplex Core program
-    /// CHECK: Verified by address constraint
-    #[account(address = mpl_core::ID)]
-    pub mpl_core_program: UncheckedAccount<'info>,
+    /// Optional operator delegation (required when signer is not the Core owner)
+    pub agent_operator: Option<Account<'info, AgentOperator>>,
 }
 
-/// Set agent wallet with Ed25519 signature verification
-/// Transaction must include Ed25519Program verify instruction before this one
-/// Wallet is stored directly in AgentAccount (no separate PDA = no rent cost)
+/// Set agent capability flags (owner or operator)
 #[derive(Accounts)]
-#[instruction(new_wallet: Pubkey, deadline: i64)]
-pub struct SetAgentWallet<'info> {
-    /// Agent owner (must be Core asset owner)
-    pub owner: Signer<'info>,
-
+pub struct SetCapabilities<'info> {
     #[account(
         mut,
         seeds = [b"agent", asset.key().as_ref()],
         bump = agent_account.bump,
+        constraint = !agent_account.frozen @ RegistryError::AgentFrozen,
     )]
     pub agent_account: Account<'info, AgentAccount>,
 
-    /// Core asset - ownership verified in instruction
-    /// CHECK: Verified via agent_account constraint and in instruction
+    /// Core asset - verifies ownership
+    /// CHECK: Ownership verified in instruction
     #[account(
         constraint = asset.key() == agent_account.asset @ RegistryError::InvalidAsset
     )]
     pub asset: UncheckedAccount<'info>,
 
-    /// Instructions sysvar for Ed25519 signature introspection
-    /// CHECK: Verified by address constraint
-    #[account(address = sysvar_instructions::ID)]
-    pub instructions_sysvar: UncheckedAccount<'info>,
+    pub owner: Signer<'info>,
+
+    /// Optional operator delegation (required when signer is not the Core owner)
+    pub agent_operator: Option<Account<'info, AgentOperator>>,
 }
 
-/// Set canonical collection pointer in AgentAccount (first-write-wins)
+/// Set agent display symbol (owner or operator)
 #[derive(Accounts)]
-#[instruction(col: String)]
-pub struct SetCollectionPointer<'info> {
+pub struct SetAgentSymbol<'info> {
     #[account(
         mut,
         seeds = [b"agent", asset.key().as_ref()],
         bump = agent_account.bump,
+        constraint = !agent_account.frozen @ RegistryError::AgentFrozen,
     )]
     pub agent_account: Account<'info, AgentAccount>,
 
-    /// Core asset - ownership verified in instruction
-    /// CHECK: Verified via agent_account constraint and in instruction
+    /// Core asset - verifies ownership
+    /// CHECK: Ownership verified in instruction
     #[account(
         constraint = asset.key() == agent_account.asset @ RegistryError::InvalidAsset
     )]
     pub asset: UncheckedAccount<'info>,
 
-    /// Creator signer (must match immutable AgentAccount.creator)
-    #[account(mut)]
     pub owner: Signer<'info>,
+
+    /// Optional operator delegation (required when signer is not the Core owner)
+    pub agent_operator: Option<Account<'info, AgentOperator>>,
 }
 
-/// Set parent link in AgentAccount (first-write-wins)
+/// Set agent lifecycle status (owner or operator)
 #[derive(Accounts)]
-#[instruction(parent_asset: Pubkey)]
-pub struct SetParentAsset<'info> {
+pub struct SetAgentStatus<'info> {
     #[account(
         mut,
         seeds = [b"agent", asset.key().as_ref()],
@@ -263,97 +298,136 @@ pub struct SetParentAsset<'info> {
     )]
     pub agent_account: Account<'info, AgentAccount>,
 
-    /// Core child asset - ownership verified in instruction
-    /// CHECK: Verified via agent_account constraint and in instruction
+    /// Core asset - verifies ownership
+    /// CHECK: Ownership verified in instruction
     #[account(
         constraint = asset.key() == agent_account.asset @ RegistryError::InvalidAsset
     )]
     pub asset: UncheckedAccount<'info>,
 
+    pub owner: Signer<'info>,
+
+    /// Optional operator delegation (required when signer is not the Core owner)
+    pub agent_operator: Option<Account<'info, AgentOperator>>,
+}
+
+/// Create an OwnerIndex page for a wallet (permissionless, payer funds rent)
+/// Page N > 0 may only be created once page N - 1 is full
+#[derive(Accounts)]
+#[instruction(owner: Pubkey, page: u16)]
+pub struct InitOwnerIndex<'info> {
     #[account(
-        seeds = [b"agent", parent_asset.as_ref()],
-        bump = parent_agent_account.bump,
+        init,
+        payer = payer,
+        space = OwnerIndex::DISCRIMINATOR.len() + OwnerIndex::INIT_SPACE,
+        seeds = [b"owner_index", owner.as_ref(), page.to_le_bytes().as_ref()],
+        bump
     )]
-    pub parent_agent_account: Account<'info, AgentAccount>,
+    pub owner_index: Account<'info, OwnerIndex>,
 
-    /// Core parent asset account
-    /// CHECK: Liveness/type verified in instruction
+    /// Previous page (required when page > 0)
     #[account(
-        constraint = parent_asset_account.key() == parent_agent_account.asset @ RegistryError::InvalidAsset
+        seeds = [
+            b"owner_index",
+            owner.as_ref(),
+            page.saturating_sub(1).to_le_bytes().as_ref()
+        ],
+        bump = previous_page.bump
     )]
-    pub parent_asset_account: UncheckedAccount<'info>,
+    pub previous_page: Option<Account<'info, OwnerIndex>>,
 
-    /// Current owner of child asset
     #[account(mut)]
-    pub owner: Signer<'info>,
-}
+    pub payer: Signer<'info>,
 
-// ============================================================================
-// Single Collection Architecture
-// ============================================================================
+    pub system_program: Program<'info, System>,
+}
 
-/// Initialize the registry with root config and base collection
-/// Only upgrade authority can call this (prevents front-running)
+/// Repair one asset's entry on an OwnerIndex page (permissionless)
 #[derive(Accounts)]
-pub struct Initialize<'info> {
-    /// Global root config
+pub struct ReindexOwner<'info> {
     #[account(
-        init,
-        payer = authority,
-        space = RootConfig::DISCRIMINATOR.len() + RootConfig::INIT_SPACE,
-        seeds = [b"root_config"],
-        bump
+        mut,
+        seeds = [
+            b"owner_index",
+            owner_index.owner.as_ref(),
+            owner_index.page.to_le_bytes().as_ref()
+        ],
+        bump = owner_index.bump
     )]
-    pub root_config: Account<'info, RootConfig>,
+    pub owner_index: Account<'info, OwnerIndex>,
 
-    /// Base registry config
+    /// Agent account of the asset (closed once deregistered)
+    /// CHECK: PDA verified by seeds, decoded in instruction when it exists
     #[account(
-        init,
-        payer = authority,
-        space = RegistryConfig::DISCRIMINATOR.len() + RegistryConfig::INIT_SPACE,
-        seeds = [b"registry_config", collection.key().as_ref()],
+        seeds = [b"agent", asset.key().as_ref()],
         bump
     )]
-    pub registry_config: Account<'info, RegistryConfig>,
+    pub agent_account: UncheckedAccount<'info>,
 
-    /// Base collection (created by CPI to Metaplex Core)
-    /// CHECK: Created by Metaplex Core CPI
-    #[account(mut)]
-    pub collection: Signer<'info>,
+    /// Asset the entry refers to
+    /// CHECK: Only used for PDA derivation
+    pub asset: UncheckedAccount<'info>,
+}
 
-    #[account(mut)]
-    pub authority: Signer<'info>,
+/// Set agent category tags (owner or operator)
+/// remaining_accounts: TagCounter PDAs for the current tags, then for the new tags
+#[derive(Accounts)]
+pub struct SetAgentTags<'info> {
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = AgentTags::DISCRIMINATOR.len() + AgentTags::INIT_SPACE,
+        seeds = [b"agent_tags", asset.key().as_ref()],
+        bump
+    )]
+    pub agent_tags: Account<'info, AgentTags>,
 
-    /// Program data account for upgrade authority verification
     #[account(
-        seeds = [crate::ID.as_ref()],
-        bump,
-        seeds::program = BPF_LOADER_UPGRADEABLE_ID,
-        constraint = program_data.upgrade_authority_address == Some(authority.key())
-            @ RegistryError::Unauthorized
+        seeds = [b"agent", asset.key().as_ref()],
+        bump = agent_account.bump,
+        constraint = !agent_account.frozen @ RegistryError::AgentFrozen,
     )]
-    pub program_data: Account<'info, ProgramData>,
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// Core asset - verifies ownership
+    /// CHECK: Ownership verified in instruction
+    #[account(
+        constraint = asset.key() == agent_account.asset @ RegistryError::InvalidAsset
+    )]
+    pub asset: UncheckedAccount<'info>,
+
+    /// Asset owner or approved operator (pays for new accounts)
+    #[account(mut)]
+    pub owner: Signer<'info>,
 
     pub system_program: Program<'info, System>,
 
-    /// Metaplex Core program
-    /// CHECK: Verified by address constraint
-    #[account(address = mpl_core::ID)]
-    pub mpl_core_program: UncheckedAccount<'info>,
+    /// Optional operator delegation (required when signer is not the Core owner)
+    pub agent_operator: Option<Account<'info, AgentOperator>>,
 }
 
-/// Register agent in the base collection
+/// Verify agent card content against the commitment (permissionless)
 #[derive(Accounts)]
-#[instruction(agent_uri: String)]
-pub struct Register<'info> {
-    /// Root config to validate base collection
+pub struct VerifyAgentCard<'info> {
     #[account(
-        seeds = [b"root_config"],
-        bump = root_config.bump,
-        constraint = root_config.base_collection == collection.key() @ RegistryError::InvalidCollection
+        seeds = [b"agent", asset.key().as_ref()],
+        bump = agent_account.bump,
     )]
-    pub root_config: Account<'info, RootConfig>,
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// CHECK: Seeds binding only
+    #[account(
+        constraint = asset.key() == agent_account.asset @ RegistryError::InvalidAsset
+    )]
+    pub asset: UncheckedAccount<'info>,
+
+    pub verifier: Signer<'info>,
+}
 
+/// Set agent URI (owner only)
+#[derive(Accounts)]
+pub struct SetAgentUri<'info> {
+    /// Registry config for this collection
     #[account(
         seeds = [b"registry_config", collection.key().as_ref()],
         bump = registry_config.bump
@@ -361,24 +435,296 @@ pub struct Register<'info> {
     pub registry_config: Account<'info, RegistryConfig>,
 
     #[account(
-        init,
-        payer = owner,
-        space = AgentAccount::DISCRIMINATOR.len() + AgentAccount::INIT_SPACE,
+        mut,
         seeds = [b"agent", asset.key().as_ref()],
-        bump
+        bump = agent_account.bump,
+        constraint = !agent_account.frozen @ RegistryError::AgentFrozen,
     )]
     pub agent_account: Account<'info, AgentAccount>,
 
-    /// New asset to create
-    /// CHECK: Created by Metaplex Core CPI
-    #[account(mut)]
-    pub asset: Signer<'info>,
-
-    /// Base collection
-    /// CHECK: Verified via root_config constraint
-    #[account(mut)]
-    pub collection: UncheckedAccount<'info>,
+    /// Core asset for URI update
+    /// CHECK: Ownership verified in instruction
+    #[account(
+        mut,
+        constraint = asset.key() == agent_account.asset @ RegistryError::InvalidAsset
+    )]
+    pub asset: UncheckedAccount<'info>,
 
+    /// Collection account (required by Core for assets in collection)
+    /// CHECK: Verified via registry_config constraint
+    #[account(
+        mut,
+        constraint = collection.key() == registry_config.collection @ RegistryError::InvalidCollection
+    )]
+    pub collection: UncheckedAccount<'info>,
+
+    /// Asset owner or approved operator (verified in instruction)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Metaplex Core program
+    /// CHECK: Verified by address constraint
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+
+    /// Optional operator delegation (required when signer is not the Core owner)
+    pub agent_operator: Option<Account<'info, AgentOperator>>,
+
+    /// Optional owner council (council-approved execution instead of owner/operator)
+    #[account(
+        seeds = [b"owner_council", asset.key().as_ref()],
+        bump = owner_council.bump
+    )]
+    pub owner_council: Option<Account<'info, OwnerCouncil>>,
+
+    /// Approved council proposal for this exact action (consumed on execution)
+    #[account(mut)]
+    pub council_proposal: Option<Account<'info, CouncilProposal>>,
+}
+
+/// Sync owner after Core transfer
+#[derive(Accounts)]
+pub struct SyncOwner<'info> {
+    #[account(
+        mut,
+        seeds = [b"agent", asset.key().as_ref()],
+        bump = agent_account.bump
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// Core asset - ownership is read from asset data
+    /// CHECK: Verified in instruction
+    #[account(
+        constraint = asset.key() == agent_account.asset @ RegistryError::InvalidAsset
+    )]
+    pub asset: UncheckedAccount<'info>,
+
+    /// Registry config (optional, provides the sync bounty)
+    #[account(
+        seeds = [b"registry_config", agent_account.collection.as_ref()],
+        bump = registry_config.bump
+    )]
+    pub registry_config: Option<Account<'info, RegistryConfig>>,
+
+    /// Registry treasury paying the bounty (optional)
+    #[account(
+        mut,
+        seeds = [b"treasury", agent_account.collection.as_ref()],
+        bump = treasury.bump
+    )]
+    pub treasury: Option<Account<'info, Treasury>>,
+
+    /// Bounty recipient (optional)
+    #[account(mut)]
+    pub cranker: Option<Signer<'info>>,
+
+    /// Previous owner's OwnerIndex page holding the asset (optional)
+    #[account(mut)]
+    pub from_owner_index: Option<Account<'info, OwnerIndex>>,
+
+    /// New owner's OwnerIndex page to add the asset to (optional)
+    #[account(mut)]
+    pub to_owner_index: Option<Account<'info, OwnerIndex>>,
+}
+
+/// Sync owner of a Token-2022 backed agent from the holder token account
+#[derive(Accounts)]
+pub struct SyncTokenOwner<'info> {
+    #[account(
+        mut,
+        seeds = [b"agent", asset.key().as_ref()],
+        bump = agent_account.bump,
+        constraint = agent_account.token_backed @ RegistryError::InvalidAsset
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// Token-2022 mint backing the agent
+    /// CHECK: Verified via agent_account constraint
+    #[account(
+        constraint = asset.key() == agent_account.asset @ RegistryError::InvalidAsset
+    )]
+    pub asset: UncheckedAccount<'info>,
+
+    /// Token account currently holding the agent NFT
+    #[account(
+        constraint = holder_token_account.mint == asset.key() @ RegistryError::InvalidTokenAccount,
+        constraint = holder_token_account.amount == 1 @ RegistryError::InvalidTokenAccount
+    )]
+    pub holder_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Previous owner's OwnerIndex page holding the asset (optional)
+    #[account(mut)]
+    pub from_owner_index: Option<Account<'info, OwnerIndex>>,
+
+    /// New owner's OwnerIndex page to add the asset to (optional)
+    #[account(mut)]
+    pub to_owner_index: Option<Account<'info, OwnerIndex>>,
+}
+
+/// Get owner of agent (cached value - may be stale)
+#[derive(Accounts)]
+pub struct OwnerOf<'info> {
+    #[account(
+        seeds = [b"agent", asset.key().as_ref()],
+        bump = agent_account.bump
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// Core asset (for PDA derivation)
+    /// CHECK: Used for PDA derivation
+    pub asset: UncheckedAccount<'info>,
+}
+
+/// Check whether an agent registration has expired
+#[derive(Accounts)]
+pub struct IsAgentExpired<'info> {
+    #[account(
+        seeds = [b"agent", asset.key().as_ref()],
+        bump = agent_account.bump
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// Core asset (for PDA derivation)
+    /// CHECK: Used for PDA derivation
+    pub asset: UncheckedAccount<'info>,
+}
+
+/// Read an agent record (returned as Borsh-encoded AgentView)
+#[derive(Accounts)]
+pub struct GetAgent<'info> {
+    #[account(
+        seeds = [b"agent", asset.key().as_ref()],
+        bump = agent_account.bump
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// Core asset (for PDA derivation)
+    /// CHECK: Used for PDA derivation
+    pub asset: UncheckedAccount<'info>,
+}
+
+/// Read a single metadata entry (returned as Borsh-encoded MetadataView)
+#[derive(Accounts)]
+#[instruction(key_hash: [u8; 16])]
+pub struct GetMetadata<'info> {
+    #[account(
+        seeds = [b"agent_meta", asset.key().as_ref(), key_hash.as_ref()],
+        bump = metadata_entry.bump
+    )]
+    pub metadata_entry: Account<'info, MetadataEntryPda>,
+
+    /// Core asset (for PDA derivation)
+    /// CHECK: Used for PDA derivation
+    pub asset: UncheckedAccount<'info>,
+}
+
+/// Get authoritative Core owner (reads live from Metaplex Core)
+#[derive(Accounts)]
+pub struct CoreOwnerOf<'info> {
+    /// Core asset to read owner from
+    /// CHECK: Validated in instruction (must be MPL Core owned)
+    pub asset: UncheckedAccount<'info>,
+
+    /// Agent account (optional, enables stale-owner detection)
+    #[account(
+        seeds = [b"agent", asset.key().as_ref()],
+        bump = agent_account.bump
+    )]
+    pub agent_account: Option<Account<'info, AgentAccount>>,
+}
+
+/// Transfer agent with automatic owner sync
+/// Automatically resets agent_wallet to None on transfer
+#[derive(Accounts)]
+pub struct TransferAgent<'info> {
+    #[account(
+        mut,
+        seeds = [b"agent", asset.key().as_ref()],
+        bump = agent_account.bump,
+        constraint = !agent_account.soulbound @ RegistryError::AgentSoulbound,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// Core asset to transfer
+    /// CHECK: Verified via agent_account constraint
+    #[account(
+        mut,
+        constraint = asset.key() == agent_account.asset @ RegistryError::InvalidAsset
+    )]
+    pub asset: UncheckedAccount<'info>,
+
+    /// Collection (required by Core transfer)
+    /// CHECK: Verified by Core CPI
+    #[account(mut)]
+    pub collection: UncheckedAccount<'info>,
+
+    /// Current owner (must sign)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// New owner receiving the asset
+    /// CHECK: Can be any account
+    pub new_owner: UncheckedAccount<'info>,
+
+    /// Metaplex Core program
+    /// CHECK: Verified by address constraint
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+
+    /// Collection transfer gate (required once the collection has one, even when disabled)
+    #[account(
+        mut,
+        seeds = [b"transfer_gate", agent_account.collection.as_ref()],
+        bump = transfer_gate.bump
+    )]
+    pub transfer_gate: Option<Account<'info, TransferGate>>,
+
+    /// Previous owner's OwnerIndex page holding the asset (optional)
+    #[account(mut)]
+    pub from_owner_index: Option<Account<'info, OwnerIndex>>,
+
+    /// New owner's OwnerIndex page to add the asset to (optional)
+    #[account(mut)]
+    pub to_owner_index: Option<Account<'info, OwnerIndex>>,
+}
+
+/// Configure, update or disable the owner council (Core owner only)
+#[derive(Accounts)]
+pub struct SetOwnerCouncil<'info> {
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = OwnerCouncil::DISCRIMINATOR.len() + OwnerCouncil::INIT_SPACE,
+        seeds = [b"owner_council", asset.key().as_ref()],
+        bump
+    )]
+    pub owner_council: Account<'info, OwnerCouncil>,
+
+    #[account(
+        seeds = [b"agent", asset.key().as_ref()],
+        bump = agent_account.bump,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// Core asset (TransferDelegate plugin managed in instruction)
+    /// CHECK: Verified via agent_account constraint
+    #[account(
+        mut,
+        constraint = asset.key() == agent_account.asset @ RegistryError::InvalidAsset
+    )]
+    pub asset: UncheckedAccount<'info>,
+
+    /// Collection (required by Core plugin CPI)
+    /// CHECK: Verified via agent_account constraint
+    #[account(
+        mut,
+        constraint = collection.key() == agent_account.collection @ RegistryError::InvalidCollection
+    )]
+    pub collection: UncheckedAccount<'info>,
+
+    /// Core owner (verified in instruction)
     #[account(mut)]
     pub owner: Signer<'info>,
 
@@ -390,6 +736,1902 @@ pub struct Register<'info> {
     pub mpl_core_program: UncheckedAccount<'info>,
 }
 
+/// Open a council proposal (council member only)
+#[derive(Accounts)]
+pub struct ProposeCouncilAction<'info> {
+    #[account(
+        mut,
+        seeds = [b"owner_council", asset.key().as_ref()],
+        bump = owner_council.bump
+    )]
+    pub owner_council: Account<'info, OwnerCouncil>,
+
+    #[account(
+        init,
+        payer = member,
+        space = CouncilProposal::DISCRIMINATOR.len() + CouncilProposal::INIT_SPACE,
+        seeds = [
+            b"council_proposal",
+            asset.key().as_ref(),
+            owner_council.proposal_count.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub council_proposal: Account<'info, CouncilProposal>,
+
+    /// CHECK: Only used as PDA seed
+    pub asset: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub member: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Approve a council proposal (council member only)
+#[derive(Accounts)]
+pub struct ApproveCouncilAction<'info> {
+    #[account(
+        seeds = [b"owner_council", asset.key().as_ref()],
+        bump = owner_council.bump
+    )]
+    pub owner_council: Account<'info, OwnerCouncil>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"council_proposal",
+            asset.key().as_ref(),
+            council_proposal.proposal_id.to_le_bytes().as_ref()
+        ],
+        bump = council_proposal.bump
+    )]
+    pub council_proposal: Account<'info, CouncilProposal>,
+
+    /// CHECK: Only used as PDA seed
+    pub asset: UncheckedAccount<'info>,
+
+    pub member: Signer<'info>,
+}
+
+/// Execute a council-approved transfer (any council member)
+/// The OwnerCouncil PDA signs as the asset's Core TransferDelegate
+#[derive(Accounts)]
+pub struct CouncilTransferAgent<'info> {
+    #[account(
+        seeds = [b"owner_council", asset.key().as_ref()],
+        bump = owner_council.bump
+    )]
+    pub owner_council: Account<'info, OwnerCouncil>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"council_proposal",
+            asset.key().as_ref(),
+            council_proposal.proposal_id.to_le_bytes().as_ref()
+        ],
+        bump = council_proposal.bump
+    )]
+    pub council_proposal: Account<'info, CouncilProposal>,
+
+    #[account(
+        mut,
+        seeds = [b"agent", asset.key().as_ref()],
+        bump = agent_account.bump,
+        constraint = !agent_account.soulbound @ RegistryError::AgentSoulbound,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// Core asset to transfer
+    /// CHECK: Verified via agent_account constraint
+    #[account(
+        mut,
+        constraint = asset.key() == agent_account.asset @ RegistryError::InvalidAsset
+    )]
+    pub asset: UncheckedAccount<'info>,
+
+    /// Collection (required by Core transfer)
+    /// CHECK: Verified via agent_account constraint
+    #[account(
+        mut,
+        constraint = collection.key() == agent_account.collection @ RegistryError::InvalidCollection
+    )]
+    pub collection: UncheckedAccount<'info>,
+
+    /// Executing council member (pays Core fees)
+    #[account(mut)]
+    pub member: Signer<'info>,
+
+    /// New owner receiving the asset
+    /// CHECK: Must match the approved proposal (verified in instruction)
+    pub new_owner: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Metaplex Core program
+    /// CHECK: Verified by address constraint
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+
+    /// Collection transfer gate (required once the collection has one, even when disabled)
+    #[account(
+        mut,
+        seeds = [b"transfer_gate", agent_account.collection.as_ref()],
+        bump = transfer_gate.bump
+    )]
+    pub transfer_gate: Option<Account<'info, TransferGate>>,
+
+    /// Previous owner's OwnerIndex page holding the asset (optional)
+    #[account(mut)]
+    pub from_owner_index: Option<Account<'info, OwnerIndex>>,
+
+    /// New owner's OwnerIndex page to add the asset to (optional)
+    #[account(mut)]
+    pub to_owner_index: Option<Account<'info, OwnerIndex>>,
+}
+
+/// Propose a two-step transfer (owner only)
+/// Installs a Core TransferDelegate plugin held by the PendingTransfer PDA
+#[derive(Accounts)]
+#[instruction(new_owner: Pubkey)]
+pub struct ProposeTransfer<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = PendingTransfer::DISCRIMINATOR.len() + PendingTransfer::INIT_SPACE,
+        seeds = [b"pending_transfer", asset.key().as_ref()],
+        bump
+    )]
+    pub pending_transfer: Account<'info, PendingTransfer>,
+
+    #[account(
+        seeds = [b"agent", asset.key().as_ref()],
+        bump = agent_account.bump,
+        constraint = !agent_account.soulbound @ RegistryError::AgentSoulbound,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// Core asset to transfer
+    /// CHECK: Verified via agent_account constraint
+    #[account(
+        mut,
+        constraint = asset.key() == agent_account.asset @ RegistryError::InvalidAsset
+    )]
+    pub asset: UncheckedAccount<'info>,
+
+    /// Collection (required by Core plugin CPI)
+    /// CHECK: Verified via agent_account constraint
+    #[account(
+        mut,
+        constraint = collection.key() == agent_account.collection @ RegistryError::InvalidCollection
+    )]
+    pub collection: UncheckedAccount<'info>,
+
+    /// Current owner (must sign)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Metaplex Core program
+    /// CHECK: Verified by address constraint
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+}
+
+/// Accept a pending transfer (recipient only)
+#[derive(Accounts)]
+pub struct AcceptTransfer<'info> {
+    #[account(
+        mut,
+        close = proposer,
+        seeds = [b"pending_transfer", asset.key().as_ref()],
+        bump = pending_transfer.bump,
+        constraint = pending_transfer.to == new_owner.key() @ RegistryError::Unauthorized,
+        constraint = pending_transfer.from == proposer.key() @ RegistryError::Unauthorized
+    )]
+    pub pending_transfer: Account<'info, PendingTransfer>,
+
+    #[account(
+        mut,
+        seeds = [b"agent", asset.key().as_ref()],
+        bump = agent_account.bump
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// Core asset to transfer
+    /// CHECK: Verified via agent_account constraint
+    #[account(
+        mut,
+        constraint = asset.key() == agent_account.asset @ RegistryError::InvalidAsset
+    )]
+    pub asset: UncheckedAccount<'info>,
+
+    /// Collection (required by Core transfer)
+    /// CHECK: Verified via agent_account constraint
+    #[account(
+        mut,
+        constraint = collection.key() == agent_account.collection @ RegistryError::InvalidCollection
+    )]
+    pub collection: UncheckedAccount<'info>,
+
+    /// Recipient accepting the transfer
+    #[account(mut)]
+    pub new_owner: Signer<'info>,
+
+    /// Original proposer (receives PendingTransfer rent back)
+    /// CHECK: Verified against pending_transfer.from
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Metaplex Core program
+    /// CHECK: Verified by address constraint
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+
+    /// Collection transfer gate (required once the collection has one, even when disabled)
+    #[account(
+        mut,
+        seeds = [b"transfer_gate", agent_account.collection.as_ref()],
+        bump = transfer_gate.bump
+    )]
+    pub transfer_gate: Option<Account<'info, TransferGate>>,
+
+    /// Previous owner's OwnerIndex page holding the asset (optional)
+    #[account(mut)]
+    pub from_owner_index: Option<Account<'info, OwnerIndex>>,
+
+    /// New owner's OwnerIndex page to add the asset to (optional)
+    #[account(mut)]
+    pub to_owner_index: Option<Account<'info, OwnerIndex>>,
+}
+
+/// Cancel a pending transfer (proposer only)
+#[derive(Accounts)]
+pub struct CancelTransfer<'info> {
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"pending_transfer", asset.key().as_ref()],
+        bump = pending_transfer.bump,
+        constraint = pending_transfer.from == owner.key() @ RegistryError::Unauthorized
+    )]
+    pub pending_transfer: Account<'info, PendingTransfer>,
+
+    #[account(
+        seeds = [b"agent", asset.key().as_ref()],
+        bump = agent_account.bump
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// Core asset
+    /// CHECK: Verified via agent_account constraint
+    #[account(
+        mut,
+        constraint = asset.key() == agent_account.asset @ RegistryError::InvalidAsset
+    )]
+    pub asset: UncheckedAccount<'info>,
+
+    /// Collection (required by Core plugin CPI)
+    /// CHECK: Verified via agent_account constraint
+    #[account(
+        mut,
+        constraint = collection.key() == agent_account.collection @ RegistryError::InvalidCollection
+    )]
+    pub collection: UncheckedAccount<'info>,
+
+    /// Proposer (receives rent back)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Metaplex Core program
+    /// CHECK: Verified by address constraint
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+}
+
+/// Set agent wallet with Ed25519 signature verification
+/// Transaction must include Ed25519Program verify instruction before this one
+/// Wallet is stored directly in AgentAccount (no separate PDA = no rent cost)
+#[derive(Accounts)]
+#[instruction(new_wallet: Pubkey, deadline: i64)]
+pub struct SetAgentWallet<'info> {
+    /// Agent owner (must be Core asset owner)
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"agent", asset.key().as_ref()],
+        bump = agent_account.bump,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// Core asset - ownership verified in instruction
+    /// CHECK: Verified via agent_account constraint and in instruction
+    #[account(
+        constraint = asset.key() == agent_account.asset @ RegistryError::InvalidAsset
+    )]
+    pub asset: UncheckedAccount<'info>,
+
+    /// Instructions sysvar for Ed25519 signature introspection
+    /// CHECK: Verified by address constraint
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+}
+
+/// Open an endpoint ownership challenge for a metadata entry (owner or operator)
+#[derive(Accounts)]
+#[instruction(key_hash: [u8; 16])]
+pub struct RequestEndpointChallenge<'info> {
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = EndpointChallenge::DISCRIMINATOR.len() + EndpointChallenge::INIT_SPACE,
+        seeds = [b"endpoint_challenge", asset.key().as_ref(), key_hash.as_ref()],
+        bump
+    )]
+    pub endpoint_challenge: Account<'info, EndpointChallenge>,
+
+    /// Metadata entry holding the endpoint
+    #[account(
+        seeds = [b"agent_meta", asset.key().as_ref(), key_hash.as_ref()],
+        bump = metadata_entry.bump,
+        constraint = metadata_entry.to_account_info().data_len() == MetadataEntryPda::SPACE
+            @ RegistryError::MetadataEntryNotMigrated
+    )]
+    pub metadata_entry: Account<'info, MetadataEntryPda>,
+
+    #[account(
+        seeds = [b"agent", asset.key().as_ref()],
+        bump = agent_account.bump,
+        constraint = !agent_account.frozen @ RegistryError::AgentFrozen,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// Core asset - verifies ownership
+    /// CHECK: Ownership verified in instruction
+    #[account(
+        constraint = asset.key() == agent_account.asset @ RegistryError::InvalidAsset
+    )]
+    pub asset: UncheckedAccount<'info>,
+
+    /// Asset owner or approved operator (pays challenge rent)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Optional operator delegation (required when signer is not the Core owner)
+    pub agent_operator: Option<Account<'info, AgentOperator>>,
+}
+
+/// Answer an endpoint challenge (permissionless)
+/// Transaction must include Ed25519Program verify instruction before this one
+#[derive(Accounts)]
+pub struct VerifyEndpoint<'info> {
+    #[account(
+        mut,
+        close = requester,
+        seeds = [
+            b"endpoint_challenge",
+            endpoint_challenge.asset.as_ref(),
+            endpoint_challenge.key_hash.as_ref()
+        ],
+        bump = endpoint_challenge.bump,
+        has_one = requester @ RegistryError::Unauthorized
+    )]
+    pub endpoint_challenge: Account<'info, EndpointChallenge>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"agent_meta",
+            endpoint_challenge.asset.as_ref(),
+            endpoint_challenge.key_hash.as_ref()
+        ],
+        bump = metadata_entry.bump,
+        constraint = metadata_entry.to_account_info().data_len() == MetadataEntryPda::SPACE
+            @ RegistryError::MetadataEntryNotMigrated
+    )]
+    pub metadata_entry: Account<'info, MetadataEntryPda>,
+
+    /// Original requester (receives challenge rent back)
+    /// CHECK: Verified against endpoint_challenge.requester
+    #[account(mut)]
+    pub requester: UncheckedAccount<'info>,
+
+    /// Instructions sysvar for Ed25519 signature introspection
+    /// CHECK: Verified by address constraint
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+}
+
+/// Bind a DID to an agent with Ed25519 proof from the DID verification key
+/// Transaction must include Ed25519Program verify instruction before this one
+#[derive(Accounts)]
+pub struct SetDid<'info> {
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = AgentDid::DISCRIMINATOR.len() + AgentDid::INIT_SPACE,
+        seeds = [b"agent_did", asset.key().as_ref()],
+        bump
+    )]
+    pub agent_did: Account<'info, AgentDid>,
+
+    #[account(
+        seeds = [b"agent", asset.key().as_ref()],
+        bump = agent_account.bump,
+        constraint = !agent_account.frozen @ RegistryError::AgentFrozen,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// Core asset - ownership verified in instruction
+    /// CHECK: Verified via agent_account constraint and in instruction
+    #[account(
+        constraint = asset.key() == agent_account.asset @ RegistryError::InvalidAsset
+    )]
+    pub asset: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Instructions sysvar for Ed25519 signature introspection
+    /// CHECK: Verified by address constraint
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Remove a DID binding (owner only)
+#[derive(Accounts)]
+pub struct ClearDid<'info> {
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"agent_did", asset.key().as_ref()],
+        bump = agent_did.bump
+    )]
+    pub agent_did: Account<'info, AgentDid>,
+
+    /// Core asset - ownership verified in instruction
+    /// CHECK: Ownership verified in instruction, PDA derivation binds the DID
+    pub asset: UncheckedAccount<'info>,
+
+    /// Current owner (receives rent back)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+/// Add or update Core plugins on the agent asset (owner only)
+#[derive(Accounts)]
+pub struct ManageAgentPlugin<'info> {
+    /// Registry config (collection update authority, signs authority-managed plugins)
+    #[account(
+        seeds = [b"registry_config", collection.key().as_ref()],
+        bump = registry_config.bump
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
+    #[account(
+        seeds = [b"agent", asset.key().as_ref()],
+        bump = agent_account.bump,
+        constraint = !agent_account.frozen @ RegistryError::AgentFrozen,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// Core asset
+    /// CHECK: Verified via agent_account constraint, ownership verified in instruction
+    #[account(
+        mut,
+        constraint = asset.key() == agent_account.asset @ RegistryError::InvalidAsset
+    )]
+    pub asset: UncheckedAccount<'info>,
+
+    /// Collection (required by Core plugin CPI)
+    /// CHECK: Verified via agent_account constraint
+    #[account(
+        mut,
+        constraint = collection.key() == agent_account.collection @ RegistryError::InvalidCollection
+    )]
+    pub collection: UncheckedAccount<'info>,
+
+    /// Current owner (pays plugin rent)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Metaplex Core program
+    /// CHECK: Verified by address constraint
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+}
+
+/// Grant an operator delegation (owner only)
+#[derive(Accounts)]
+pub struct GrantOperator<'info> {
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = AgentOperator::DISCRIMINATOR.len() + AgentOperator::INIT_SPACE,
+        seeds = [b"agent_operator", asset.key().as_ref(), operator.key().as_ref()],
+        bump
+    )]
+    pub agent_operator: Account<'info, AgentOperator>,
+
+    #[account(
+        seeds = [b"agent", asset.key().as_ref()],
+        bump = agent_account.bump,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// Core asset - ownership verified in instruction
+    /// CHECK: Verified via agent_account constraint and in instruction
+    #[account(
+        constraint = asset.key() == agent_account.asset @ RegistryError::InvalidAsset
+    )]
+    pub asset: UncheckedAccount<'info>,
+
+    /// Operator key receiving the delegation
+    /// CHECK: Can be any account
+    pub operator: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Revoke an operator delegation (current owner only)
+#[derive(Accounts)]
+pub struct RevokeOperator<'info> {
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"agent_operator", asset.key().as_ref(), agent_operator.operator.as_ref()],
+        bump = agent_operator.bump
+    )]
+    pub agent_operator: Account<'info, AgentOperator>,
+
+    /// Core asset - ownership verified in instruction
+    /// CHECK: Ownership verified in instruction, PDA derivation binds the delegation
+    pub asset: UncheckedAccount<'info>,
+
+    /// Current owner (receives rent back)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+/// Set agent wallet with the wallet co-signing the transaction
+/// Alternative to the Ed25519 proof flow when the wallet can sign directly
+#[derive(Accounts)]
+pub struct SetAgentWalletCosigned<'info> {
+    /// Agent owner (must be Core asset owner)
+    pub owner: Signer<'info>,
+
+    /// Wallet being registered (proves key control by signing)
+    pub new_wallet: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"agent", asset.key().as_ref()],
+        bump = agent_account.bump,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// Core asset - ownership verified in instruction
+    /// CHECK: Verified via agent_account constraint and in instruction
+    #[account(
+        constraint = asset.key() == agent_account.asset @ RegistryError::InvalidAsset
+    )]
+    pub asset: UncheckedAccount<'info>,
+}
+
+/// Set canonical collection pointer in AgentAccount (first-write-wins)
+#[derive(Accounts)]
+#[instruction(col: String)]
+pub struct SetCollectionPointer<'info> {
+    #[account(
+        mut,
+        seeds = [b"agent", asset.key().as_ref()],
+        bump = agent_account.bump,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// Core asset - ownership verified in instruction
+    /// CHECK: Verified via agent_account constraint and in instruction
+    #[account(
+        constraint = asset.key() == agent_account.asset @ RegistryError::InvalidAsset
+    )]
+    pub asset: UncheckedAccount<'info>,
+
+    /// Creator signer (must match immutable AgentAccount.creator)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+/// Set parent link in AgentAccount (first-write-wins)
+#[derive(Accounts)]
+#[instruction(parent_asset: Pubkey)]
+pub struct SetParentAsset<'info> {
+    #[account(
+        mut,
+        seeds = [b"agent", asset.key().as_ref()],
+        bump = agent_account.bump,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// Core child asset - ownership verified in instruction
+    /// CHECK: Verified via agent_account constraint and in instruction
+    #[account(
+        constraint = asset.key() == agent_account.asset @ RegistryError::InvalidAsset
+    )]
+    pub asset: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"agent", parent_asset.as_ref()],
+        bump = parent_agent_account.bump,
+    )]
+    pub parent_agent_account: Account<'info, AgentAccount>,
+
+    /// Core parent asset account
+    /// CHECK: Liveness/type verified in instruction
+    #[account(
+        constraint = parent_asset_account.key() == parent_agent_account.asset @ RegistryError::InvalidAsset
+    )]
+    pub parent_asset_account: UncheckedAccount<'info>,
+
+    /// Current owner of child asset
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+// ============================================================================
+// Single Collection Architecture
+// ============================================================================
+
+/// Initialize the registry with root config and base collection
+/// Only upgrade authority can call this (prevents front-running)
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    /// Global root config
+    #[account(
+        init,
+        payer = authority,
+        space = RootConfig::DISCRIMINATOR.len() + RootConfig::INIT_SPACE,
+        seeds = [b"root_config"],
+        bump
+    )]
+    pub root_config: Account<'info, RootConfig>,
+
+    /// Base registry config
+    #[account(
+        init,
+        payer = authority,
+        space = RegistryConfig::DISCRIMINATOR.len() + RegistryConfig::INIT_SPACE,
+        seeds = [b"registry_config", collection.key().as_ref()],
+        bump
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
+    /// Base collection (created by CPI to Metaplex Core)
+    /// CHECK: Created by Metaplex Core CPI
+    #[account(mut)]
+    pub collection: Signer<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Program data account for upgrade authority verification
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = BPF_LOADER_UPGRADEABLE_ID,
+        constraint = program_data.upgrade_authority_address == Some(authority.key())
+            @ RegistryError::Unauthorized
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Metaplex Core program
+    /// CHECK: Verified by address constraint
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+}
+
+/// Publish deployment feature flags (root authority only)
+#[derive(Accounts)]
+pub struct SetRegistryFeatures<'info> {
+    #[account(
+        seeds = [b"root_config"],
+        bump = root_config.bump,
+        has_one = authority @ RegistryError::Unauthorized
+    )]
+    pub root_config: Account<'info, RootConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = RegistryFeatures::DISCRIMINATOR.len() + RegistryFeatures::INIT_SPACE,
+        seeds = [b"registry_features"],
+        bump
+    )]
+    pub registry_features: Account<'info, RegistryFeatures>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Create an additional protocol-operated base registry (root authority only)
+#[derive(Accounts)]
+pub struct CreateBaseRegistry<'info> {
+    /// Root config (allocates the registry id)
+    #[account(
+        mut,
+        seeds = [b"root_config"],
+        bump = root_config.bump,
+        has_one = authority @ RegistryError::Unauthorized
+    )]
+    pub root_config: Account<'info, RootConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = RegistryConfig::DISCRIMINATOR.len() + RegistryConfig::INIT_SPACE,
+        seeds = [b"registry_config", collection.key().as_ref()],
+        bump
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
+    /// New registry collection (created by CPI to Metaplex Core)
+    #[account(mut)]
+    pub collection: Signer<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Metaplex Core program
+    /// CHECK: Verified by address constraint
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+}
+
+/// Create an organization-operated user registry (permissionless)
+#[derive(Accounts)]
+pub struct CreateUserRegistry<'info> {
+    /// Root config (allocates the registry id)
+    #[account(
+        mut,
+        seeds = [b"root_config"],
+        bump = root_config.bump
+    )]
+    pub root_config: Account<'info, RootConfig>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = RegistryConfig::DISCRIMINATOR.len() + RegistryConfig::INIT_SPACE,
+        seeds = [b"registry_config", collection.key().as_ref()],
+        bump
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
+    /// New registry collection (created by CPI to Metaplex Core)
+    #[account(mut)]
+    pub collection: Signer<'info>,
+
+    /// Registry owner (becomes RegistryConfig.authority)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Metaplex Core program
+    /// CHECK: Verified by address constraint
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+}
+
+/// Turn an existing Core collection into a user registry (collection update authority)
+/// The collection's update authority is delegated to the new registry config PDA
+#[derive(Accounts)]
+pub struct ApproveCollection<'info> {
+    /// Root config (allocates the registry id)
+    #[account(
+        mut,
+        seeds = [b"root_config"],
+        bump = root_config.bump
+    )]
+    pub root_config: Account<'info, RootConfig>,
+
+    #[account(
+        init,
+        payer = collection_authority,
+        space = RegistryConfig::DISCRIMINATOR.len() + RegistryConfig::INIT_SPACE,
+        seeds = [b"registry_config", collection.key().as_ref()],
+        bump
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
+    /// Existing Core collection
+    /// CHECK: Owner and update authority verified in instruction
+    #[account(mut)]
+    pub collection: UncheckedAccount<'info>,
+
+    /// Current collection update authority (becomes RegistryConfig.authority)
+    #[account(mut)]
+    pub collection_authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Metaplex Core program
+    /// CHECK: Verified by address constraint
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+}
+
+/// Register agent in a registry collection (base or user registry)
+#[derive(Accounts)]
+#[instruction(agent_uri: String)]
+pub struct Register<'info> {
+    /// Root config (registry_config seeds validate the collection)
+    #[account(
+        seeds = [b"root_config"],
+        bump = root_config.bump
+    )]
+    pub root_config: Account<'info, RootConfig>,
+
+    /// Registry config (allocates the agent id from its own range)
+    #[account(
+        mut,
+        seeds = [b"registry_config", collection.key().as_ref()],
+        bump = registry_config.bump
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = AgentAccount::DISCRIMINATOR.len() + AgentAccount::INIT_SPACE,
+        seeds = [b"agent", asset.key().as_ref()],
+        bump
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// Reverse index for the newly allocated agent id
+    ///
+    /// Two registrations in the same collection built against the same next_agent_id race
+    /// for this PDA; the loser fails ConstraintSeeds and is resubmitted with a fresh read.
+    #[account(
+        init,
+        payer = payer,
+        space = AgentIdIndex::DISCRIMINATOR.len() + AgentIdIndex::INIT_SPACE,
+        seeds = [b"agent_id", registry_config.next_agent_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub agent_id_index: Account<'info, AgentIdIndex>,
+
+    /// New asset to create
+    /// CHECK: Created by Metaplex Core CPI
+    #[account(mut)]
+    pub asset: Signer<'info>,
+
+    /// Registry collection
+    /// CHECK: Verified via registry_config seeds
+    #[account(mut)]
+    pub collection: UncheckedAccount<'info>,
+
+    /// Agent owner (receives the asset; pays ATOM stats rent when initialized here)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// Pays rent, registration fee and bond (may be the owner or a sponsor)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Metaplex Core program
+    /// CHECK: Verified by address constraint
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+
+    /// Registry treasury (required when registration_fee > 0)
+    #[account(mut)]
+    pub treasury: Option<Account<'info, Treasury>>,
+
+    // === OPTIONAL: initialize ATOM stats in the same instruction ===
+    // If omitted, stats must be initialized later by the owner
+
+    /// AtomConfig PDA (owned by atom-engine)
+    /// CHECK: Validated by atom-engine program
+    pub atom_config: Option<UncheckedAccount<'info>>,
+
+    /// AtomStats PDA to create
+    /// CHECK: PDA verified in instruction, initialized by atom-engine
+    #[account(mut)]
+    pub atom_stats: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: ATOM Engine program ID (verified in instruction)
+    pub atom_engine_program: Option<UncheckedAccount<'info>>,
+
+    /// Owner's OwnerIndex page to add the asset to (optional)
+    #[account(mut)]
+    pub owner_index: Option<Account<'info, OwnerIndex>>,
+
+    // === OPTIONAL: register_with_referral only ===
+
+    /// Referral PDA to create: ["referral", asset]
+    /// CHECK: PDA verified and initialized in instruction
+    #[account(mut)]
+    pub referral: Option<UncheckedAccount<'info>>,
+
+    /// Referrer receiving the fee share
+    /// CHECK: Must match the referrer argument (verified in instruction)
+    #[account(mut)]
+    pub referrer: Option<UncheckedAccount<'info>>,
+}
+
+/// Upgrade a legacy AgentAccount layout (permissionless)
+#[derive(Accounts)]
+pub struct MigrateAgent<'info> {
+    /// Agent account in a previous layout
+    /// CHECK: PDA verified by seeds, layout decoded in instruction
+    #[account(
+        mut,
+        seeds = [b"agent", asset.key().as_ref()],
+        bump,
+        owner = crate::ID @ RegistryError::UnsupportedAgentLayout
+    )]
+    pub agent_account: UncheckedAccount<'info>,
+
+    /// Asset the agent account belongs to
+    /// CHECK: Only used for PDA derivation
+    pub asset: UncheckedAccount<'info>,
+
+    /// Registry config of the agent's collection (allocates the agent id)
+    #[account(
+        mut,
+        seeds = [b"registry_config", registry_config.collection.as_ref()],
+        bump = registry_config.bump
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = AgentIdIndex::DISCRIMINATOR.len() + AgentIdIndex::INIT_SPACE,
+        seeds = [b"agent_id", registry_config.next_agent_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub agent_id_index: Account<'info, AgentIdIndex>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Upgrade the v0.6.0 RootConfig layout (permissionless)
+#[derive(Accounts)]
+pub struct MigrateRootConfig<'info> {
+    /// Root config in the v0.6.0 layout
+    /// CHECK: PDA verified by seeds, layout decoded in instruction
+    #[account(
+        mut,
+        seeds = [b"root_config"],
+        bump,
+        owner = crate::ID @ RegistryError::UnsupportedConfigLayout
+    )]
+    pub root_config: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Upgrade a v0.6.0 MetadataEntryPda layout (permissionless)
+#[derive(Accounts)]
+#[instruction(key_hash: [u8; 16])]
+pub struct MigrateMetadataEntry<'info> {
+    /// Metadata entry in the v0.6.0 layout
+    /// CHECK: PDA verified by seeds, layout decoded in instruction
+    #[account(
+        mut,
+        seeds = [b"agent_meta", asset.key().as_ref(), key_hash.as_ref()],
+        bump,
+        owner = crate::ID @ RegistryError::UnsupportedMetadataLayout
+    )]
+    pub metadata_entry: UncheckedAccount<'info>,
+
+    /// Asset the entry belongs to
+    /// CHECK: Only used for PDA derivation
+    pub asset: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Upgrade a v0.6.0 RegistryConfig layout (permissionless)
+#[derive(Accounts)]
+pub struct MigrateRegistryConfig<'info> {
+    /// Registry config in the v0.6.0 layout
+    /// CHECK: PDA verified by seeds, layout decoded in instruction
+    #[account(
+        mut,
+        seeds = [b"registry_config", collection.key().as_ref()],
+        bump,
+        owner = crate::ID @ RegistryError::UnsupportedConfigLayout
+    )]
+    pub registry_config: UncheckedAccount<'info>,
+
+    /// Collection the registry config belongs to
+    /// CHECK: Only used for PDA derivation
+    pub collection: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Rebind an agent (and its agent_id) to a new Core asset
+/// Owner of both assets signs, co-signed by the current registry authority
+#[derive(Accounts)]
+pub struct ReplaceAsset<'info> {
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"agent", old_asset.key().as_ref()],
+        bump = old_agent_account.bump,
+        constraint = !old_agent_account.soulbound @ RegistryError::AgentSoulbound,
+    )]
+    pub old_agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = AgentAccount::DISCRIMINATOR.len() + AgentAccount::INIT_SPACE,
+        seeds = [b"agent", new_asset.key().as_ref()],
+        bump
+    )]
+    pub new_agent_account: Account<'info, AgentAccount>,
+
+    /// Currently bound asset (may already be burned)
+    /// CHECK: Verified via old_agent_account constraint and in instruction
+    #[account(
+        constraint = old_asset.key() == old_agent_account.asset @ RegistryError::InvalidAsset
+    )]
+    pub old_asset: UncheckedAccount<'info>,
+
+    /// Core asset to bind
+    /// CHECK: Ownership and collection verified in instruction
+    pub new_asset: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"agent_id", old_agent_account.agent_id.to_le_bytes().as_ref()],
+        bump = agent_id_index.bump
+    )]
+    pub agent_id_index: Account<'info, AgentIdIndex>,
+
+    /// Registry config of the current collection (authority co-signs)
+    #[account(
+        mut,
+        seeds = [b"registry_config", old_agent_account.collection.as_ref()],
+        bump = registry_config.bump,
+        has_one = authority @ RegistryError::Unauthorized
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
+    /// Registry config of the new asset's collection (same account when unchanged)
+    #[account(
+        mut,
+        seeds = [b"registry_config", new_registry_config.collection.as_ref()],
+        bump = new_registry_config.bump
+    )]
+    pub new_registry_config: Account<'info, RegistryConfig>,
+
+    /// Owner of both assets (pays the new AgentAccount, receives the old rent)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Deregister agent: burn the Core asset (if still live), close AgentAccount and AgentIdIndex
+/// Rent is returned to the owner
+#[derive(Accounts)]
+pub struct Deregister<'info> {
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"agent", asset.key().as_ref()],
+        bump = agent_account.bump,
+        constraint = !agent_account.frozen @ RegistryError::AgentFrozen,
+        constraint = agent_account.status != AgentAccount::STATUS_SUSPENDED
+            @ RegistryError::AgentSuspended,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// Reverse index of the agent id (closed with the agent)
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"agent_id", agent_account.agent_id.to_le_bytes().as_ref()],
+        bump = agent_id_index.bump
+    )]
+    pub agent_id_index: Account<'info, AgentIdIndex>,
+
+    /// Core asset to burn (or already burned)
+    /// CHECK: Verified via agent_account constraint and in instruction
+    #[account(
+        mut,
+        constraint = asset.key() == agent_account.asset @ RegistryError::InvalidAsset
+    )]
+    pub asset: UncheckedAccount<'info>,
+
+    /// Collection (required by Core burn)
+    /// CHECK: Verified via agent_account constraint
+    #[account(
+        mut,
+        constraint = collection.key() == agent_account.collection @ RegistryError::InvalidCollection
+    )]
+    pub collection: UncheckedAccount<'info>,
+
+    /// Agent owner (must sign, receives rent back)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Metaplex Core program
+    /// CHECK: Verified by address constraint
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+
+    /// Registry config (decrements total_agents; signs the soulbound unfreeze)
+    #[account(
+        mut,
+        seeds = [b"registry_config", collection.key().as_ref()],
+        bump = registry_config.bump
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
+    /// Owner's OwnerIndex page holding the asset (optional)
+    #[account(mut)]
+    pub owner_index: Option<Account<'info, OwnerIndex>>,
+
+    /// Registry treasury (required when the asset was already burned; receives the rent)
+    #[account(
+        mut,
+        seeds = [b"treasury", collection.key().as_ref()],
+        bump = treasury.bump
+    )]
+    pub treasury: Option<Account<'info, Treasury>>,
+}
+
+/// Freeze or unfreeze an agent (registry authority only)
+#[derive(Accounts)]
+pub struct FreezeAgent<'info> {
+    #[account(
+        mut,
+        seeds = [b"agent", asset.key().as_ref()],
+        bump = agent_account.bump
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// Core asset (for PDA derivation)
+    /// CHECK: Verified via agent_account constraint
+    #[account(
+        constraint = asset.key() == agent_account.asset @ RegistryError::InvalidAsset
+    )]
+    pub asset: UncheckedAccount<'info>,
+
+    /// Registry config of the agent's collection
+    #[account(
+        seeds = [b"registry_config", agent_account.collection.as_ref()],
+        bump = registry_config.bump,
+        has_one = authority @ RegistryError::Unauthorized
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Set the registration validity period (registry authority only)
+#[derive(Accounts)]
+pub struct SetRegistrationPeriod<'info> {
+    #[account(
+        mut,
+        seeds = [b"registry_config", registry_config.collection.as_ref()],
+        bump = registry_config.bump,
+        has_one = authority @ RegistryError::Unauthorized
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Renew an agent registration (owner only)
+#[derive(Accounts)]
+pub struct RenewAgent<'info> {
+    #[account(
+        mut,
+        seeds = [b"agent", asset.key().as_ref()],
+        bump = agent_account.bump
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// Core asset - ownership verified in instruction
+    /// CHECK: Verified via agent_account constraint and in instruction
+    #[account(
+        constraint = asset.key() == agent_account.asset @ RegistryError::InvalidAsset
+    )]
+    pub asset: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"registry_config", agent_account.collection.as_ref()],
+        bump = registry_config.bump
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
+    pub owner: Signer<'info>,
+}
+
+/// Register agent backed by a Token-2022 NFT (metadata pointer + token metadata)
+#[derive(Accounts)]
+#[instruction(agent_uri: String)]
+pub struct RegisterToken2022<'info> {
+    /// Root config (registry_config seeds validate the collection)
+    #[account(
+        seeds = [b"root_config"],
+        bump = root_config.bump
+    )]
+    pub root_config: Account<'info, RootConfig>,
+
+    /// Registry config (allocates the agent id from its own range)
+    #[account(
+        mut,
+        seeds = [b"registry_config", collection.key().as_ref()],
+        bump = registry_config.bump,
+        constraint = registry_config.token2022_enabled @ RegistryError::Token2022BackendDisabled
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = AgentAccount::DISCRIMINATOR.len() + AgentAccount::INIT_SPACE,
+        seeds = [b"agent", mint.key().as_ref()],
+        bump
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// Reverse index for the newly allocated agent id
+    #[account(
+        init,
+        payer = owner,
+        space = AgentIdIndex::DISCRIMINATOR.len() + AgentIdIndex::INIT_SPACE,
+        seeds = [b"agent_id", registry_config.next_agent_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub agent_id_index: Account<'info, AgentIdIndex>,
+
+    /// New NFT mint (registry config is mint, freeze and metadata authority)
+    #[account(
+        init,
+        payer = owner,
+        mint::decimals = 0,
+        mint::authority = registry_config,
+        mint::freeze_authority = registry_config,
+        mint::token_program = token_program,
+        extensions::metadata_pointer::authority = registry_config,
+        extensions::metadata_pointer::metadata_address = mint,
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Owner's associated token account receiving the single NFT token
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+    )]
+    pub owner_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Registry collection (used for registry grouping, no Core CPI on this path)
+    /// CHECK: Verified via registry_config seeds
+    pub collection: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    pub token_program: Program<'info, Token2022>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// Registry treasury (required when registration_fee > 0)
+    #[account(mut)]
+    pub treasury: Option<Account<'info, Treasury>>,
+
+    /// Owner's OwnerIndex page to add the asset to (optional)
+    #[account(mut)]
+    pub owner_index: Option<Account<'info, OwnerIndex>>,
+}
+
+/// Enable or disable the Token-2022 backend (registry authority only)
+#[derive(Accounts)]
+pub struct SetToken2022Backend<'info> {
+    #[account(
+        mut,
+        seeds = [b"registry_config", registry_config.collection.as_ref()],
+        bump = registry_config.bump,
+        has_one = authority @ RegistryError::Unauthorized
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Create the registry treasury (registry authority only)
+#[derive(Accounts)]
+pub struct InitializeTreasury<'info> {
+    #[account(
+        seeds = [b"registry_config", registry_config.collection.as_ref()],
+        bump = registry_config.bump,
+        has_one = authority @ RegistryError::Unauthorized
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = Treasury::DISCRIMINATOR.len() + Treasury::INIT_SPACE,
+        seeds = [b"treasury", registry_config.collection.as_ref()],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Add a registry attestor (registry authority only)
+#[derive(Accounts)]
+pub struct AddAttestor<'info> {
+    #[account(
+        seeds = [b"registry_config", registry_config.collection.as_ref()],
+        bump = registry_config.bump,
+        has_one = authority @ RegistryError::Unauthorized
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = Attestor::DISCRIMINATOR.len() + Attestor::INIT_SPACE,
+        seeds = [b"attestor", registry_config.collection.as_ref(), attestor.key().as_ref()],
+        bump
+    )]
+    pub attestor_record: Account<'info, Attestor>,
+
+    /// Attestor key
+    /// CHECK: Can be any account
+    pub attestor: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Remove a registry attestor (registry authority only)
+#[derive(Accounts)]
+pub struct RemoveAttestor<'info> {
+    #[account(
+        seeds = [b"registry_config", registry_config.collection.as_ref()],
+        bump = registry_config.bump,
+        has_one = authority @ RegistryError::Unauthorized
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [
+            b"attestor",
+            registry_config.collection.as_ref(),
+            attestor_record.attestor.as_ref()
+        ],
+        bump = attestor_record.bump
+    )]
+    pub attestor_record: Account<'info, Attestor>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+/// Set an agent's verification level (registry attestor only)
+#[derive(Accounts)]
+pub struct SetVerification<'info> {
+    #[account(
+        seeds = [b"attestor", agent_account.collection.as_ref(), attestor.key().as_ref()],
+        bump = attestor_record.bump
+    )]
+    pub attestor_record: Account<'info, Attestor>,
+
+    #[account(
+        seeds = [b"agent", asset.key().as_ref()],
+        bump = agent_account.bump,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// CHECK: Verified via agent_account constraint
+    #[account(
+        constraint = asset.key() == agent_account.asset @ RegistryError::InvalidAsset
+    )]
+    pub asset: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = attestor,
+        space = AgentVerification::DISCRIMINATOR.len() + AgentVerification::INIT_SPACE,
+        seeds = [b"agent_verification", asset.key().as_ref()],
+        bump
+    )]
+    pub agent_verification: Account<'info, AgentVerification>,
+
+    #[account(mut)]
+    pub attestor: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Set the registration fee (registry authority only)
+#[derive(Accounts)]
+pub struct SetRegistrationFee<'info> {
+    #[account(
+        mut,
+        seeds = [b"registry_config", registry_config.collection.as_ref()],
+        bump = registry_config.bump,
+        has_one = authority @ RegistryError::Unauthorized
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Set the collection royalty policy (registry authority only)
+/// Installs or updates the Core Royalties plugin on the collection
+#[derive(Accounts)]
+pub struct SetCollectionRoyalties<'info> {
+    #[account(
+        mut,
+        seeds = [b"registry_config", registry_config.collection.as_ref()],
+        bump = registry_config.bump,
+        has_one = authority @ RegistryError::Unauthorized
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
+    /// Collection (registry_config is its update authority)
+    /// CHECK: Verified via registry_config constraint
+    #[account(
+        mut,
+        constraint = collection.key() == registry_config.collection @ RegistryError::InvalidCollection
+    )]
+    pub collection: UncheckedAccount<'info>,
+
+    /// Treasury PDA receiving collection-level royalties
+    #[account(
+        seeds = [b"treasury", registry_config.collection.as_ref()],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    /// Registry authority (pays plugin rent)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Metaplex Core program
+    /// CHECK: Verified by address constraint
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+}
+
+/// Apply the registry creator-royalty policy to an agent asset (permissionless crank)
+#[derive(Accounts)]
+pub struct ApplyCreatorRoyalty<'info> {
+    #[account(
+        seeds = [b"registry_config", collection.key().as_ref()],
+        bump = registry_config.bump
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
+    #[account(
+        seeds = [b"agent", asset.key().as_ref()],
+        bump = agent_account.bump,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// Core asset
+    /// CHECK: Verified via agent_account constraint
+    #[account(
+        mut,
+        constraint = asset.key() == agent_account.asset @ RegistryError::InvalidAsset
+    )]
+    pub asset: UncheckedAccount<'info>,
+
+    /// Collection (required by Core plugin CPI)
+    /// CHECK: Verified via agent_account constraint
+    #[account(
+        mut,
+        constraint = collection.key() == agent_account.collection @ RegistryError::InvalidCollection
+    )]
+    pub collection: UncheckedAccount<'info>,
+
+    /// Pays plugin rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Metaplex Core program
+    /// CHECK: Verified by address constraint
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+}
+
+/// Set the deregistration dispute window (registry authority only)
+#[derive(Accounts)]
+pub struct SetDisputeWindow<'info> {
+    #[account(
+        mut,
+        seeds = [b"registry_config", registry_config.collection.as_ref()],
+        bump = registry_config.bump,
+        has_one = authority @ RegistryError::Unauthorized
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Set the feedback indexing mode (registry authority only)
+#[derive(Accounts)]
+pub struct SetFeedbackIndexMode<'info> {
+    #[account(
+        mut,
+        seeds = [b"registry_config", registry_config.collection.as_ref()],
+        bump = registry_config.bump,
+        has_one = authority @ RegistryError::Unauthorized
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Configure paid feedback (registry authority only)
+#[derive(Accounts)]
+pub struct SetFeedbackPayment<'info> {
+    #[account(
+        mut,
+        seeds = [b"registry_config", registry_config.collection.as_ref()],
+        bump = registry_config.bump,
+        has_one = authority @ RegistryError::Unauthorized
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Toggle the self-feedback escape hatch (registry authority only)
+#[derive(Accounts)]
+pub struct SetAllowSelfFeedback<'info> {
+    #[account(
+        mut,
+        seeds = [b"registry_config", registry_config.collection.as_ref()],
+        bump = registry_config.bump,
+        has_one = authority @ RegistryError::Unauthorized
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Set the feedback cooldown (registry authority only)
+#[derive(Accounts)]
+pub struct SetFeedbackCooldown<'info> {
+    #[account(
+        mut,
+        seeds = [b"registry_config", registry_config.collection.as_ref()],
+        bump = registry_config.bump,
+        has_one = authority @ RegistryError::Unauthorized
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Configure feedback bonds (registry authority only)
+#[derive(Accounts)]
+pub struct SetFeedbackBond<'info> {
+    #[account(
+        mut,
+        seeds = [b"registry_config", registry_config.collection.as_ref()],
+        bump = registry_config.bump,
+        has_one = authority @ RegistryError::Unauthorized
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Set the committed feedback reveal delay (registry authority only)
+#[derive(Accounts)]
+pub struct SetFeedbackRevealDelay<'info> {
+    #[account(
+        mut,
+        seeds = [b"registry_config", registry_config.collection.as_ref()],
+        bump = registry_config.bump,
+        has_one = authority @ RegistryError::Unauthorized
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Toggle strict canonical feedback tags (registry authority only)
+#[derive(Accounts)]
+pub struct SetStrictFeedbackTags<'info> {
+    #[account(
+        mut,
+        seeds = [b"registry_config", registry_config.collection.as_ref()],
+        bump = registry_config.bump,
+        has_one = authority @ RegistryError::Unauthorized
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Set the referral fee share (registry authority only)
+#[derive(Accounts)]
+pub struct SetReferralShare<'info> {
+    #[account(
+        mut,
+        seeds = [b"registry_config", registry_config.collection.as_ref()],
+        bump = registry_config.bump,
+        has_one = authority @ RegistryError::Unauthorized
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Set the registration bond and cooldown (registry authority only)
+#[derive(Accounts)]
+pub struct SetRegistrationBond<'info> {
+    #[account(
+        mut,
+        seeds = [b"registry_config", registry_config.collection.as_ref()],
+        bump = registry_config.bump,
+        has_one = authority @ RegistryError::Unauthorized
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Set the registration allowlist root (registry authority only)
+#[derive(Accounts)]
+pub struct SetAllowlistRoot<'info> {
+    #[account(
+        mut,
+        seeds = [b"registry_config", registry_config.collection.as_ref()],
+        bump = registry_config.bump,
+        has_one = authority @ RegistryError::Unauthorized
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Slash an agent's registration bond into the treasury (registry authority only)
+#[derive(Accounts)]
+pub struct SlashRegistrationBond<'info> {
+    #[account(
+        seeds = [b"registry_config", registry_config.collection.as_ref()],
+        bump = registry_config.bump,
+        has_one = authority @ RegistryError::Unauthorized
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"agent", agent_account.asset.as_ref()],
+        bump = agent_account.bump,
+        constraint = agent_account.collection == registry_config.collection
+            @ RegistryError::InvalidCollection
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury", registry_config.collection.as_ref()],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Set the owner-sync bounty (registry authority only)
+#[derive(Accounts)]
+pub struct SetSyncBounty<'info> {
+    #[account(
+        mut,
+        seeds = [b"registry_config", registry_config.collection.as_ref()],
+        bump = registry_config.bump,
+        has_one = authority @ RegistryError::Unauthorized
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Update registry collection name/URI (registry authority only)
+#[derive(Accounts)]
+pub struct UpdateCollection<'info> {
+    #[account(
+        seeds = [b"registry_config", collection.key().as_ref()],
+        bump = registry_config.bump,
+        has_one = authority @ RegistryError::Unauthorized
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
+    /// Registry collection
+    /// CHECK: Verified via registry_config seeds
+    #[account(mut)]
+    pub collection: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Metaplex Core program
+    /// CHECK: Verified by address constraint
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+}
+
+/// Enable or disable the collection transfer gate (registry authority only)
+#[derive(Accounts)]
+pub struct SetTransferGate<'info> {
+    #[account(
+        seeds = [b"registry_config", collection.key().as_ref()],
+        bump = registry_config.bump,
+        has_one = authority @ RegistryError::Unauthorized
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = TransferGate::DISCRIMINATOR.len() + TransferGate::INIT_SPACE,
+        seeds = [b"transfer_gate", collection.key().as_ref()],
+        bump
+    )]
+    pub transfer_gate: Account<'info, TransferGate>,
+
+    /// Registry collection
+    /// CHECK: Verified via registry_config seeds
+    #[account(mut)]
+    pub collection: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Metaplex Core program
+    /// CHECK: Verified by address constraint
+    #[account(address = mpl_core::ID)]
+    pub mpl_core_program: UncheckedAccount<'info>,
+}
+
+/// Set the URI scheme allowlist (registry authority only)
+#[derive(Accounts)]
+pub struct SetUriSchemes<'info> {
+    #[account(
+        mut,
+        seeds = [b"registry_config", registry_config.collection.as_ref()],
+        bump = registry_config.bump,
+        has_one = authority @ RegistryError::Unauthorized
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Propose or cancel a registry authority rotation (registry authority only)
+#[derive(Accounts)]
+pub struct SetRegistryAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"registry_config", registry_config.collection.as_ref()],
+        bump = registry_config.bump,
+        has_one = authority @ RegistryError::Unauthorized
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Accept a registry authority rotation (proposed authority only, may be a PDA signer)
+#[derive(Accounts)]
+pub struct AcceptRegistryAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"registry_config", registry_config.collection.as_ref()],
+        bump = registry_config.bump,
+        constraint = registry_config.pending_authority == Some(new_authority.key())
+            @ RegistryError::Unauthorized
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
+    pub new_authority: Signer<'info>,
+}
+
+/// Withdraw collected fees from the treasury (registry authority only)
+#[derive(Accounts)]
+pub struct WithdrawTreasury<'info> {
+    #[account(
+        seeds = [b"registry_config", registry_config.collection.as_ref()],
+        bump = registry_config.bump,
+        has_one = authority @ RegistryError::Unauthorized
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury", registry_config.collection.as_ref()],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    /// Recipient of the withdrawn lamports
+    /// CHECK: Any account chosen by the authority
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+}
+
 /// Enable ATOM for an agent (one-way)
 #[derive(Accounts)]
 pub struct EnableAtom<'info> {
@@ -0,0 +1,120 @@
+//! Owner council (m-of-n) action hashing.
+//!
+//! An OwnerCouncil lets up to five co-owners authorize management actions on an
+//! agent. A member opens a CouncilProposal committing to an action hash; once the
+//! approval bitmap reaches the council threshold, any member may execute the
+//! matching instruction exactly once.
+//!
+//! ```text
+//! action_hash = keccak256(DOMAIN_COUNCIL_ACTION_V1 || asset || action (u8) || payload)
+//! ```
+//!
+//! Payloads per action:
+//! - `COUNCIL_ACTION_SET_URI`: uri bytes
+//! - `COUNCIL_ACTION_SET_METADATA`: key_hash (16) || immutable (u8) || expires_at (i64 LE) || value
+//! - `COUNCIL_ACTION_TRANSFER`: new_owner (32)
+//! - `COUNCIL_ACTION_SET_URI_HINTS`: content_type (u8) || content_length (u32 LE) || uri bytes
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::keccak;
+
+/// Domain separator for council action hashes (exactly 16 bytes)
+pub const DOMAIN_COUNCIL_ACTION_V1: &[u8; 16] = b"8004_COUNCIL_AC1";
+
+pub const COUNCIL_ACTION_SET_URI: u8 = 0;
+pub const COUNCIL_ACTION_SET_METADATA: u8 = 1;
+pub const COUNCIL_ACTION_TRANSFER: u8 = 2;
+pub const COUNCIL_ACTION_SET_URI_HINTS: u8 = 3;
+
+/// Hash committed to by a CouncilProposal
+pub fn compute_council_action_hash(asset: &Pubkey, action: u8, payload: &[u8]) -> [u8; 32] {
+    keccak::hashv(&[DOMAIN_COUNCIL_ACTION_V1, asset.as_ref(), &[action], payload]).0
+}
+
+/// Payload for a council-approved metadata write
+pub fn metadata_action_payload(
+    key_hash: &[u8; 16],
+    immutable: bool,
+    expires_at: i64,
+    value: &[u8],
+) -> Vec<u8> {
+    let mut payload = Vec::with_capacity(16 + 1 + 8 + value.len());
+    payload.extend_from_slice(key_hash);
+    payload.push(immutable as u8);
+    payload.extend_from_slice(&expires_at.to_le_bytes());
+    payload.extend_from_slice(value);
+    payload
+}
+
+/// Payload for a council-approved URI update carrying content hints
+pub fn uri_hints_action_payload(content_type: u8, content_length: u32, uri: &str) -> Vec<u8> {
+    let mut payload = Vec::with_capacity(1 + 4 + uri.len());
+    payload.push(content_type);
+    payload.extend_from_slice(&content_length.to_le_bytes());
+    payload.extend_from_slice(uri.as_bytes());
+    payload
+}
+
+/// Number of distinct members recorded in an approval bitmap
+pub fn approval_count(approvals: u8) -> u8 {
+    approvals.count_ones() as u8
+}
+
+/// Validate a council configuration
+/// An empty member list with threshold 0 disables the council.
+pub fn is_valid_council(members: &[Pubkey], threshold: u8, max_members: usize) -> bool {
+    if members.is_empty() {
+        return threshold == 0;
+    }
+    if members.len() > max_members || threshold == 0 || threshold as usize > members.len() {
+        return false;
+    }
+    members
+        .iter()
+        .enumerate()
+        .all(|(i, m)| *m != Pubkey::default() && !members[..i].contains(m))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_action_hash_binds_asset_action_and_payload() {
+        let asset = Pubkey::new_unique();
+        let base = compute_council_action_hash(&asset, COUNCIL_ACTION_SET_URI, b"ipfs://a");
+
+        assert_eq!(
+            base,
+            compute_council_action_hash(&asset, COUNCIL_ACTION_SET_URI, b"ipfs://a")
+        );
+        assert_ne!(
+            base,
+            compute_council_action_hash(&asset, COUNCIL_ACTION_SET_URI, b"ipfs://b")
+        );
+        assert_ne!(
+            base,
+            compute_council_action_hash(&asset, COUNCIL_ACTION_TRANSFER, b"ipfs://a")
+        );
+        assert_ne!(
+            base,
+            compute_council_action_hash(&Pubkey::new_unique(), COUNCIL_ACTION_SET_URI, b"ipfs://a")
+        );
+    }
+
+    #[test]
+    fn test_is_valid_council() {
+        let a = Pubkey::new_unique();
+        let b = Pubkey::new_unique();
+
+        assert!(is_valid_council(&[], 0, 5));
+        assert!(!is_valid_council(&[], 1, 5));
+        assert!(is_valid_council(&[a, b], 2, 5));
+        assert!(!is_valid_council(&[a, b], 3, 5));
+        assert!(!is_valid_council(&[a, b], 0, 5));
+        assert!(!is_valid_council(&[a, a], 1, 5));
+        assert!(!is_valid_council(&[a, Pubkey::default()], 1, 5));
+        assert!(!is_valid_council(&[a, b], 1, 1));
+        assert_eq!(approval_count(0b1_0101), 3);
+    }
+}
@@ -8,6 +8,7 @@ pub struct MetadataSet {
     pub immutable: bool,            // offset 32 (moved up)
     pub key: String,                // offset 33 (variable, moved to end)
     pub value: Vec<u8>,             // variable
+    pub expires_at: i64,            // 0 = immutable forever (if immutable)
 }
 
 /// Event emitted when agent metadata is deleted
@@ -17,6 +18,64 @@ pub struct MetadataDeleted {
     pub key: String,                // offset 32 (only variable field, OK at end)
 }
 
+/// Event emitted when a metadata chunk is written (set or append)
+#[event]
+pub struct MetadataChunkSet {
+    pub asset: Pubkey,
+    pub key_hash: [u8; 16],
+    pub chunk_index: u16,
+    pub immutable: bool,
+    /// Full chunk content after the write
+    pub data: Vec<u8>,
+}
+
+/// Event emitted when a metadata chunk is deleted
+#[event]
+pub struct MetadataChunkDeleted {
+    pub asset: Pubkey,
+    pub key_hash: [u8; 16],
+    pub chunk_index: u16,
+}
+
+/// Event emitted when an endpoint ownership challenge is opened
+#[event]
+pub struct EndpointChallengeRequested {
+    pub asset: Pubkey,
+    pub key_hash: [u8; 16],
+    pub endpoint_key: Pubkey,
+    pub nonce: [u8; 32],
+    pub expires_at: i64,
+}
+
+/// Event emitted when an endpoint answers its challenge
+#[event]
+pub struct EndpointVerified {
+    pub asset: Pubkey,
+    pub key_hash: [u8; 16],
+    pub endpoint_key: Pubkey,
+    /// keccak256 of the metadata value the proof is bound to
+    pub value_hash: [u8; 32],
+}
+
+/// Event emitted when an agent card hash is committed
+#[event]
+pub struct AgentCardCommitted {
+    pub asset: Pubkey,
+    pub card_hash: [u8; 32],
+    pub card_chunk_root: [u8; 32],
+    pub committed_by: Pubkey,
+}
+
+/// Event emitted when agent card content is verified against the commitment
+#[event]
+pub struct AgentCardVerified {
+    pub asset: Pubkey,
+    pub card_hash: [u8; 32],
+    pub verified_by: Pubkey,
+    /// None = full card verified, Some(i) = chunk i verified via Merkle proof
+    pub chunk_index: Option<u32>,
+}
+
 /// Event emitted when agent URI is updated
 /// Field order optimized for indexing: fixed-size fields first, variable-size (String) last
 #[event]
@@ -26,6 +85,14 @@ pub struct UriUpdated {
     pub new_uri: String,            // offset 64 (variable, moved to end)
 }
 
+/// Event emitted when the agent URI content hints change
+#[event]
+pub struct UriHintsUpdated {
+    pub asset: Pubkey,
+    pub content_type: u8,
+    pub content_length: u32,
+}
+
 /// Event emitted when agent owner is synced after transfer
 #[event]
 pub struct AgentOwnerSynced {
@@ -34,6 +101,22 @@ pub struct AgentOwnerSynced {
     pub new_owner: Pubkey,
 }
 
+/// Event emitted when a two-step transfer is proposed
+#[event]
+pub struct TransferProposed {
+    pub asset: Pubkey,
+    pub from: Pubkey,
+    pub to: Pubkey,
+}
+
+/// Event emitted when a pending transfer is cancelled by the proposer
+#[event]
+pub struct TransferCancelled {
+    pub asset: Pubkey,
+    pub from: Pubkey,
+    pub to: Pubkey,
+}
+
 /// Event emitted when agent wallet is set or updated
 #[event]
 pub struct WalletUpdated {
@@ -53,6 +136,26 @@ pub struct WalletResetOnOwnerSync {
     pub owner_after_sync: Pubkey,
 }
 
+/// Event emitted when an operator delegation is granted
+#[event]
+pub struct OperatorGranted {
+    pub asset: Pubkey,
+    pub operator: Pubkey,
+    pub granted_by: Pubkey,
+    /// AgentOperator::SCOPE_* bits (SCOPE_ALL for full operators)
+    pub scope: u64,
+    /// Session expiry (0 = never)
+    pub expires_at: i64,
+}
+
+/// Event emitted when an operator delegation is revoked
+#[event]
+pub struct OperatorRevoked {
+    pub asset: Pubkey,
+    pub operator: Pubkey,
+    pub revoked_by: Pubkey,
+}
+
 /// Event emitted when collection pointer is first set
 #[event]
 pub struct CollectionPointerSet {
@@ -81,20 +184,479 @@ pub struct RegistryInitialized {
     pub authority: Pubkey,
 }
 
+/// Event emitted when the registry authority changes the registration period
+#[event]
+pub struct RegistrationPeriodUpdated {
+    pub collection: Pubkey,
+    pub registration_period: i64,
+}
+
+/// Event emitted when the registry authority toggles the Token-2022 backend
+#[event]
+pub struct Token2022BackendUpdated {
+    pub collection: Pubkey,
+    pub enabled: bool,
+}
+
+/// Event emitted when the registry authority changes the registration fee
+#[event]
+pub struct RegistrationFeeUpdated {
+    pub collection: Pubkey,
+    pub registration_fee: u64,
+}
+
+/// Event emitted when a registration fee is collected
+#[event]
+pub struct RegistrationFeeCollected {
+    pub asset: Pubkey,
+    pub payer: Pubkey,
+    pub amount: u64,
+}
+
+/// Event emitted when the registry royalty policy changes
+#[event]
+pub struct CollectionRoyaltiesUpdated {
+    pub collection: Pubkey,
+    pub basis_points: u16,
+    pub route: u8,
+    /// Collection-level royalty recipient (treasury PDA)
+    pub treasury: Pubkey,
+}
+
+/// Event emitted when the registry authority adds or removes an attestor
+#[event]
+pub struct AttestorUpdated {
+    pub collection: Pubkey,
+    pub attestor: Pubkey,
+    pub active: bool,
+}
+
+/// Event emitted when an attestor sets an agent's verification level
+#[event]
+pub struct AgentVerificationSet {
+    pub asset: Pubkey,
+    pub attestor: Pubkey,
+    pub level: u8,
+    pub attestation_hash: [u8; 32],
+}
+
+/// Event emitted when an agent is registered for an Ethereum owner
+#[event]
+pub struct EvmOwnerLinked {
+    pub asset: Pubkey,
+    pub evm_owner: [u8; 20],
+    pub delegate: Pubkey,
+}
+
+/// Event emitted when the feedback indexing mode changes
+#[event]
+pub struct FeedbackIndexModeUpdated {
+    pub collection: Pubkey,
+    pub mode: u8,
+}
+
+/// Event emitted when the paid feedback configuration changes
+#[event]
+pub struct FeedbackPaymentUpdated {
+    pub collection: Pubkey,
+    pub mint: Pubkey,
+    pub min_amount: u64,
+}
+
+/// Event emitted when the self-feedback escape hatch is toggled
+#[event]
+pub struct SelfFeedbackPolicyUpdated {
+    pub collection: Pubkey,
+    pub allowed: bool,
+}
+
+/// Event emitted when the per client-agent feedback cooldown changes
+#[event]
+pub struct FeedbackCooldownUpdated {
+    pub collection: Pubkey,
+    pub cooldown_slots: u64,
+}
+
+/// Event emitted when the feedback bond configuration changes
+#[event]
+pub struct FeedbackBondUpdated {
+    pub collection: Pubkey,
+    pub amount: u64,
+    pub challenge_window: i64,
+}
+
+/// Event emitted when the committed feedback reveal delay changes
+#[event]
+pub struct FeedbackRevealDelayUpdated {
+    pub collection: Pubkey,
+    pub delay: i64,
+}
+
+/// Event emitted when strict canonical feedback tags are toggled
+#[event]
+pub struct StrictFeedbackTagsUpdated {
+    pub collection: Pubkey,
+    pub strict: bool,
+}
+
+/// Event emitted when the deregistration dispute window changes
+#[event]
+pub struct DisputeWindowUpdated {
+    pub collection: Pubkey,
+    pub dispute_window: i64,
+}
+
+/// Event emitted when the referral fee share changes
+#[event]
+pub struct ReferralShareUpdated {
+    pub collection: Pubkey,
+    pub referral_share_bps: u16,
+}
+
+/// Event emitted when a registration is attributed to a referrer
+#[event]
+pub struct AgentReferred {
+    pub asset: Pubkey,
+    pub collection: Pubkey,
+    pub referrer: Pubkey,
+    pub fee_share: u64,
+}
+
+/// Event emitted when the registration bond configuration changes
+#[event]
+pub struct RegistrationBondUpdated {
+    pub collection: Pubkey,
+    pub registration_bond: u64,
+    pub bond_cooldown: i64,
+}
+
+/// Event emitted when the registration allowlist root changes
+#[event]
+pub struct AllowlistRootUpdated {
+    pub collection: Pubkey,
+    pub allowlist_root: [u8; 32],
+}
+
+/// Event emitted when a registration bond is locked in a new AgentAccount
+#[event]
+pub struct RegistrationBondLocked {
+    pub asset: Pubkey,
+    pub payer: Pubkey,
+    pub amount: u64,
+    pub unlocks_at: i64,
+}
+
+/// Event emitted when the registry authority slashes a registration bond
+#[event]
+pub struct RegistrationBondSlashed {
+    pub asset: Pubkey,
+    pub amount: u64,
+    pub slashed_by: Pubkey,
+}
+
+/// Event emitted when the registry authority withdraws from the treasury
+#[event]
+pub struct TreasuryWithdrawn {
+    pub collection: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+}
+
+/// Event emitted when an agent registration is renewed
+#[event]
+pub struct AgentRenewed {
+    pub asset: Pubkey,
+    pub valid_until: i64,
+    pub renewed_by: Pubkey,
+}
+
+/// Event emitted when the root authority creates an additional base registry
+#[event]
+pub struct BaseRegistryCreated {
+    pub registry: Pubkey,
+    pub collection: Pubkey,
+    pub created_by: Pubkey,
+}
+
+/// Event emitted when the deployment feature flags change
+#[event]
+pub struct RegistryFeaturesUpdated {
+    pub version: u16,
+    pub features: u64,
+    pub updated_by: Pubkey,
+}
+
+/// Event emitted when an organization creates its own user registry
+#[event]
+pub struct UserRegistryCreated {
+    pub registry: Pubkey,
+    pub collection: Pubkey,
+    pub owner: Pubkey,
+    pub name: String,
+}
+
 /// Event emitted when agent is registered
 /// Field order: fixed-size first (Pubkey, bool), variable-size last (String)
+/// Carries everything an indexer needs to build the agent record without a fetch
 #[event]
 pub struct AgentRegistered {
     pub asset: Pubkey,
     pub collection: Pubkey,
+    /// RegistryConfig PDA of the (base or user) registry
+    pub registry: Pubkey,
     pub owner: Pubkey,
+    pub agent_id: u64,
+    /// keccak256(agent_uri) (zero when the URI is empty)
+    pub uri_hash: [u8; 32],
+    pub created_slot: u64,
     pub atom_enabled: bool,
+    pub token_backed: bool,
+    pub soulbound: bool,
     pub agent_uri: String,
 }
 
+/// Event emitted when an agent is rebound to a new Core asset
+/// Asset-keyed satellites (metadata, ATOM stats) can be re-keyed from this event
+#[event]
+pub struct AssetReplaced {
+    pub agent_id: u64,
+    pub old_asset: Pubkey,
+    pub new_asset: Pubkey,
+    pub old_collection: Pubkey,
+    pub new_collection: Pubkey,
+    pub owner: Pubkey,
+    pub approved_by: Pubkey,
+}
+
+/// Event emitted when a legacy AgentAccount is upgraded to the current layout
+#[event]
+pub struct AgentMigrated {
+    pub asset: Pubkey,
+    pub agent_id: u64,
+    pub from_version: u8,
+    pub to_version: u8,
+}
+
+/// Event emitted when a v0.6.0 MetadataEntryPda is upgraded to the current layout
+#[event]
+pub struct MetadataEntryMigrated {
+    pub asset: Pubkey,
+    pub key_hash: [u8; 16],
+}
+
+/// Event emitted when a v0.6.0 RootConfig is upgraded to the current layout
+#[event]
+pub struct RootConfigMigrated {
+    pub base_collection: Pubkey,
+    pub next_registry_id: u64,
+}
+
+/// Event emitted when a v0.6.0 RegistryConfig is upgraded to the current layout
+#[event]
+pub struct RegistryConfigMigrated {
+    pub collection: Pubkey,
+    pub authority: Pubkey,
+}
+
+/// Event emitted when an agent is renamed
+#[event]
+pub struct AgentNameUpdated {
+    pub asset: Pubkey,
+    pub updated_by: Pubkey,
+    pub new_name: String,
+}
+
+/// Event emitted when an agent's display symbol changes
+#[event]
+pub struct AgentSymbolUpdated {
+    pub asset: Pubkey,
+    pub updated_by: Pubkey,
+    pub new_symbol: String,
+}
+
+/// Event emitted when an agent's lifecycle status changes
+#[event]
+pub struct AgentStatusUpdated {
+    pub asset: Pubkey,
+    pub old_status: u8,
+    pub new_status: u8,
+    pub updated_by: Pubkey,
+}
+
+/// Event emitted when the registry authority freezes or unfreezes an agent
+#[event]
+pub struct AgentFreezeUpdated {
+    pub asset: Pubkey,
+    pub frozen: bool,
+    pub updated_by: Pubkey,
+}
+
 /// Event emitted when ATOM is enabled for an agent (one-way)
 #[event]
 pub struct AtomEnabled {
     pub asset: Pubkey,
     pub enabled_by: Pubkey,
 }
+
+/// Event emitted when an agent is deregistered (asset burned, AgentAccount closed)
+#[event]
+pub struct AgentDeregistered {
+    pub asset: Pubkey,
+    pub collection: Pubkey,
+    pub owner: Pubkey,
+}
+
+/// Event emitted when a DID is bound to an agent
+#[event]
+pub struct DidSet {
+    pub asset: Pubkey,
+    pub verification_key: Pubkey,
+    pub set_by: Pubkey,
+    pub did: String,
+}
+
+/// Event emitted when a DID binding is removed
+#[event]
+pub struct DidCleared {
+    pub asset: Pubkey,
+    pub cleared_by: Pubkey,
+}
+
+/// Event emitted when agent capability flags change
+#[event]
+pub struct CapabilitiesUpdated {
+    pub asset: Pubkey,
+    pub capabilities: u64,
+    pub updated_by: Pubkey,
+}
+
+/// Event emitted when the FeedbackAuth requirement is toggled
+#[event]
+pub struct FeedbackAuthRequirementUpdated {
+    pub asset: Pubkey,
+    pub required: bool,
+    pub updated_by: Pubkey,
+}
+
+/// Event emitted when a Core plugin is added or updated on an agent asset
+#[event]
+pub struct AgentPluginUpdated {
+    pub asset: Pubkey,
+    /// PLUGIN_KIND_* constant
+    pub plugin_kind: u8,
+    /// Delegate, oracle account or registry config controlling the plugin
+    pub plugin_authority: Pubkey,
+    pub updated_by: Pubkey,
+}
+
+/// Event emitted when the cached owner is found to differ from the live owner
+#[event]
+pub struct StaleOwnerDetected {
+    pub asset: Pubkey,
+    pub cached_owner: Pubkey,
+    pub live_owner: Pubkey,
+    pub last_synced_slot: u64,
+    pub detected_slot: u64,
+}
+
+/// Event emitted when a cranker is paid for repairing a stale owner
+#[event]
+pub struct SyncBountyPaid {
+    pub asset: Pubkey,
+    pub cranker: Pubkey,
+    pub amount: u64,
+}
+
+/// Event emitted when the sync bounty changes
+#[event]
+pub struct SyncBountyUpdated {
+    pub collection: Pubkey,
+    pub sync_bounty: u64,
+}
+
+/// Event emitted when a collection's transfer gate is enabled or disabled
+#[event]
+pub struct TransferGateUpdated {
+    pub collection: Pubkey,
+    pub gate: Pubkey,
+    pub enabled: bool,
+}
+
+/// Event emitted when registry collection name/URI change (None = unchanged)
+#[event]
+pub struct CollectionUpdated {
+    pub collection: Pubkey,
+    pub updated_by: Pubkey,
+    pub name: Option<String>,
+    pub uri: Option<String>,
+}
+
+/// Event emitted when the registry URI scheme allowlist changes
+#[event]
+pub struct UriSchemesUpdated {
+    pub collection: Pubkey,
+    pub allowed_uri_schemes: u8,
+}
+
+/// Event emitted when a registry authority rotation is proposed or cancelled
+#[event]
+pub struct RegistryAuthorityProposed {
+    pub collection: Pubkey,
+    pub authority: Pubkey,
+    pub pending_authority: Option<Pubkey>,
+}
+
+/// Event emitted when a registry authority rotation completes
+#[event]
+pub struct RegistryAuthorityTransferred {
+    pub collection: Pubkey,
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+/// Event emitted when agent category tags change
+#[event]
+pub struct AgentTagsUpdated {
+    pub asset: Pubkey,
+    pub tag_hashes: Vec<[u8; 32]>,
+    /// Plain-text tags (hashes are keccak256 of these)
+    pub tags: Vec<String>,
+}
+
+/// Event emitted when an owner council is configured or disabled
+#[event]
+pub struct OwnerCouncilUpdated {
+    pub asset: Pubkey,
+    pub owner: Pubkey,
+    pub members: Vec<Pubkey>,
+    pub threshold: u8,
+    pub epoch: u32,
+}
+
+/// Event emitted when a council member opens a proposal
+#[event]
+pub struct CouncilActionProposed {
+    pub asset: Pubkey,
+    pub proposal_id: u64,
+    pub action: u8,
+    pub action_hash: [u8; 32],
+    pub proposer: Pubkey,
+}
+
+/// Event emitted when a council member approves a proposal
+#[event]
+pub struct CouncilActionApproved {
+    pub asset: Pubkey,
+    pub proposal_id: u64,
+    pub member: Pubkey,
+    pub approvals: u8,
+}
+
+/// Event emitted when an approved council proposal is executed
+#[event]
+pub struct CouncilActionExecuted {
+    pub asset: Pubkey,
+    pub proposal_id: u64,
+    pub action: u8,
+    pub executed_by: Pubkey,
+}
@@ -1,19 +1,53 @@
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::keccak;
+use anchor_lang::solana_program::secp256k1_recover::secp256k1_recover;
 use anchor_lang::solana_program::sysvar::instructions::{
     load_current_index_checked, load_instruction_at_checked,
 };
+use anchor_lang::system_program;
+use anchor_spl::token_2022::spl_token_2022::instruction::AuthorityType;
+use anchor_spl::token_interface::{
+    mint_to, set_authority, token_metadata_initialize, MintTo, SetAuthority,
+    TokenMetadataInitialize,
+};
+use mpl_core::accounts::{BaseAssetV1, BaseCollectionV1};
+use mpl_core::fetch_plugin;
 use mpl_core::instructions::{
-    CreateCollectionV2CpiBuilder, CreateV2CpiBuilder, TransferV1CpiBuilder,
-    UpdateV1CpiBuilder,
+    AddCollectionExternalPluginAdapterV1CpiBuilder, AddCollectionPluginV1CpiBuilder,
+    AddExternalPluginAdapterV1CpiBuilder,
+    AddPluginV1CpiBuilder, ApprovePluginAuthorityV1CpiBuilder, BurnV1CpiBuilder,
+    CreateCollectionV2CpiBuilder, CreateV2CpiBuilder, RemovePluginV1CpiBuilder,
+    RevokePluginAuthorityV1CpiBuilder, TransferV1CpiBuilder, UpdateCollectionPluginV1CpiBuilder,
+    UpdateCollectionV1CpiBuilder, UpdatePluginV1CpiBuilder, UpdateV1CpiBuilder,
+};
+use mpl_core::types::{
+    Creator, ExternalCheckResult, ExternalPluginAdapterInitInfo, FreezeDelegate,
+    HookableLifecycleEvent, OracleInitInfo, PermanentFreezeDelegate, Plugin, PluginAuthority,
+    PluginAuthorityPair, PluginType, Royalties, RuleSet, TransferDelegate,
+    ValidationResultsOffset,
 };
 
+use super::allowlist::verify_allowlist_proof;
+use super::card::{compute_card_hash, verify_card_chunk};
+use super::council::{
+    approval_count, compute_council_action_hash, is_valid_council, metadata_action_payload,
+    uri_hints_action_payload, COUNCIL_ACTION_SET_METADATA, COUNCIL_ACTION_SET_URI,
+    COUNCIL_ACTION_SET_URI_HINTS, COUNCIL_ACTION_TRANSFER,
+};
 use super::contexts::*;
 use super::events::*;
+use super::schema::{
+    is_valid_category_tag, validate_uri_scheme, validate_well_known_value, URI_SCHEMES_ALL,
+};
 use super::state::*;
 use crate::constants::*;
-use crate::core_asset::{get_core_owner, verify_core_owner};
+use crate::core_asset::{
+    get_core_collection, get_core_owner, is_core_asset_burned, verify_core_owner,
+    verify_owner_or_operator,
+};
 use crate::error::RegistryError;
+use crate::reputation::sketch::SKETCH_BUCKETS;
 
 /// Maximum deadline window: 5 minutes (300 seconds)
 const MAX_DEADLINE_WINDOW: i64 = 300;
@@ -21,6 +55,28 @@ const MAX_DEADLINE_WINDOW: i64 = 300;
 /// Message prefix for wallet set signature
 const WALLET_SET_MESSAGE_PREFIX: &[u8] = b"8004_WALLET_SET:";
 
+/// Message prefix for DID binding signature
+const DID_SET_MESSAGE_PREFIX: &[u8] = b"8004_DID_SET:";
+
+/// Message prefix for endpoint challenge responses
+const ENDPOINT_VERIFY_MESSAGE_PREFIX: &[u8] = b"8004_ENDPOINT_VERIFY:";
+
+/// Message prefix for EVM owner registration proofs
+const EVM_REGISTER_MESSAGE_PREFIX: &[u8] = b"8004_EVM_REGISTER:";
+
+/// eth_sign (EIP-191 version 0x45) prefix; followed by the decimal message length
+const ETH_SIGNED_MESSAGE_PREFIX: &[u8] = b"\x19Ethereum Signed Message:\n";
+
+/// secp256k1 group order n / 2 (big-endian); signatures with a larger s are malleable
+const SECP256K1_HALF_ORDER: [u8; 32] = [
+    0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0xff, 0x5d, 0x57, 0x6e, 0x73, 0x57, 0xa4, 0x50, 0x1d, 0xdf, 0xe9, 0x2f, 0x46, 0x68, 0x1b,
+    0x20, 0xa0,
+];
+
+/// Core external plugin check flag: the oracle may reject the lifecycle event
+const ORACLE_CAN_REJECT: u32 = 1 << 2;
+
 /// Prefix for canonical collection pointer storage
 const COLLECTION_POINTER_PREFIX: &str = "c1:";
 
@@ -30,6 +86,7 @@ const COLLECTION_POINTER_PREFIX: &str = "c1:";
 /// Updates existing entry if not immutable.
 /// key_hash is SHA256(key)[0..16] for collision resistance (2^128 space)
 /// Note: "agentWallet" is a reserved key - use set_agent_wallet instruction instead
+/// Well-known keys (see identity::schema) must match their value format
 pub fn set_metadata_pda(
     ctx: Context<SetMetadataPda>,
     key_hash: [u8; 16],
@@ -37,32 +94,68 @@ pub fn set_metadata_pda(
     value: Vec<u8>,
     immutable: bool,
 ) -> Result<()> {
-    // Block reserved metadata key "agentWallet" - must use set_agent_wallet instruction
-    require!(key != "agentWallet", RegistryError::ReservedMetadataKey);
-
-    // Verify key_hash matches SHA256(key)[0..16]
-    use anchor_lang::solana_program::hash::hash;
-    let computed_hash = hash(key.as_bytes());
-    let expected: [u8; 16] = computed_hash.to_bytes()[0..16]
-        .try_into()
-        .map_err(|_| RegistryError::Overflow)?;
-    require!(key_hash == expected, RegistryError::KeyHashMismatch);
-
-    // Verify ownership via Core asset
-    verify_core_owner(&ctx.accounts.asset, &ctx.accounts.owner.key())?;
+    write_metadata_entry(ctx, key_hash, key, value, immutable, 0)
+}
 
-    // Validate key length
+/// Set metadata that stays immutable only until `expires_at`
+///
+/// After the deadline the owner may replace or delete the entry. Intended for
+/// time-limited attestations (certifications, audits).
+pub fn set_metadata_pda_with_expiry(
+    ctx: Context<SetMetadataPda>,
+    key_hash: [u8; 16],
+    key: String,
+    value: Vec<u8>,
+    immutable: bool,
+    expires_at: i64,
+) -> Result<()> {
     require!(
-        key.len() <= MetadataEntryPda::MAX_KEY_LENGTH,
-        RegistryError::KeyTooLong
+        expires_at > Clock::get()?.unix_timestamp,
+        RegistryError::InvalidMetadataExpiry
     );
+    write_metadata_entry(ctx, key_hash, key, value, immutable, expires_at)
+}
 
-    // Validate value length
+fn write_metadata_entry(
+    ctx: Context<SetMetadataPda>,
+    key_hash: [u8; 16],
+    key: String,
+    value: Vec<u8>,
+    immutable: bool,
+    expires_at: i64,
+) -> Result<()> {
+    // Verify key_hash matches SHA256(key)[0..16]
     require!(
-        value.len() <= MetadataEntryPda::MAX_VALUE_LENGTH,
-        RegistryError::ValueTooLong
+        key_hash == metadata_key_hash(&key)?,
+        RegistryError::KeyHashMismatch
     );
 
+    // Verify ownership (or operator delegation / council approval) via Core asset
+    if ctx.accounts.council_proposal.is_some() {
+        let payload = metadata_action_payload(&key_hash, immutable, expires_at, &value);
+        let action_hash = compute_council_action_hash(
+            &ctx.accounts.asset.key(),
+            COUNCIL_ACTION_SET_METADATA,
+            &payload,
+        );
+        authorize_council_action(
+            &ctx.accounts.asset,
+            &ctx.accounts.owner.key(),
+            ctx.accounts.owner_council.as_deref(),
+            ctx.accounts.council_proposal.as_deref_mut(),
+            &action_hash,
+        )?;
+    } else {
+        verify_owner_or_operator(
+            &ctx.accounts.asset,
+            &ctx.accounts.owner.key(),
+            ctx.accounts.agent_operator.as_deref(),
+            AgentOperator::SCOPE_METADATA,
+        )?;
+    }
+
+    validate_metadata_entry(&key, &value)?;
+
     let asset = ctx.accounts.asset.key();
     let is_new = ctx.accounts.metadata_entry.asset == Pubkey::default();
 
@@ -73,7 +166,7 @@ pub fn set_metadata_pda(
             RegistryError::KeyHashCollision
         );
 
-        if ctx.accounts.metadata_entry.immutable {
+        if ctx.accounts.metadata_entry.is_locked(Clock::get()?.unix_timestamp) {
             return Err(RegistryError::MetadataImmutable.into());
         }
     }
@@ -84,6 +177,8 @@ pub fn set_metadata_pda(
     entry.metadata_key = key.clone();
     entry.metadata_value = value.clone();
     entry.immutable = immutable;
+    entry.expires_at = expires_at;
+    entry.verified_key = Pubkey::default();
     if is_new {
         entry.bump = ctx.bumps.metadata_entry;
     }
@@ -94,6 +189,7 @@ pub fn set_metadata_pda(
         immutable,
         key: key.clone(),
         value,
+        expires_at,
     });
 
     msg!("Metadata '{}' set for asset {} (immutable: {})", key, asset, immutable);
@@ -103,17 +199,25 @@ pub fn set_metadata_pda(
 
 /// Delete metadata PDA and recover rent
 ///
-/// Only works if metadata is not immutable.
+/// Only works if metadata is not immutable (or its immutability has expired).
 pub fn delete_metadata_pda(ctx: Context<DeleteMetadataPda>, _key_hash: [u8; 16]) -> Result<()> {
-    // Verify ownership via Core asset
-    verify_core_owner(&ctx.accounts.asset, &ctx.accounts.owner.key())?;
+    // Verify ownership (or operator delegation) via Core asset
+    verify_owner_or_operator(
+        &ctx.accounts.asset,
+        &ctx.accounts.owner.key(),
+        ctx.accounts.agent_operator.as_deref(),
+        AgentOperator::SCOPE_METADATA,
+    )?;
 
     let entry = &ctx.accounts.metadata_entry;
     let asset = ctx.accounts.asset.key();
     let key = entry.metadata_key.clone();
 
-    // Check if immutable
-    require!(!entry.immutable, RegistryError::MetadataImmutable);
+    // Check if immutable (time-boxed entries unlock after expires_at)
+    require!(
+        !entry.is_locked(Clock::get()?.unix_timestamp),
+        RegistryError::MetadataImmutable
+    );
 
     // Emit event before closing
     emit!(MetadataDeleted { asset, key: key.clone() });
@@ -123,16 +227,197 @@ pub fn delete_metadata_pda(ctx: Context<DeleteMetadataPda>, _key_hash: [u8; 16])
     Ok(())
 }
 
+/// Replace the content of a metadata chunk
+///
+/// Chunks extend an existing metadata entry beyond 250 bytes. No chunk can be written,
+/// created or deleted while the parent entry is locked.
+pub fn set_metadata_chunk(
+    ctx: Context<SetMetadataChunk>,
+    key_hash: [u8; 16],
+    chunk_index: u16,
+    data: Vec<u8>,
+) -> Result<()> {
+    write_metadata_chunk(ctx, key_hash, chunk_index, data, false)
+}
+
+/// Append bytes to a metadata chunk (creates it if missing)
+///
+/// Lets a single 1024-byte chunk be filled across several transactions.
+pub fn append_metadata_chunk(
+    ctx: Context<SetMetadataChunk>,
+    key_hash: [u8; 16],
+    chunk_index: u16,
+    data: Vec<u8>,
+) -> Result<()> {
+    write_metadata_chunk(ctx, key_hash, chunk_index, data, true)
+}
+
+fn write_metadata_chunk(
+    ctx: Context<SetMetadataChunk>,
+    key_hash: [u8; 16],
+    chunk_index: u16,
+    data: Vec<u8>,
+    append: bool,
+) -> Result<()> {
+    verify_owner_or_operator(
+        &ctx.accounts.asset,
+        &ctx.accounts.owner.key(),
+        ctx.accounts.agent_operator.as_deref(),
+        AgentOperator::SCOPE_METADATA,
+    )?;
+
+    require!(
+        chunk_index < ctx.accounts.registry_config.max_metadata_chunks,
+        RegistryError::ChunkIndexOutOfRange
+    );
+
+    let entry = &ctx.accounts.metadata_entry;
+    require!(
+        !entry.is_locked(Clock::get()?.unix_timestamp),
+        RegistryError::MetadataImmutable
+    );
+    let immutable = entry.immutable;
+    let asset = ctx.accounts.asset.key();
+    let chunk = &mut ctx.accounts.metadata_chunk;
+    let is_new = chunk.asset == Pubkey::default();
+
+    if append && !is_new {
+        chunk.data.extend_from_slice(&data);
+    } else {
+        chunk.data = data;
+    }
+    require!(
+        chunk.data.len() <= MetadataChunk::MAX_CHUNK_SIZE,
+        RegistryError::ChunkTooLarge
+    );
+
+    chunk.asset = asset;
+    chunk.key_hash = key_hash;
+    chunk.chunk_index = chunk_index;
+    chunk.immutable = immutable;
+    if is_new {
+        chunk.bump = ctx.bumps.metadata_chunk;
+    }
+
+    emit!(MetadataChunkSet {
+        asset,
+        key_hash,
+        chunk_index,
+        immutable,
+        data: chunk.data.clone(),
+    });
+
+    Ok(())
+}
+
+/// Delete a metadata chunk and recover rent (only while the parent entry is unlocked)
+pub fn delete_metadata_chunk(
+    ctx: Context<DeleteMetadataChunk>,
+    key_hash: [u8; 16],
+    chunk_index: u16,
+) -> Result<()> {
+    verify_owner_or_operator(
+        &ctx.accounts.asset,
+        &ctx.accounts.owner.key(),
+        ctx.accounts.agent_operator.as_deref(),
+        AgentOperator::SCOPE_METADATA,
+    )?;
+
+    let entry_info = ctx.accounts.metadata_entry.to_account_info();
+    if !entry_info.data_is_empty() {
+        require!(
+            entry_info.data_len() == MetadataEntryPda::SPACE,
+            RegistryError::MetadataEntryNotMigrated
+        );
+        let entry = MetadataEntryPda::try_deserialize(&mut &entry_info.try_borrow_data()?[..])?;
+        require!(
+            !entry.is_locked(Clock::get()?.unix_timestamp),
+            RegistryError::MetadataImmutable
+        );
+    }
+
+    emit!(MetadataChunkDeleted {
+        asset: ctx.accounts.asset.key(),
+        key_hash,
+        chunk_index,
+    });
+
+    Ok(())
+}
+
+/// Set the maximum number of chunks per metadata key
+pub fn set_max_metadata_chunks(
+    ctx: Context<SetMaxMetadataChunks>,
+    max_metadata_chunks: u16,
+) -> Result<()> {
+    ctx.accounts.registry_config.max_metadata_chunks = max_metadata_chunks;
+    msg!("Max metadata chunks set to {}", max_metadata_chunks);
+    Ok(())
+}
+
 /// Set agent URI
 pub fn set_agent_uri(ctx: Context<SetAgentUri>, new_uri: String) -> Result<()> {
-    // Verify ownership via Core asset
-    verify_core_owner(&ctx.accounts.asset, &ctx.accounts.owner.key())?;
+    set_agent_uri_inner(ctx, new_uri, None)
+}
+
+/// Set agent URI together with content-type / size hints for indexers and clients
+///
+/// content_type is one of AgentAccount::URI_CONTENT_*; content_length 0 means unknown.
+pub fn set_agent_uri_with_hints(
+    ctx: Context<SetAgentUri>,
+    new_uri: String,
+    content_type: u8,
+    content_length: u32,
+) -> Result<()> {
+    require!(
+        content_type <= AgentAccount::URI_CONTENT_OTHER,
+        RegistryError::InvalidUriContentType
+    );
+    set_agent_uri_inner(ctx, new_uri, Some((content_type, content_length)))
+}
+
+/// Shared URI update; plain updates clear hints since they described the previous document
+fn set_agent_uri_inner(
+    ctx: Context<SetAgentUri>,
+    new_uri: String,
+    hints: Option<(u8, u32)>,
+) -> Result<()> {
+    // Verify ownership (or operator delegation / council approval) via Core asset
+    if ctx.accounts.council_proposal.is_some() {
+        let action_hash = match hints {
+            Some((content_type, content_length)) => compute_council_action_hash(
+                &ctx.accounts.asset.key(),
+                COUNCIL_ACTION_SET_URI_HINTS,
+                &uri_hints_action_payload(content_type, content_length, &new_uri),
+            ),
+            None => compute_council_action_hash(
+                &ctx.accounts.asset.key(),
+                COUNCIL_ACTION_SET_URI,
+                new_uri.as_bytes(),
+            ),
+        };
+        authorize_council_action(
+            &ctx.accounts.asset,
+            &ctx.accounts.owner.key(),
+            ctx.accounts.owner_council.as_deref(),
+            ctx.accounts.council_proposal.as_deref_mut(),
+            &action_hash,
+        )?;
+    } else {
+        verify_owner_or_operator(
+            &ctx.accounts.asset,
+            &ctx.accounts.owner.key(),
+            ctx.accounts.agent_operator.as_deref(),
+            AgentOperator::SCOPE_URI,
+        )?;
+    }
 
     // Validate URI length
     require!(
         new_uri.len() <= AgentAccount::MAX_URI_LENGTH,
         RegistryError::UriTooLong
     );
+    validate_uri_scheme(&new_uri, ctx.accounts.registry_config.allowed_uri_schemes)?;
 
     let asset = ctx.accounts.asset.key();
     let collection_key = ctx.accounts.collection.key();
@@ -154,9 +439,20 @@ pub fn set_agent_uri(ctx: Context<SetAgentUri>, new_uri: String) -> Result<()> {
         signer_seeds,
     )?;
 
-    // Update AgentAccount
+    // Update AgentAccount (card commitment refers to the previous URI content)
     let agent = &mut ctx.accounts.agent_account;
-    agent.agent_uri = new_uri.clone();
+    agent.uri_hash = AgentAccount::compute_uri_hash(&new_uri);
+    if !agent.compact {
+        agent.agent_uri = new_uri.clone();
+    }
+    agent.card_hash = [0u8; 32];
+    agent.card_chunk_root = [0u8; 32];
+
+    let (content_type, content_length) = hints.unwrap_or((AgentAccount::URI_CONTENT_UNKNOWN, 0));
+    let hints_changed =
+        agent.uri_content_type != content_type || agent.uri_content_length != content_length;
+    agent.uri_content_type = content_type;
+    agent.uri_content_length = content_length;
 
     emit!(UriUpdated {
         asset,
@@ -164,586 +460,4497 @@ pub fn set_agent_uri(ctx: Context<SetAgentUri>, new_uri: String) -> Result<()> {
         new_uri,
     });
 
-    msg!("Agent URI updated for asset {}", asset);
-
-    Ok(())
-}
-
-/// Sync agent owner from Core asset
-/// Automatically resets agent_wallet when ownership changes (security feature)
-/// Use case: After marketplace transfer, new owner calls this to sync + reset wallet
-pub fn sync_owner(ctx: Context<SyncOwner>) -> Result<()> {
-    let agent = &mut ctx.accounts.agent_account;
-    let new_owner = get_core_owner(&ctx.accounts.asset)?;
-    let old_owner = agent.owner;
-    let asset = agent.asset;
-
-    // Only update if owner changed
-    if old_owner != new_owner {
-        agent.owner = new_owner;
-
-        // Reset wallet on ownership change (security: prevents old owner's wallet from being used)
-        let old_wallet = agent.agent_wallet;
-        if old_wallet.is_some() {
-            agent.agent_wallet = None;
-            emit!(WalletResetOnOwnerSync {
-                asset,
-                old_wallet,
-                new_wallet: Pubkey::default(),
-                owner_after_sync: new_owner,
-            });
-        }
-
-        emit!(AgentOwnerSynced {
+    if hints_changed {
+        emit!(UriHintsUpdated {
             asset,
-            old_owner,
-            new_owner,
+            content_type,
+            content_length,
         });
-
-        msg!("Agent owner synced for asset {}: {} -> {} (wallet reset)", asset, old_owner, new_owner);
-    } else {
-        msg!("Agent owner unchanged for asset {}", asset);
     }
 
-    Ok(())
-}
-
-/// Get agent owner (CACHED value - may be stale after external transfer)
-///
-/// WARNING: This returns the cached owner from AgentAccount, not the live Core owner.
-/// After an external NFT transfer (via Metaplex Core directly), this value becomes stale
-/// until sync_owner is called. For authoritative ownership, read the Core asset directly.
-///
-/// Use cases:
-/// - Quick lookup when staleness is acceptable
-/// - UI display (with note about potential staleness)
-///
-/// For authoritative ownership verification, use verify_core_owner() or read Core asset.
-pub fn owner_of(ctx: Context<OwnerOf>) -> Result<Pubkey> {
-    Ok(ctx.accounts.agent_account.owner)
-}
+    msg!("Agent URI updated for asset {}", asset);
 
-/// Get authoritative Core owner (reads directly from Metaplex Core asset)
-///
-/// This always returns the current owner regardless of cache state.
-/// Use this when authoritative ownership is required.
-pub fn core_owner_of(ctx: Context<CoreOwnerOf>) -> Result<Pubkey> {
-    get_core_owner(&ctx.accounts.asset)
+    Ok(())
 }
 
-/// Transfer agent with automatic owner sync
-/// Automatically resets agent_wallet to None on transfer for security
-pub fn transfer_agent(ctx: Context<TransferAgent>) -> Result<()> {
-    // Verify current ownership
-    verify_core_owner(&ctx.accounts.asset, &ctx.accounts.owner.key())?;
+/// Rename the agent (owner or operator), updating the Core asset name
+pub fn set_agent_name(ctx: Context<SetAgentUri>, new_name: String) -> Result<()> {
+    verify_owner_or_operator(
+        &ctx.accounts.asset,
+        &ctx.accounts.owner.key(),
+        ctx.accounts.agent_operator.as_deref(),
+        AgentOperator::SCOPE_PROFILE,
+    )?;
 
-    // Prevent self-transfer
     require!(
-        ctx.accounts.owner.key() != ctx.accounts.new_owner.key(),
-        RegistryError::TransferToSelf
+        !new_name.is_empty() && new_name.len() <= AgentAccount::MAX_NAME_LENGTH,
+        RegistryError::InvalidAgentName
+    );
+    require!(
+        !ctx.accounts.agent_account.token_backed,
+        RegistryError::InvalidAsset
     );
 
-    let old_owner = ctx.accounts.owner.key();
-    let new_owner = ctx.accounts.new_owner.key();
-    let asset = ctx.accounts.agent_account.asset;
+    let asset = ctx.accounts.asset.key();
+    let collection_key = ctx.accounts.collection.key();
+    let registry_bump = ctx.accounts.registry_config.bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        SEED_REGISTRY_CONFIG,
+        collection_key.as_ref(),
+        &[registry_bump],
+    ]];
 
-    // Transfer Core asset
-    TransferV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+    UpdateV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
         .asset(&ctx.accounts.asset.to_account_info())
         .collection(Some(&ctx.accounts.collection.to_account_info()))
         .payer(&ctx.accounts.owner.to_account_info())
-        .authority(Some(&ctx.accounts.owner.to_account_info()))
-        .new_owner(&ctx.accounts.new_owner.to_account_info())
-        .invoke()?;
-
-    // Update cached owner and reset wallet
-    let agent = &mut ctx.accounts.agent_account;
-    let old_wallet = agent.agent_wallet;
-    agent.owner = new_owner;
-    agent.agent_wallet = None; // Security: reset wallet on transfer
+        .authority(Some(&ctx.accounts.registry_config.to_account_info()))
+        .system_program(&ctx.accounts.system_program.to_account_info())
+        .new_name(new_name.clone())
+        .invoke_signed(signer_seeds)?;
 
-    // Emit wallet reset event if there was a wallet
-    if old_wallet.is_some() {
-        emit!(WalletUpdated {
-            asset,
-            old_wallet,
-            new_wallet: Pubkey::default(),
-            updated_by: old_owner,
-        });
-        msg!("Agent wallet reset on transfer");
-    }
+    ctx.accounts.agent_account.nft_name = new_name.clone();
 
-    emit!(AgentOwnerSynced {
+    emit!(AgentNameUpdated {
         asset,
-        old_owner,
-        new_owner,
+        new_name,
+        updated_by: ctx.accounts.owner.key(),
     });
 
-    msg!("Agent transferred: {} -> {}", old_owner, new_owner);
-
     Ok(())
 }
 
-/// Set agent wallet with Ed25519 signature verification
+/// Set the agent display symbol (owner or operator)
 ///
-/// Message format: "8004_WALLET_SET:" || asset (32 bytes) || new_wallet (32 bytes) || owner (32 bytes) || deadline (8 bytes LE)
-/// Wallet is stored directly in AgentAccount (no separate PDA = no rent cost)
-pub fn set_agent_wallet(
-    ctx: Context<SetAgentWallet>,
-    new_wallet: Pubkey,
-    deadline: i64,
-) -> Result<()> {
-    let clock = Clock::get()?;
-    let asset = ctx.accounts.asset.key();
-
-    // 1. Verify caller is Core asset owner
-    verify_core_owner(&ctx.accounts.asset, &ctx.accounts.owner.key())?;
+/// Metaplex Core assets carry no symbol, so the value lives in AgentAccount only.
+pub fn set_agent_symbol(ctx: Context<SetAgentSymbol>, new_symbol: String) -> Result<()> {
+    verify_owner_or_operator(
+        &ctx.accounts.asset,
+        &ctx.accounts.owner.key(),
+        ctx.accounts.agent_operator.as_deref(),
+        AgentOperator::SCOPE_PROFILE,
+    )?;
 
-    // 2. Verify deadline is not expired
     require!(
-        clock.unix_timestamp <= deadline,
-        RegistryError::DeadlineExpired
+        new_symbol.len() <= AgentAccount::MAX_SYMBOL_LENGTH
+            && new_symbol.bytes().all(|b| b.is_ascii_graphic()),
+        RegistryError::InvalidAgentName
     );
 
-    // 3. Verify deadline is not too far in the future
-    require!(
-        deadline <= clock.unix_timestamp + MAX_DEADLINE_WINDOW,
-        RegistryError::DeadlineTooFar
-    );
+    let agent = &mut ctx.accounts.agent_account;
+    agent.nft_symbol = new_symbol.clone();
 
-    // 4. Build expected message and verify Ed25519 signature
-    let expected_message =
-        build_wallet_set_message(asset, new_wallet, ctx.accounts.owner.key(), deadline);
-    verify_ed25519_signature(
-        &ctx.accounts.instructions_sysvar,
-        new_wallet,
-        &expected_message,
+    emit!(AgentSymbolUpdated {
+        asset: agent.asset,
+        new_symbol,
+        updated_by: ctx.accounts.owner.key(),
+    });
+
+    Ok(())
+}
+
+/// Commit keccak256 of the off-chain agent card referenced by agent_uri
+///
+/// `card_chunk_root` enables chunk-by-chunk verification for cards too large
+/// for one transaction (zero = full-card verification only).
+pub fn commit_agent_card(
+    ctx: Context<CommitAgentCard>,
+    card_hash: [u8; 32],
+    card_chunk_root: [u8; 32],
+) -> Result<()> {
+    verify_owner_or_operator(
+        &ctx.accounts.asset,
+        &ctx.accounts.owner.key(),
+        ctx.accounts.agent_operator.as_deref(),
+        AgentOperator::SCOPE_URI,
     )?;
 
-    // 5. Store wallet and sync owner atomically.
-    // Keeping owner/wallet updates in one instruction avoids stale cached-owner state.
     let agent = &mut ctx.accounts.agent_account;
-    let old_wallet = agent.agent_wallet;
-    let old_owner = agent.owner;
-    let new_owner = ctx.accounts.owner.key();
-
-    agent.agent_wallet = Some(new_wallet);
-    agent.owner = new_owner; // Implicit sync
+    agent.card_hash = card_hash;
+    agent.card_chunk_root = card_chunk_root;
 
-    emit!(WalletUpdated {
-        asset,
-        old_wallet,
-        new_wallet,
-        updated_by: new_owner,
+    emit!(AgentCardCommitted {
+        asset: agent.asset,
+        card_hash,
+        card_chunk_root,
+        committed_by: ctx.accounts.owner.key(),
     });
 
-    if old_owner != new_owner {
-        emit!(AgentOwnerSynced {
-            asset,
-            old_owner,
-            new_owner,
-        });
-    }
-
-    msg!("Agent wallet set to {} (verified via Ed25519 signature)", new_wallet);
-
     Ok(())
 }
 
-/// Set canonical collection pointer in AgentAccount.
-/// First-write-wins once `col_locked` is set to true.
-pub fn set_collection_pointer(ctx: Context<SetCollectionPointer>, col: String) -> Result<()> {
-    set_collection_pointer_inner(ctx, col, true)
-}
-
-/// Set canonical collection pointer with explicit lock behavior.
-/// If `lock` is false, creator can update the pointer until eventually locked.
-pub fn set_collection_pointer_with_options(
-    ctx: Context<SetCollectionPointer>,
-    col: String,
-    lock: bool,
-) -> Result<()> {
-    set_collection_pointer_inner(ctx, col, lock)
-}
-
-fn set_collection_pointer_inner(
-    ctx: Context<SetCollectionPointer>,
-    col: String,
-    lock: bool,
-) -> Result<()> {
-    // Validate child asset account is a live Core asset.
-    get_core_owner(&ctx.accounts.asset).map_err(|_| RegistryError::InvalidAsset)?;
-
-    validate_collection_pointer(&col)?;
+/// Set the agent capability bitmask
+///
+/// Undefined standardized bits (lower 32) are rejected; upper 32 bits are free-form.
+pub fn set_capabilities(ctx: Context<SetCapabilities>, capabilities: u64) -> Result<()> {
+    verify_owner_or_operator(
+        &ctx.accounts.asset,
+        &ctx.accounts.owner.key(),
+        ctx.accounts.agent_operator.as_deref(),
+        AgentOperator::SCOPE_PROFILE,
+    )?;
 
-    let signer = ctx.accounts.owner.key();
-    let agent = &mut ctx.accounts.agent_account;
-    require!(signer == agent.creator, RegistryError::NotAgentCreator);
     require!(
-        !agent.col_locked,
-        RegistryError::CollectionPointerAlreadySet
+        capabilities & AgentAccount::CAP_STANDARD_MASK & !AgentAccount::CAP_KNOWN == 0,
+        RegistryError::InvalidCapabilities
     );
 
-    agent.col = col.clone();
-    if lock {
-        agent.col_locked = true;
-    }
+    let agent = &mut ctx.accounts.agent_account;
+    agent.capabilities = capabilities;
 
-    emit!(CollectionPointerSet {
+    emit!(CapabilitiesUpdated {
         asset: agent.asset,
-        set_by: signer,
-        col,
+        capabilities,
+        updated_by: ctx.accounts.owner.key(),
     });
 
     Ok(())
 }
 
-/// Set parent link in AgentAccount.
-/// First-write-wins once `parent_locked` is set to true.
-/// Authorization rule: signer must match parent creator snapshot.
-pub fn set_parent_asset(ctx: Context<SetParentAsset>, parent_asset: Pubkey) -> Result<()> {
-    set_parent_asset_inner(ctx, parent_asset, true)
-}
+/// Require (or stop requiring) an ERC-8004 FeedbackAuth for new feedback (owner or operator)
+pub fn set_feedback_auth_required(ctx: Context<SetCapabilities>, required: bool) -> Result<()> {
+    verify_owner_or_operator(
+        &ctx.accounts.asset,
+        &ctx.accounts.owner.key(),
+        ctx.accounts.agent_operator.as_deref(),
+        AgentOperator::SCOPE_PROFILE,
+    )?;
 
-/// Set parent link with explicit lock behavior.
-/// If `lock` is false, matching signer can update parent until eventually locked.
-pub fn set_parent_asset_with_options(
-    ctx: Context<SetParentAsset>,
-    parent_asset: Pubkey,
-    lock: bool,
-) -> Result<()> {
-    set_parent_asset_inner(ctx, parent_asset, lock)
-}
+    let agent = &mut ctx.accounts.agent_account;
+    agent.feedback_auth_required = required;
 
-fn set_parent_asset_inner(
-    ctx: Context<SetParentAsset>,
-    parent_asset: Pubkey,
-    lock: bool,
-) -> Result<()> {
-    // Verify caller is current live child owner.
-    verify_core_owner(&ctx.accounts.asset, &ctx.accounts.owner.key())?;
+    emit!(FeedbackAuthRequirementUpdated {
+        asset: agent.asset,
+        required,
+        updated_by: ctx.accounts.owner.key(),
+    });
 
-    // Verify parent Core asset is still valid/livable in Core.
-    get_core_owner(&ctx.accounts.parent_asset_account)
-        .map_err(|_| RegistryError::InvalidParentAsset)?;
+    Ok(())
+}
 
-    let owner = ctx.accounts.owner.key();
-    let parent_creator = ctx.accounts.parent_agent_account.creator;
-    let agent = &mut ctx.accounts.agent_account;
+/// Set the agent lifecycle status (owner or operator)
+///
+/// Allowed: ACTIVE, PAUSED, RETIRED. RETIRED is terminal and a SUSPENDED agent can
+/// only be reinstated by the registry authority.
+pub fn set_agent_status(ctx: Context<SetAgentStatus>, status: u8) -> Result<()> {
+    verify_owner_or_operator(
+        &ctx.accounts.asset,
+        &ctx.accounts.owner.key(),
+        ctx.accounts.agent_operator.as_deref(),
+        AgentOperator::SCOPE_PROFILE,
+    )?;
 
-    require!(!agent.parent_locked, RegistryError::ParentAlreadySet);
-    require!(agent.asset != parent_asset, RegistryError::ParentSelfReference);
     require!(
-        owner == parent_creator,
-        RegistryError::NotParentCreator
+        status <= AgentAccount::STATUS_RETIRED,
+        RegistryError::InvalidAgentStatus
+    );
+    let agent = &mut ctx.accounts.agent_account;
+    require!(
+        agent.status != AgentAccount::STATUS_SUSPENDED,
+        RegistryError::AgentSuspended
+    );
+    require!(
+        agent.status != AgentAccount::STATUS_RETIRED,
+        RegistryError::InvalidAgentStatus
     );
 
-    agent.parent_asset = Some(parent_asset);
-    if lock {
-        agent.parent_locked = true;
-    }
+    let old_status = agent.status;
+    agent.status = status;
 
-    emit!(ParentAssetSet {
+    emit!(AgentStatusUpdated {
         asset: agent.asset,
-        parent_asset,
-        parent_creator,
-        set_by: owner,
+        old_status,
+        new_status: status,
+        updated_by: ctx.accounts.owner.key(),
     });
 
     Ok(())
 }
 
-// ============================================================================
-// Helper functions
-// ============================================================================
+/// Create an OwnerIndex page listing the agents of `owner`
+///
+/// Permissionless: the index only ever holds assets added by register/transfer/sync/reindex,
+/// so anyone may fund a page. Page N > 0 requires page N - 1 to be full.
+pub fn init_owner_index(ctx: Context<InitOwnerIndex>, owner: Pubkey, page: u16) -> Result<()> {
+    if page > 0 {
+        let previous = ctx
+            .accounts
+            .previous_page
+            .as_ref()
+            .ok_or(RegistryError::OwnerIndexMismatch)?;
+        require!(previous.is_full(), RegistryError::OwnerIndexMismatch);
+    }
 
-/// Build the message that wallet owner must sign for set_agent_wallet
-fn build_wallet_set_message(
-    asset: Pubkey,
-    new_wallet: Pubkey,
-    owner: Pubkey,
-    deadline: i64,
-) -> Vec<u8> {
-    let mut message = Vec::with_capacity(WALLET_SET_MESSAGE_PREFIX.len() + 32 + 32 + 32 + 8);
-    message.extend_from_slice(WALLET_SET_MESSAGE_PREFIX);
-    message.extend_from_slice(asset.as_ref());
-    message.extend_from_slice(new_wallet.as_ref());
-    message.extend_from_slice(owner.as_ref());
-    message.extend_from_slice(&deadline.to_le_bytes());
-    message
+    let index = &mut ctx.accounts.owner_index;
+    index.owner = owner;
+    index.page = page;
+    index.bump = ctx.bumps.owner_index;
+    index.assets = Vec::new();
+
+    msg!("Owner index page {} created for {}", page, owner);
+
+    Ok(())
 }
 
-fn validate_collection_pointer(col: &str) -> Result<()> {
-    require!(
-        col.len() <= AgentAccount::MAX_COL_LENGTH,
-        RegistryError::InvalidCollectionPointer
-    );
-    require!(
-        col.starts_with(COLLECTION_POINTER_PREFIX),
-        RegistryError::InvalidCollectionPointer
-    );
+/// Bring one asset's entry on an OwnerIndex page in line with the agent's cached owner
+///
+/// Permissionless crank for pages that register, transfer, sync or deregister did not
+/// receive: the asset is removed from a page of any other wallet (or once the agent is
+/// deregistered) and added to a page of its cached owner. Run sync_owner first when the
+/// cached owner may be stale.
+pub fn reindex_owner(ctx: Context<ReindexOwner>) -> Result<()> {
+    let agent_info = ctx.accounts.agent_account.to_account_info();
+    let cached_owner = if *agent_info.owner == crate::ID && !agent_info.data_is_empty() {
+        Some(AgentAccount::try_deserialize(&mut &agent_info.try_borrow_data()?[..])?.owner)
+    } else {
+        None
+    };
 
-    let cid = &col[COLLECTION_POINTER_PREFIX.len()..];
-    require!(!cid.is_empty(), RegistryError::InvalidCollectionPointer);
-    require!(
-        cid.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit()),
-        RegistryError::InvalidCollectionPointer
-    );
+    let index = &mut ctx.accounts.owner_index;
+    let asset = ctx.accounts.asset.key();
+    let listed = index.assets.contains(&asset);
+    if cached_owner == Some(index.owner) {
+        if !listed {
+            let owner = index.owner;
+            add_owner_index_entry(index, owner, asset)?;
+        }
+    } else if listed {
+        let owner = index.owner;
+        remove_owner_index_entry(index, owner, asset)?;
+    }
+
+    msg!("Owner index page {} of {} reindexed for {}", index.page, index.owner, asset);
 
     Ok(())
 }
 
-/// Verify Ed25519 signature via sysvar introspection
-/// SECURITY: Ed25519 instruction MUST be immediately before this instruction (current_index - 1)
-fn verify_ed25519_signature(
-    instructions_sysvar: &AccountInfo,
-    expected_signer: Pubkey,
-    expected_message: &[u8],
+/// Replace the agent's category tags (max 4) and keep per-tag counters current
+///
+/// remaining_accounts: one writable TagCounter PDA per currently stored tag (slot
+/// order), followed by one per new tag (argument order). Counters are created on
+/// first use and paid by the signer.
+pub fn set_agent_tags<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SetAgentTags<'info>>,
+    tags: Vec<String>,
 ) -> Result<()> {
-    let current_idx = load_current_index_checked(instructions_sysvar)
-        .map_err(|_| RegistryError::MissingSignatureVerification)?;
-
-    // SECURITY FIX: Ed25519 instruction MUST be at index (current_index - 1)
-    // This prevents signature reuse attacks where an attacker places a valid
-    // signature earlier in the transaction for a different purpose
-    require!(current_idx >= 1, RegistryError::MissingSignatureVerification);
-    let ed25519_idx = (current_idx - 1) as usize;
+    verify_owner_or_operator(
+        &ctx.accounts.asset,
+        &ctx.accounts.owner.key(),
+        ctx.accounts.agent_operator.as_deref(),
+        AgentOperator::SCOPE_PROFILE,
+    )?;
 
-    let ix = load_instruction_at_checked(ed25519_idx, instructions_sysvar)
-        .map_err(|_| RegistryError::MissingSignatureVerification)?;
+    require!(tags.len() <= AgentTags::MAX_TAGS, RegistryError::InvalidTag);
+    let mut new_hashes: Vec<[u8; 32]> = Vec::with_capacity(tags.len());
+    for tag in &tags {
+        require!(is_valid_category_tag(tag), RegistryError::InvalidTag);
+        let hash = keccak::hash(tag.as_bytes()).0;
+        require!(!new_hashes.contains(&hash), RegistryError::InvalidTag);
+        new_hashes.push(hash);
+    }
 
-    // Must be Ed25519 program
+    let old_count = ctx.accounts.agent_tags.count as usize;
+    let old_hashes: Vec<[u8; 32]> = ctx.accounts.agent_tags.tags[..old_count].to_vec();
     require!(
-        ix.program_id == ed25519_program::ID,
-        RegistryError::MissingSignatureVerification
+        ctx.remaining_accounts.len() == old_count + new_hashes.len(),
+        RegistryError::TagCounterMismatch
     );
 
-    // Validate instruction data length (header is 16 bytes minimum)
-    require!(ix.data.len() >= 16, RegistryError::InvalidSignature);
+    let payer = ctx.accounts.owner.to_account_info();
+    let system_program_info = ctx.accounts.system_program.to_account_info();
+    let (old_counters, new_counters) = ctx.remaining_accounts.split_at(old_count);
+    for (hash, counter_info) in old_hashes.iter().zip(old_counters) {
+        adjust_tag_counter(counter_info, &payer, &system_program_info, hash, false)?;
+    }
+    for (hash, counter_info) in new_hashes.iter().zip(new_counters) {
+        adjust_tag_counter(counter_info, &payer, &system_program_info, hash, true)?;
+    }
 
-    let num_signatures = ix.data[0];
-    require!(num_signatures == 1, RegistryError::InvalidSignature);
+    let asset = ctx.accounts.asset.key();
+    let agent_tags = &mut ctx.accounts.agent_tags;
+    agent_tags.asset = asset;
+    agent_tags.tags = [[0u8; 32]; AgentTags::MAX_TAGS];
+    agent_tags.tags[..new_hashes.len()].copy_from_slice(&new_hashes);
+    agent_tags.count = new_hashes.len() as u8;
+    agent_tags.bump = ctx.bumps.agent_tags;
 
-    // SECURITY FIX: Verify all instruction indices are u16::MAX (0xFFFF)
-    // This ensures signature, pubkey, and message are INLINE in this instruction,
-    // not referenced from another instruction in the transaction.
-    // Without this check, an attacker could craft an Ed25519 instruction that
-    // references data from a different instruction, bypassing our validation.
-    let sig_instruction_index = u16::from_le_bytes([ix.data[4], ix.data[5]]);
-    let pubkey_instruction_index = u16::from_le_bytes([ix.data[8], ix.data[9]]);
-    let msg_instruction_index = u16::from_le_bytes([ix.data[14], ix.data[15]]);
+    emit!(AgentTagsUpdated {
+        asset,
+        tag_hashes: new_hashes,
+        tags,
+    });
+
+    Ok(())
+}
 
+/// Verify the full agent card against the committed hash
+pub fn verify_agent_card(ctx: Context<VerifyAgentCard>, card: Vec<u8>) -> Result<()> {
+    let agent = &ctx.accounts.agent_account;
+    require!(agent.card_hash != [0u8; 32], RegistryError::AgentCardNotCommitted);
     require!(
-        sig_instruction_index == u16::MAX
-            && pubkey_instruction_index == u16::MAX
-            && msg_instruction_index == u16::MAX,
-        RegistryError::InvalidSignature
+        compute_card_hash(&card) == agent.card_hash,
+        RegistryError::AgentCardMismatch
     );
 
-    let signature_offset = u16::from_le_bytes([ix.data[2], ix.data[3]]) as usize;
-    let pubkey_offset = u16::from_le_bytes([ix.data[6], ix.data[7]]) as usize;
-    let message_offset = u16::from_le_bytes([ix.data[10], ix.data[11]]) as usize;
-    let message_size = u16::from_le_bytes([ix.data[12], ix.data[13]]) as usize;
+    emit!(AgentCardVerified {
+        asset: agent.asset,
+        card_hash: agent.card_hash,
+        verified_by: ctx.accounts.verifier.key(),
+        chunk_index: None,
+    });
 
-    // Validate bounds
+    Ok(())
+}
+
+/// Verify one agent card chunk against the committed chunk root
+pub fn verify_agent_card_chunk(
+    ctx: Context<VerifyAgentCard>,
+    chunk_index: u32,
+    chunk: Vec<u8>,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    let agent = &ctx.accounts.agent_account;
     require!(
-        pubkey_offset + 32 <= ix.data.len()
-            && message_offset + message_size <= ix.data.len()
-            && signature_offset + 64 <= ix.data.len(),
-        RegistryError::InvalidSignature
+        agent.card_hash != [0u8; 32] && agent.card_chunk_root != [0u8; 32],
+        RegistryError::AgentCardNotCommitted
+    );
+    require!(
+        verify_card_chunk(&agent.card_chunk_root, chunk_index, &chunk, &proof),
+        RegistryError::AgentCardMismatch
     );
 
-    // Verify pubkey matches expected signer
-    let pubkey_bytes: [u8; 32] = ix.data[pubkey_offset..pubkey_offset + 32]
-        .try_into()
-        .map_err(|_| RegistryError::InvalidSignature)?;
-    let signer = Pubkey::from(pubkey_bytes);
-    require!(signer == expected_signer, RegistryError::InvalidSignature);
-
-    // Verify message matches expected message
-    let message = &ix.data[message_offset..message_offset + message_size];
-    require!(message == expected_message, RegistryError::InvalidSignature);
+    emit!(AgentCardVerified {
+        asset: agent.asset,
+        card_hash: agent.card_hash,
+        verified_by: ctx.accounts.verifier.key(),
+        chunk_index: Some(chunk_index),
+    });
 
     Ok(())
 }
 
-/// Create Core asset via CPI
-#[inline(never)]
-fn create_core_asset_cpi<'info>(
-    mpl_core_program: &AccountInfo<'info>,
-    asset: &AccountInfo<'info>,
-    collection: &AccountInfo<'info>,
+/// Sync agent owner from Core asset
+/// Automatically resets agent_wallet when ownership changes (security feature)
+/// Use case: After marketplace transfer, new owner calls this to sync + reset wallet
+///
+/// Permissionless crank: when the cache was stale and the optional registry config,
+/// treasury and cranker accounts are passed, the registry's sync_bounty is paid out.
+pub fn sync_owner(ctx: Context<SyncOwner>) -> Result<()> {
+    let new_owner = get_core_owner(&ctx.accounts.asset)?;
+    let old_owner = ctx.accounts.agent_account.owner;
+    let stale = detect_stale_owner(&ctx.accounts.agent_account, new_owner)?;
+    apply_owner_sync(&mut ctx.accounts.agent_account, new_owner)?;
+    move_owner_index(
+        ctx.accounts.from_owner_index.as_deref_mut(),
+        ctx.accounts.to_owner_index.as_deref_mut(),
+        ctx.accounts.agent_account.asset,
+        old_owner,
+        new_owner,
+    )?;
+
+    // Bounty only for crankers that actually repaired a stale cache
+    if stale {
+        if let (Some(registry), Some(treasury), Some(cranker)) = (
+            ctx.accounts.registry_config.as_ref(),
+            ctx.accounts.treasury.as_mut(),
+            ctx.accounts.cranker.as_ref(),
+        ) {
+            pay_sync_bounty(registry, treasury, cranker, ctx.accounts.agent_account.asset)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Sync owner of a Token-2022 backed agent from the token account holding the NFT
+/// Permissionless, same wallet-reset semantics as sync_owner
+pub fn sync_token_owner(ctx: Context<SyncTokenOwner>) -> Result<()> {
+    let new_owner = ctx.accounts.holder_token_account.owner;
+    let old_owner = ctx.accounts.agent_account.owner;
+    detect_stale_owner(&ctx.accounts.agent_account, new_owner)?;
+    apply_owner_sync(&mut ctx.accounts.agent_account, new_owner)?;
+    move_owner_index(
+        ctx.accounts.from_owner_index.as_deref_mut(),
+        ctx.accounts.to_owner_index.as_deref_mut(),
+        ctx.accounts.agent_account.asset,
+        old_owner,
+        new_owner,
+    )
+}
+
+/// Get agent owner (CACHED value - may be stale after external transfer)
+///
+/// WARNING: This returns the cached owner from AgentAccount, not the live Core owner.
+/// After an external NFT transfer (via Metaplex Core directly), this value becomes stale
+/// until sync_owner is called. For authoritative ownership, read the Core asset directly.
+///
+/// Use cases:
+/// - Quick lookup when staleness is acceptable
+/// - UI display (with note about potential staleness)
+///
+/// For authoritative ownership verification, use verify_core_owner() or read Core asset.
+pub fn owner_of(ctx: Context<OwnerOf>) -> Result<Pubkey> {
+    Ok(ctx.accounts.agent_account.owner)
+}
+
+/// Get agent record
+///
+/// Like every getter here, the value is written with set_return_data (Anchor return
+/// value), so CPI callers read it via get_return_data and simulators via returnData.
+pub fn get_agent(ctx: Context<GetAgent>) -> Result<AgentView> {
+    Ok(AgentView::from(&*ctx.accounts.agent_account))
+}
+
+/// Get a single metadata entry (key_hash = SHA256(key)[0..16])
+pub fn get_metadata(ctx: Context<GetMetadata>, _key_hash: [u8; 16]) -> Result<MetadataView> {
+    let entry = &ctx.accounts.metadata_entry;
+    Ok(MetadataView {
+        asset: entry.asset,
+        immutable: entry.immutable,
+        expires_at: entry.expires_at,
+        key: entry.metadata_key.clone(),
+        value: entry.metadata_value.clone(),
+    })
+}
+
+/// Get authoritative Core owner (reads directly from Metaplex Core asset)
+///
+/// This always returns the current owner regardless of cache state.
+/// Use this when authoritative ownership is required. When the agent account is
+/// passed, drift from the cached owner is reported via StaleOwnerDetected.
+pub fn core_owner_of(ctx: Context<CoreOwnerOf>) -> Result<Pubkey> {
+    let owner = get_core_owner(&ctx.accounts.asset)?;
+    if let Some(agent) = ctx.accounts.agent_account.as_ref() {
+        detect_stale_owner(agent, owner)?;
+    }
+    Ok(owner)
+}
+
+/// Transfer agent with automatic owner sync
+/// Automatically resets agent_wallet to None on transfer for security
+pub fn transfer_agent(ctx: Context<TransferAgent>) -> Result<()> {
+    // Verify current ownership
+    verify_core_owner(&ctx.accounts.asset, &ctx.accounts.owner.key())?;
+
+    // Prevent self-transfer
+    require!(
+        ctx.accounts.owner.key() != ctx.accounts.new_owner.key(),
+        RegistryError::TransferToSelf
+    );
+
+    let old_owner = ctx.accounts.owner.key();
+    let new_owner = ctx.accounts.new_owner.key();
+
+    // Transfer Core asset (opening the collection transfer gate for this CPI only)
+    let gate_info = ctx.accounts.transfer_gate.as_ref().map(|g| g.to_account_info());
+    let gate_result = open_transfer_gate(gate_info.as_ref())?;
+    TransferV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+        .asset(&ctx.accounts.asset.to_account_info())
+        .collection(Some(&ctx.accounts.collection.to_account_info()))
+        .payer(&ctx.accounts.owner.to_account_info())
+        .authority(Some(&ctx.accounts.owner.to_account_info()))
+        .new_owner(&ctx.accounts.new_owner.to_account_info())
+        .add_remaining_accounts(&transfer_gate_accounts(gate_info.as_ref()))
+        .invoke()?;
+    close_transfer_gate(gate_info.as_ref(), gate_result)?;
+
+    apply_transfer(&mut ctx.accounts.agent_account, old_owner, new_owner, old_owner)?;
+    move_owner_index(
+        ctx.accounts.from_owner_index.as_deref_mut(),
+        ctx.accounts.to_owner_index.as_deref_mut(),
+        ctx.accounts.asset.key(),
+        old_owner,
+        new_owner,
+    )?;
+
+    msg!("Agent transferred: {} -> {}", old_owner, new_owner);
+
+    Ok(())
+}
+
+/// Configure, update or disable the owner council (Core owner only)
+///
+/// Members are co-owners whose threshold approvals can authorize URI, metadata and
+/// transfer actions. While enabled the council PDA holds the Core TransferDelegate
+/// authority (replacing a pending two-step transfer delegate, if any). Every call
+/// bumps the council epoch so proposals approved under the old membership go stale.
+/// Pass no members and threshold 0 to disable.
+pub fn set_owner_council(
+    ctx: Context<SetOwnerCouncil>,
+    members: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    let owner = ctx.accounts.owner.key();
+    verify_core_owner(&ctx.accounts.asset, &owner)?;
+    require!(
+        is_valid_council(&members, threshold, OwnerCouncil::MAX_MEMBERS),
+        RegistryError::InvalidCouncil
+    );
+
+    let asset = ctx.accounts.asset.key();
+    let council_key = ctx.accounts.owner_council.key();
+    let enabled = threshold > 0;
+
+    if !ctx.accounts.agent_account.soulbound {
+        let delegate_authority = fetch_plugin::<BaseAssetV1, TransferDelegate>(
+            &ctx.accounts.asset.to_account_info(),
+            PluginType::TransferDelegate,
+        )
+        .ok()
+        .map(|(authority, _, _)| authority);
+        let held_by_council = matches!(
+            delegate_authority,
+            Some(PluginAuthority::Address { address }) if address == council_key
+        );
+
+        if enabled && delegate_authority.is_none() {
+            AddPluginV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+                .asset(&ctx.accounts.asset.to_account_info())
+                .collection(Some(&ctx.accounts.collection.to_account_info()))
+                .payer(&ctx.accounts.owner.to_account_info())
+                .authority(Some(&ctx.accounts.owner.to_account_info()))
+                .system_program(&ctx.accounts.system_program.to_account_info())
+                .plugin(Plugin::TransferDelegate(TransferDelegate {}))
+                .init_authority(PluginAuthority::Address { address: council_key })
+                .invoke()?;
+        } else if enabled && !held_by_council {
+            ApprovePluginAuthorityV1CpiBuilder::new(
+                &ctx.accounts.mpl_core_program.to_account_info(),
+            )
+            .asset(&ctx.accounts.asset.to_account_info())
+            .collection(Some(&ctx.accounts.collection.to_account_info()))
+            .payer(&ctx.accounts.owner.to_account_info())
+            .authority(Some(&ctx.accounts.owner.to_account_info()))
+            .system_program(&ctx.accounts.system_program.to_account_info())
+            .plugin_type(PluginType::TransferDelegate)
+            .new_authority(PluginAuthority::Address { address: council_key })
+            .invoke()?;
+        } else if !enabled && held_by_council {
+            RevokePluginAuthorityV1CpiBuilder::new(
+                &ctx.accounts.mpl_core_program.to_account_info(),
+            )
+            .asset(&ctx.accounts.asset.to_account_info())
+            .collection(Some(&ctx.accounts.collection.to_account_info()))
+            .payer(&ctx.accounts.owner.to_account_info())
+            .authority(Some(&ctx.accounts.owner.to_account_info()))
+            .system_program(&ctx.accounts.system_program.to_account_info())
+            .plugin_type(PluginType::TransferDelegate)
+            .invoke()?;
+        }
+    }
+
+    let council = &mut ctx.accounts.owner_council;
+    if council.asset == Pubkey::default() {
+        council.asset = asset;
+        council.bump = ctx.bumps.owner_council;
+    }
+    council.owner = owner;
+    council.members = members.clone();
+    council.threshold = threshold;
+    council.epoch = council.epoch.checked_add(1).ok_or(RegistryError::Overflow)?;
+
+    emit!(OwnerCouncilUpdated {
+        asset,
+        owner,
+        members,
+        threshold,
+        epoch: council.epoch,
+    });
+
+    msg!("Owner council for asset {} set (threshold {})", asset, threshold);
+
+    Ok(())
+}
+
+/// Open a council proposal committing to one action (see identity::council)
+///
+/// The proposer's approval is recorded immediately.
+pub fn propose_council_action(
+    ctx: Context<ProposeCouncilAction>,
+    action: u8,
+    action_hash: [u8; 32],
+) -> Result<()> {
+    let member = ctx.accounts.member.key();
+    let asset = ctx.accounts.asset.key();
+    require!(action <= COUNCIL_ACTION_TRANSFER, RegistryError::CouncilActionMismatch);
+
+    let council = &mut ctx.accounts.owner_council;
+    let index = council.member_index(&member).ok_or(RegistryError::NotCouncilMember)?;
+    let proposal_id = council.proposal_count;
+    council.proposal_count = proposal_id.checked_add(1).ok_or(RegistryError::Overflow)?;
+    let epoch = council.epoch;
+
+    let proposal = &mut ctx.accounts.council_proposal;
+    proposal.asset = asset;
+    proposal.proposal_id = proposal_id;
+    proposal.epoch = epoch;
+    proposal.action = action;
+    proposal.action_hash = action_hash;
+    proposal.approvals = 1u8 << index;
+    proposal.proposer = member;
+    proposal.executed = false;
+    proposal.created_at = Clock::get()?.unix_timestamp;
+    proposal.bump = ctx.bumps.council_proposal;
+
+    emit!(CouncilActionProposed {
+        asset,
+        proposal_id,
+        action,
+        action_hash,
+        proposer: member,
+    });
+
+    msg!("Council proposal {} opened for asset {}", proposal_id, asset);
+
+    Ok(())
+}
+
+/// Approve a pending council proposal
+pub fn approve_council_action(ctx: Context<ApproveCouncilAction>) -> Result<()> {
+    let member = ctx.accounts.member.key();
+    let council = &ctx.accounts.owner_council;
+    let index = council.member_index(&member).ok_or(RegistryError::NotCouncilMember)?;
+
+    let proposal = &mut ctx.accounts.council_proposal;
+    require!(
+        !proposal.executed && proposal.epoch == council.epoch,
+        RegistryError::CouncilProposalStale
+    );
+    let bit = 1u8 << index;
+    require!(proposal.approvals & bit == 0, RegistryError::CouncilAlreadyApproved);
+    proposal.approvals |= bit;
+
+    emit!(CouncilActionApproved {
+        asset: proposal.asset,
+        proposal_id: proposal.proposal_id,
+        member,
+        approvals: approval_count(proposal.approvals),
+    });
+
+    Ok(())
+}
+
+/// Execute a council-approved transfer
+///
+/// The OwnerCouncil PDA signs as Core TransferDelegate. Core hands the delegate
+/// authority back to the new owner, and the council is void once the owner changes.
+pub fn council_transfer_agent(ctx: Context<CouncilTransferAgent>) -> Result<()> {
+    let member = ctx.accounts.member.key();
+    let asset = ctx.accounts.asset.key();
+    let new_owner = ctx.accounts.new_owner.key();
+    let old_owner = get_core_owner(&ctx.accounts.asset)?;
+    require!(old_owner != new_owner, RegistryError::TransferToSelf);
+
+    let action_hash =
+        compute_council_action_hash(&asset, COUNCIL_ACTION_TRANSFER, new_owner.as_ref());
+    authorize_council_action(
+        &ctx.accounts.asset,
+        &member,
+        Some(&*ctx.accounts.owner_council),
+        Some(&mut *ctx.accounts.council_proposal),
+        &action_hash,
+    )?;
+
+    let council_bump = ctx.accounts.owner_council.bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[SEED_OWNER_COUNCIL, asset.as_ref(), &[council_bump]]];
+
+    let gate_info = ctx.accounts.transfer_gate.as_ref().map(|g| g.to_account_info());
+    let gate_result = open_transfer_gate(gate_info.as_ref())?;
+    TransferV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+        .asset(&ctx.accounts.asset.to_account_info())
+        .collection(Some(&ctx.accounts.collection.to_account_info()))
+        .payer(&ctx.accounts.member.to_account_info())
+        .authority(Some(&ctx.accounts.owner_council.to_account_info()))
+        .new_owner(&ctx.accounts.new_owner.to_account_info())
+        .system_program(Some(&ctx.accounts.system_program.to_account_info()))
+        .add_remaining_accounts(&transfer_gate_accounts(gate_info.as_ref()))
+        .invoke_signed(signer_seeds)?;
+    close_transfer_gate(gate_info.as_ref(), gate_result)?;
+
+    apply_transfer(&mut ctx.accounts.agent_account, old_owner, new_owner, member)?;
+    move_owner_index(
+        ctx.accounts.from_owner_index.as_deref_mut(),
+        ctx.accounts.to_owner_index.as_deref_mut(),
+        asset,
+        old_owner,
+        new_owner,
+    )?;
+
+    msg!("Council transfer executed: {} -> {}", old_owner, new_owner);
+
+    Ok(())
+}
+
+/// Propose a two-step transfer
+///
+/// The recipient must call accept_transfer before the asset moves. The PendingTransfer
+/// PDA becomes the asset's Core TransferDelegate so acceptance needs no proposer signature.
+pub fn propose_transfer(ctx: Context<ProposeTransfer>, new_owner: Pubkey) -> Result<()> {
+    let owner = ctx.accounts.owner.key();
+    verify_core_owner(&ctx.accounts.asset, &owner)?;
+    require!(owner != new_owner, RegistryError::TransferToSelf);
+
+    let asset = ctx.accounts.asset.key();
+    let pending_key = ctx.accounts.pending_transfer.key();
+
+    AddPluginV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+        .asset(&ctx.accounts.asset.to_account_info())
+        .collection(Some(&ctx.accounts.collection.to_account_info()))
+        .payer(&ctx.accounts.owner.to_account_info())
+        .authority(Some(&ctx.accounts.owner.to_account_info()))
+        .system_program(&ctx.accounts.system_program.to_account_info())
+        .plugin(Plugin::TransferDelegate(TransferDelegate {}))
+        .init_authority(PluginAuthority::Address { address: pending_key })
+        .invoke()?;
+
+    let pending = &mut ctx.accounts.pending_transfer;
+    pending.asset = asset;
+    pending.from = owner;
+    pending.to = new_owner;
+    pending.created_at = Clock::get()?.unix_timestamp;
+    pending.bump = ctx.bumps.pending_transfer;
+
+    emit!(TransferProposed {
+        asset,
+        from: owner,
+        to: new_owner,
+    });
+
+    msg!("Transfer proposed: {} -> {} for asset {}", owner, new_owner, asset);
+
+    Ok(())
+}
+
+/// Accept a pending transfer
+///
+/// Executes the Core transfer signed by the PendingTransfer delegate, syncs the cached
+/// owner and resets the agent wallet exactly like transfer_agent.
+pub fn accept_transfer(ctx: Context<AcceptTransfer>) -> Result<()> {
+    let old_owner = ctx.accounts.pending_transfer.from;
+    let new_owner = ctx.accounts.new_owner.key();
+
+    // Proposer must still own the asset (an external Core transfer voids the proposal)
+    verify_core_owner(&ctx.accounts.asset, &old_owner)?;
+
+    let asset = ctx.accounts.asset.key();
+    let pending_bump = ctx.accounts.pending_transfer.bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        SEED_PENDING_TRANSFER,
+        asset.as_ref(),
+        &[pending_bump],
+    ]];
+
+    // Core revokes the TransferDelegate authority back to the owner on transfer
+    let gate_info = ctx.accounts.transfer_gate.as_ref().map(|g| g.to_account_info());
+    let gate_result = open_transfer_gate(gate_info.as_ref())?;
+    TransferV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+        .asset(&ctx.accounts.asset.to_account_info())
+        .collection(Some(&ctx.accounts.collection.to_account_info()))
+        .payer(&ctx.accounts.new_owner.to_account_info())
+        .authority(Some(&ctx.accounts.pending_transfer.to_account_info()))
+        .new_owner(&ctx.accounts.new_owner.to_account_info())
+        .system_program(Some(&ctx.accounts.system_program.to_account_info()))
+        .add_remaining_accounts(&transfer_gate_accounts(gate_info.as_ref()))
+        .invoke_signed(signer_seeds)?;
+    close_transfer_gate(gate_info.as_ref(), gate_result)?;
+
+    apply_transfer(&mut ctx.accounts.agent_account, old_owner, new_owner, new_owner)?;
+    move_owner_index(
+        ctx.accounts.from_owner_index.as_deref_mut(),
+        ctx.accounts.to_owner_index.as_deref_mut(),
+        ctx.accounts.asset.key(),
+        old_owner,
+        new_owner,
+    )?;
+
+    msg!("Transfer accepted: {} -> {}", old_owner, new_owner);
+
+    Ok(())
+}
+
+/// Cancel a pending transfer and recover rent
+///
+/// Removes the TransferDelegate plugin when the proposer still owns the asset.
+pub fn cancel_transfer(ctx: Context<CancelTransfer>) -> Result<()> {
+    let owner = ctx.accounts.owner.key();
+    let asset = ctx.accounts.asset.key();
+
+    if get_core_owner(&ctx.accounts.asset)? == owner {
+        RemovePluginV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+            .asset(&ctx.accounts.asset.to_account_info())
+            .collection(Some(&ctx.accounts.collection.to_account_info()))
+            .payer(&ctx.accounts.owner.to_account_info())
+            .authority(Some(&ctx.accounts.owner.to_account_info()))
+            .system_program(&ctx.accounts.system_program.to_account_info())
+            .plugin_type(PluginType::TransferDelegate)
+            .invoke()?;
+    }
+
+    emit!(TransferCancelled {
+        asset,
+        from: owner,
+        to: ctx.accounts.pending_transfer.to,
+    });
+
+    msg!("Transfer cancelled for asset {}", asset);
+
+    Ok(())
+}
+
+/// Set agent wallet with Ed25519 signature verification
+///
+/// Message format: "8004_WALLET_SET:" || asset (32 bytes) || new_wallet (32 bytes) || owner (32 bytes) || deadline (8 bytes LE)
+/// Wallet is stored directly in AgentAccount (no separate PDA = no rent cost)
+pub fn set_agent_wallet(
+    ctx: Context<SetAgentWallet>,
+    new_wallet: Pubkey,
+    deadline: i64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let asset = ctx.accounts.asset.key();
+
+    // 1. Verify caller is Core asset owner
+    verify_core_owner(&ctx.accounts.asset, &ctx.accounts.owner.key())?;
+
+    // 2. Verify deadline is not expired
+    require!(
+        clock.unix_timestamp <= deadline,
+        RegistryError::DeadlineExpired
+    );
+
+    // 3. Verify deadline is not too far in the future
+    require!(
+        deadline <= clock.unix_timestamp + MAX_DEADLINE_WINDOW,
+        RegistryError::DeadlineTooFar
+    );
+
+    // 4. Build expected message and verify Ed25519 signature
+    let expected_message =
+        build_wallet_set_message(asset, new_wallet, ctx.accounts.owner.key(), deadline);
+    verify_ed25519_signature(
+        &ctx.accounts.instructions_sysvar,
+        new_wallet,
+        &expected_message,
+    )?;
+
+    // 5. Store wallet and sync owner atomically.
+    store_agent_wallet(
+        &mut ctx.accounts.agent_account,
+        new_wallet,
+        ctx.accounts.owner.key(),
+    )?;
+
+    msg!("Agent wallet set to {} (verified via Ed25519 signature)", new_wallet);
+
+    Ok(())
+}
+
+/// Set agent wallet with the wallet co-signing the transaction
+///
+/// Key control is proven by the wallet's transaction signature, so no
+/// Ed25519 instruction or deadline is needed.
+pub fn set_agent_wallet_cosigned(ctx: Context<SetAgentWalletCosigned>) -> Result<()> {
+    verify_core_owner(&ctx.accounts.asset, &ctx.accounts.owner.key())?;
+
+    let new_wallet = ctx.accounts.new_wallet.key();
+    store_agent_wallet(
+        &mut ctx.accounts.agent_account,
+        new_wallet,
+        ctx.accounts.owner.key(),
+    )?;
+
+    msg!("Agent wallet set to {} (verified via co-signature)", new_wallet);
+
+    Ok(())
+}
+
+/// Grant an operator delegation
+///
+/// The operator can then call set_agent_uri, set_metadata_pda and delete_metadata_pda
+/// on behalf of the owner. Re-granting after an ownership change refreshes `granted_by`.
+pub fn grant_operator(ctx: Context<GrantOperator>) -> Result<()> {
+    grant_operator_inner(ctx, AgentOperator::SCOPE_ALL, 0)
+}
+
+/// Grant a time-limited session key restricted to `scope` (AgentOperator::SCOPE_* bits)
+///
+/// Uses the same PDA as grant_operator, so granting a session to an existing operator
+/// narrows it (and vice versa). Expired sessions are rejected and can be revoked.
+pub fn grant_session(ctx: Context<GrantOperator>, scope: u64, expires_at: i64) -> Result<()> {
+    require!(
+        scope != 0 && expires_at > Clock::get()?.unix_timestamp,
+        RegistryError::InvalidSession
+    );
+    grant_operator_inner(ctx, scope, expires_at)
+}
+
+fn grant_operator_inner(ctx: Context<GrantOperator>, scope: u64, expires_at: i64) -> Result<()> {
+    let owner = ctx.accounts.owner.key();
+    verify_core_owner(&ctx.accounts.asset, &owner)?;
+
+    let asset = ctx.accounts.asset.key();
+    let operator_key = ctx.accounts.operator.key();
+    require!(operator_key != owner, RegistryError::Unauthorized);
+
+    let operator = &mut ctx.accounts.agent_operator;
+    operator.asset = asset;
+    operator.operator = operator_key;
+    operator.granted_by = owner;
+    operator.bump = ctx.bumps.agent_operator;
+    operator.scope = scope;
+    operator.expires_at = expires_at;
+
+    emit!(OperatorGranted {
+        asset,
+        operator: operator_key,
+        granted_by: owner,
+        scope,
+        expires_at,
+    });
+
+    msg!("Operator {} granted for asset {}", operator_key, asset);
+
+    Ok(())
+}
+
+/// Revoke an operator delegation and recover rent
+///
+/// Callable by the current Core owner, including after a transfer, so stale
+/// grants from a previous owner can be cleaned up.
+pub fn revoke_operator(ctx: Context<RevokeOperator>) -> Result<()> {
+    let owner = ctx.accounts.owner.key();
+    verify_core_owner(&ctx.accounts.asset, &owner)?;
+
+    let operator = &ctx.accounts.agent_operator;
+    emit!(OperatorRevoked {
+        asset: operator.asset,
+        operator: operator.operator,
+        revoked_by: owner,
+    });
+
+    msg!("Operator {} revoked for asset {}", operator.operator, operator.asset);
+
+    Ok(())
+}
+
+/// Set canonical collection pointer in AgentAccount.
+/// First-write-wins once `col_locked` is set to true.
+pub fn set_collection_pointer(ctx: Context<SetCollectionPointer>, col: String) -> Result<()> {
+    set_collection_pointer_inner(ctx, col, true)
+}
+
+/// Set canonical collection pointer with explicit lock behavior.
+/// If `lock` is false, creator can update the pointer until eventually locked.
+pub fn set_collection_pointer_with_options(
+    ctx: Context<SetCollectionPointer>,
+    col: String,
+    lock: bool,
+) -> Result<()> {
+    set_collection_pointer_inner(ctx, col, lock)
+}
+
+fn set_collection_pointer_inner(
+    ctx: Context<SetCollectionPointer>,
+    col: String,
+    lock: bool,
+) -> Result<()> {
+    // Validate child asset account is a live Core asset.
+    get_core_owner(&ctx.accounts.asset).map_err(|_| RegistryError::InvalidAsset)?;
+
+    validate_collection_pointer(&col)?;
+
+    let signer = ctx.accounts.owner.key();
+    let agent = &mut ctx.accounts.agent_account;
+    require!(signer == agent.creator, RegistryError::NotAgentCreator);
+    require!(
+        !agent.col_locked,
+        RegistryError::CollectionPointerAlreadySet
+    );
+
+    agent.col = col.clone();
+    if lock {
+        agent.col_locked = true;
+    }
+
+    emit!(CollectionPointerSet {
+        asset: agent.asset,
+        set_by: signer,
+        col,
+    });
+
+    Ok(())
+}
+
+/// Set parent link in AgentAccount.
+/// First-write-wins once `parent_locked` is set to true.
+/// Authorization rule: signer must match parent creator snapshot.
+pub fn set_parent_asset(ctx: Context<SetParentAsset>, parent_asset: Pubkey) -> Result<()> {
+    set_parent_asset_inner(ctx, parent_asset, true)
+}
+
+/// Set parent link with explicit lock behavior.
+/// If `lock` is false, matching signer can update parent until eventually locked.
+pub fn set_parent_asset_with_options(
+    ctx: Context<SetParentAsset>,
+    parent_asset: Pubkey,
+    lock: bool,
+) -> Result<()> {
+    set_parent_asset_inner(ctx, parent_asset, lock)
+}
+
+fn set_parent_asset_inner(
+    ctx: Context<SetParentAsset>,
+    parent_asset: Pubkey,
+    lock: bool,
+) -> Result<()> {
+    // Verify caller is current live child owner.
+    verify_core_owner(&ctx.accounts.asset, &ctx.accounts.owner.key())?;
+
+    // Verify parent Core asset is still valid/livable in Core.
+    get_core_owner(&ctx.accounts.parent_asset_account)
+        .map_err(|_| RegistryError::InvalidParentAsset)?;
+
+    let owner = ctx.accounts.owner.key();
+    let parent_creator = ctx.accounts.parent_agent_account.creator;
+    let agent = &mut ctx.accounts.agent_account;
+
+    require!(!agent.parent_locked, RegistryError::ParentAlreadySet);
+    require!(agent.asset != parent_asset, RegistryError::ParentSelfReference);
+    require!(
+        owner == parent_creator,
+        RegistryError::NotParentCreator
+    );
+
+    agent.parent_asset = Some(parent_asset);
+    if lock {
+        agent.parent_locked = true;
+    }
+
+    emit!(ParentAssetSet {
+        asset: agent.asset,
+        parent_asset,
+        parent_creator,
+        set_by: owner,
+    });
+
+    Ok(())
+}
+
+/// Bind a DID (did:sol or did:web) to an agent
+///
+/// Message format: "8004_DID_SET:" || asset (32 bytes) || owner (32 bytes) || deadline (8 bytes LE) || did
+/// signed by `verification_key`. Replaces any previous binding.
+pub fn set_did(
+    ctx: Context<SetDid>,
+    did: String,
+    verification_key: Pubkey,
+    deadline: i64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let asset = ctx.accounts.asset.key();
+    let owner = ctx.accounts.owner.key();
+
+    verify_core_owner(&ctx.accounts.asset, &owner)?;
+
+    require!(
+        clock.unix_timestamp <= deadline,
+        RegistryError::DeadlineExpired
+    );
+    require!(
+        deadline <= clock.unix_timestamp + MAX_DEADLINE_WINDOW,
+        RegistryError::DeadlineTooFar
+    );
+
+    validate_did(&did, &verification_key)?;
+
+    let expected_message = build_did_set_message(asset, owner, deadline, &did);
+    verify_ed25519_signature(
+        &ctx.accounts.instructions_sysvar,
+        verification_key,
+        &expected_message,
+    )?;
+
+    let binding = &mut ctx.accounts.agent_did;
+    binding.asset = asset;
+    binding.verification_key = verification_key;
+    binding.bound_by = owner;
+    binding.did = did.clone();
+    binding.bump = ctx.bumps.agent_did;
+
+    emit!(DidSet {
+        asset,
+        verification_key,
+        set_by: owner,
+        did,
+    });
+
+    Ok(())
+}
+
+/// Open an endpoint ownership challenge for a metadata entry (owner or operator)
+///
+/// The nonce mixes the entry, the claimed key and the current clock so every request
+/// yields a fresh value; re-requesting replaces a pending challenge.
+pub fn request_endpoint_challenge(
+    ctx: Context<RequestEndpointChallenge>,
+    key_hash: [u8; 16],
+    endpoint_key: Pubkey,
+) -> Result<()> {
+    verify_owner_or_operator(
+        &ctx.accounts.asset,
+        &ctx.accounts.owner.key(),
+        ctx.accounts.agent_operator.as_deref(),
+        AgentOperator::SCOPE_METADATA,
+    )?;
+
+    let clock = Clock::get()?;
+    let asset = ctx.accounts.asset.key();
+    let nonce = keccak::hashv(&[
+        asset.as_ref(),
+        key_hash.as_ref(),
+        endpoint_key.as_ref(),
+        &clock.slot.to_le_bytes(),
+        &clock.unix_timestamp.to_le_bytes(),
+        &ctx.accounts.metadata_entry.metadata_value,
+    ])
+    .0;
+    let expires_at = clock
+        .unix_timestamp
+        .checked_add(EndpointChallenge::TTL)
+        .ok_or(RegistryError::Overflow)?;
+
+    let challenge = &mut ctx.accounts.endpoint_challenge;
+    challenge.asset = asset;
+    challenge.key_hash = key_hash;
+    challenge.endpoint_key = endpoint_key;
+    challenge.nonce = nonce;
+    challenge.expires_at = expires_at;
+    challenge.requester = ctx.accounts.owner.key();
+    challenge.bump = ctx.bumps.endpoint_challenge;
+
+    emit!(EndpointChallengeRequested {
+        asset,
+        key_hash,
+        endpoint_key,
+        nonce,
+        expires_at,
+    });
+
+    Ok(())
+}
+
+/// Answer an endpoint challenge (permissionless relay of the endpoint's signature)
+///
+/// Requires an Ed25519Program instruction by endpoint_key over
+/// "8004_ENDPOINT_VERIFY:" || asset || key_hash || keccak256(value) || nonce.
+/// On success the metadata entry records endpoint_key as verified.
+pub fn verify_endpoint(ctx: Context<VerifyEndpoint>) -> Result<()> {
+    let challenge = &ctx.accounts.endpoint_challenge;
+    require!(
+        Clock::get()?.unix_timestamp <= challenge.expires_at,
+        RegistryError::EndpointChallengeExpired
+    );
+
+    let value_hash = keccak::hash(&ctx.accounts.metadata_entry.metadata_value).0;
+    let expected_message = build_endpoint_verify_message(
+        challenge.asset,
+        &challenge.key_hash,
+        &value_hash,
+        &challenge.nonce,
+    );
+    verify_ed25519_signature(
+        &ctx.accounts.instructions_sysvar,
+        challenge.endpoint_key,
+        &expected_message,
+    )?;
+
+    let endpoint_key = challenge.endpoint_key;
+    let asset = challenge.asset;
+    let key_hash = challenge.key_hash;
+    ctx.accounts.metadata_entry.verified_key = endpoint_key;
+
+    emit!(EndpointVerified {
+        asset,
+        key_hash,
+        endpoint_key,
+        value_hash,
+    });
+
+    Ok(())
+}
+
+/// Remove the DID binding and recover rent
+pub fn clear_did(ctx: Context<ClearDid>) -> Result<()> {
+    verify_core_owner(&ctx.accounts.asset, &ctx.accounts.owner.key())?;
+
+    emit!(DidCleared {
+        asset: ctx.accounts.asset.key(),
+        cleared_by: ctx.accounts.owner.key(),
+    });
+
+    Ok(())
+}
+
+/// Add a Core FreezeDelegate plugin controlled by `delegate` (e.g. a rental program)
+///
+/// FreezeDelegate is owner-managed, so the owner signs the Core CPI directly.
+pub fn add_freeze_delegate(ctx: Context<ManageAgentPlugin>, delegate: Pubkey) -> Result<()> {
+    let owner = ctx.accounts.owner.key();
+    verify_core_owner(&ctx.accounts.asset, &owner)?;
+
+    AddPluginV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+        .asset(&ctx.accounts.asset.to_account_info())
+        .collection(Some(&ctx.accounts.collection.to_account_info()))
+        .payer(&ctx.accounts.owner.to_account_info())
+        .authority(Some(&ctx.accounts.owner.to_account_info()))
+        .system_program(&ctx.accounts.system_program.to_account_info())
+        .plugin(Plugin::FreezeDelegate(FreezeDelegate { frozen: false }))
+        .init_authority(PluginAuthority::Address { address: delegate })
+        .invoke()?;
+
+    emit!(AgentPluginUpdated {
+        asset: ctx.accounts.asset.key(),
+        plugin_kind: PLUGIN_KIND_FREEZE_DELEGATE,
+        plugin_authority: delegate,
+        updated_by: owner,
+    });
+
+    Ok(())
+}
+
+/// Add or update the Core Royalties plugin on the agent asset
+///
+/// Royalties are authority-managed; the registry config PDA signs as collection
+/// update authority on behalf of the verified owner.
+pub fn set_agent_royalties(
+    ctx: Context<ManageAgentPlugin>,
+    basis_points: u16,
+    creators: Vec<RoyaltyShare>,
+) -> Result<()> {
+    let owner = ctx.accounts.owner.key();
+    verify_core_owner(&ctx.accounts.asset, &owner)?;
+    require!(
+        ctx.accounts.registry_config.royalty_bps == 0,
+        RegistryError::RoyaltiesManagedByRegistry
+    );
+
+    require!(
+        basis_points <= 10_000
+            && !creators.is_empty()
+            && creators.len() <= RoyaltyShare::MAX_CREATORS
+            && creators.iter().map(|c| c.percentage as u16).sum::<u16>() == 100,
+        RegistryError::InvalidRoyalties
+    );
+
+    let plugin = Plugin::Royalties(Royalties {
+        basis_points,
+        creators: creators
+            .iter()
+            .map(|c| Creator {
+                address: c.address,
+                percentage: c.percentage,
+            })
+            .collect(),
+        rule_set: RuleSet::None,
+    });
+
+    let collection_key = ctx.accounts.collection.key();
+    let registry_bump = ctx.accounts.registry_config.bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        SEED_REGISTRY_CONFIG,
+        collection_key.as_ref(),
+        &[registry_bump],
+    ]];
+
+    let exists = fetch_plugin::<BaseAssetV1, Royalties>(
+        &ctx.accounts.asset.to_account_info(),
+        PluginType::Royalties,
+    )
+    .is_ok();
+
+    if exists {
+        UpdatePluginV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+            .asset(&ctx.accounts.asset.to_account_info())
+            .collection(Some(&ctx.accounts.collection.to_account_info()))
+            .payer(&ctx.accounts.owner.to_account_info())
+            .authority(Some(&ctx.accounts.registry_config.to_account_info()))
+            .system_program(&ctx.accounts.system_program.to_account_info())
+            .plugin(plugin)
+            .invoke_signed(signer_seeds)?;
+    } else {
+        AddPluginV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+            .asset(&ctx.accounts.asset.to_account_info())
+            .collection(Some(&ctx.accounts.collection.to_account_info()))
+            .payer(&ctx.accounts.owner.to_account_info())
+            .authority(Some(&ctx.accounts.registry_config.to_account_info()))
+            .system_program(&ctx.accounts.system_program.to_account_info())
+            .plugin(plugin)
+            .init_authority(PluginAuthority::UpdateAuthority)
+            .invoke_signed(signer_seeds)?;
+    }
+
+    emit!(AgentPluginUpdated {
+        asset: ctx.accounts.asset.key(),
+        plugin_kind: PLUGIN_KIND_ROYALTIES,
+        plugin_authority: ctx.accounts.registry_config.key(),
+        updated_by: owner,
+    });
+
+    Ok(())
+}
+
+/// Apply the registry creator-royalty policy to an agent asset
+///
+/// Permissionless: writes an asset-level Royalties plugin paying `royalty_bps` to the
+/// agent's creator snapshot, overriding the collection-level treasury default.
+pub fn apply_creator_royalty(ctx: Context<ApplyCreatorRoyalty>) -> Result<()> {
+    let registry = &ctx.accounts.registry_config;
+    require!(
+        registry.royalty_bps > 0 && registry.royalty_route == RegistryConfig::ROYALTY_ROUTE_CREATOR,
+        RegistryError::InvalidRoyalties
+    );
+
+    let plugin = Plugin::Royalties(Royalties {
+        basis_points: registry.royalty_bps,
+        creators: vec![Creator {
+            address: ctx.accounts.agent_account.creator,
+            percentage: 100,
+        }],
+        rule_set: RuleSet::None,
+    });
+
+    let collection_key = ctx.accounts.collection.key();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        SEED_REGISTRY_CONFIG,
+        collection_key.as_ref(),
+        &[registry.bump],
+    ]];
+
+    let exists = fetch_plugin::<BaseAssetV1, Royalties>(
+        &ctx.accounts.asset.to_account_info(),
+        PluginType::Royalties,
+    )
+    .is_ok();
+
+    if exists {
+        UpdatePluginV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+            .asset(&ctx.accounts.asset.to_account_info())
+            .collection(Some(&ctx.accounts.collection.to_account_info()))
+            .payer(&ctx.accounts.payer.to_account_info())
+            .authority(Some(&registry.to_account_info()))
+            .system_program(&ctx.accounts.system_program.to_account_info())
+            .plugin(plugin)
+            .invoke_signed(signer_seeds)?;
+    } else {
+        AddPluginV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+            .asset(&ctx.accounts.asset.to_account_info())
+            .collection(Some(&ctx.accounts.collection.to_account_info()))
+            .payer(&ctx.accounts.payer.to_account_info())
+            .authority(Some(&registry.to_account_info()))
+            .system_program(&ctx.accounts.system_program.to_account_info())
+            .plugin(plugin)
+            .init_authority(PluginAuthority::UpdateAuthority)
+            .invoke_signed(signer_seeds)?;
+    }
+
+    emit!(AgentPluginUpdated {
+        asset: ctx.accounts.asset.key(),
+        plugin_kind: PLUGIN_KIND_ROYALTIES,
+        plugin_authority: registry.key(),
+        updated_by: ctx.accounts.payer.key(),
+    });
+
+    Ok(())
+}
+
+/// Add a Core Oracle plugin that can reject transfers of the agent asset
+///
+/// `oracle` is the account whose validation result gates transfers.
+pub fn add_agent_oracle(ctx: Context<ManageAgentPlugin>, oracle: Pubkey) -> Result<()> {
+    let owner = ctx.accounts.owner.key();
+    verify_core_owner(&ctx.accounts.asset, &owner)?;
+
+    let collection_key = ctx.accounts.collection.key();
+    let registry_bump = ctx.accounts.registry_config.bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        SEED_REGISTRY_CONFIG,
+        collection_key.as_ref(),
+        &[registry_bump],
+    ]];
+
+    AddExternalPluginAdapterV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+        .asset(&ctx.accounts.asset.to_account_info())
+        .collection(Some(&ctx.accounts.collection.to_account_info()))
+        .payer(&ctx.accounts.owner.to_account_info())
+        .authority(Some(&ctx.accounts.registry_config.to_account_info()))
+        .system_program(&ctx.accounts.system_program.to_account_info())
+        .init_info(ExternalPluginAdapterInitInfo::Oracle(OracleInitInfo {
+            base_address: oracle,
+            init_plugin_authority: Some(PluginAuthority::UpdateAuthority),
+            lifecycle_checks: vec![(
+                HookableLifecycleEvent::Transfer,
+                ExternalCheckResult { flags: ORACLE_CAN_REJECT },
+            )],
+            base_address_config: None,
+            results_offset: Some(ValidationResultsOffset::Anchor),
+        }))
+        .invoke_signed(signer_seeds)?;
+
+    emit!(AgentPluginUpdated {
+        asset: ctx.accounts.asset.key(),
+        plugin_kind: PLUGIN_KIND_ORACLE,
+        plugin_authority: oracle,
+        updated_by: owner,
+    });
+
+    Ok(())
+}
+
+// ============================================================================
+// Helper functions
+// ============================================================================
+
+/// Allocate the registry's next agent id, count the agent and fill its reverse index
+///
+/// Each registry owns the id range starting at `registry_id << 32`, so registrations in
+/// different collections never contend on one counter.
+fn assign_agent_id(
+    registry: &mut RegistryConfig,
+    index: &mut AgentIdIndex,
+    asset: Pubkey,
+    bump: u8,
+) -> Result<u64> {
+    let agent_id = registry.next_agent_id;
+    require!(
+        agent_id >> RegistryConfig::AGENT_ID_SEQUENCE_BITS == registry.registry_id,
+        RegistryError::Overflow
+    );
+    registry.next_agent_id = agent_id
+        .checked_add(1)
+        .ok_or(RegistryError::Overflow)?;
+    registry.total_agents = registry
+        .total_agents
+        .checked_add(1)
+        .ok_or(RegistryError::Overflow)?;
+
+    index.agent_id = agent_id;
+    index.asset = asset;
+    index.bump = bump;
+
+    Ok(agent_id)
+}
+
+/// Metadata PDA key hash: SHA256(key)[0..16]
+fn metadata_key_hash(key: &str) -> Result<[u8; 16]> {
+    use anchor_lang::solana_program::hash::hash;
+    let computed_hash = hash(key.as_bytes());
+    let key_hash: [u8; 16] = computed_hash.to_bytes()[0..16]
+        .try_into()
+        .map_err(|_| RegistryError::Overflow)?;
+    Ok(key_hash)
+}
+
+/// Validate a metadata key/value pair (reserved keys, lengths, well-known schemas)
+fn validate_metadata_entry(key: &str, value: &[u8]) -> Result<()> {
+    // Block reserved metadata key "agentWallet" - must use set_agent_wallet instruction
+    require!(key != "agentWallet", RegistryError::ReservedMetadataKey);
+
+    require!(
+        key.len() <= MetadataEntryPda::MAX_KEY_LENGTH,
+        RegistryError::KeyTooLong
+    );
+    require!(
+        value.len() <= MetadataEntryPda::MAX_VALUE_LENGTH,
+        RegistryError::ValueTooLong
+    );
+
+    // Validate value format for well-known keys (a2a.endpoint, x402.wallet, ...)
+    validate_well_known_value(key, value)
+}
+
+/// Create and populate a MetadataEntryPda passed via remaining_accounts
+///
+/// The account must be the canonical ["agent_meta", asset, key_hash] PDA.
+#[inline(never)]
+fn create_metadata_entry_account<'info>(
+    metadata_info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program_info: &AccountInfo<'info>,
+    asset: Pubkey,
+    entry: &MetadataInput,
+) -> Result<()> {
+    validate_metadata_entry(&entry.key, &entry.value)?;
+    let key_hash = metadata_key_hash(&entry.key)?;
+
+    let (expected, bump) = Pubkey::find_program_address(
+        &[SEED_AGENT_META, asset.as_ref(), key_hash.as_ref()],
+        &crate::ID,
+    );
+    require!(
+        metadata_info.key() == expected,
+        RegistryError::MetadataAccountMismatch
+    );
+
+    create_pda_account(
+        metadata_info,
+        payer,
+        system_program_info,
+        MetadataEntryPda::DISCRIMINATOR.len() + MetadataEntryPda::INIT_SPACE,
+        &[SEED_AGENT_META, asset.as_ref(), key_hash.as_ref(), &[bump]],
+    )?;
+
+    let account = MetadataEntryPda {
+        asset,
+        immutable: entry.immutable,
+        bump,
+        metadata_key: entry.key.clone(),
+        metadata_value: entry.value.clone(),
+        expires_at: 0,
+        verified_key: Pubkey::default(),
+    };
+    let mut data = metadata_info.try_borrow_mut_data()?;
+    account.try_serialize(&mut &mut data[..])?;
+
+    emit!(MetadataSet {
+        asset,
+        immutable: entry.immutable,
+        key: entry.key.clone(),
+        value: entry.value.clone(),
+        expires_at: 0,
+    });
+
+    Ok(())
+}
+
+/// Increment or decrement a TagCounter passed via remaining_accounts, creating it on
+/// first increment. The account must be the canonical ["tag_counter", tag_hash] PDA.
+#[inline(never)]
+fn adjust_tag_counter<'info>(
+    counter_info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program_info: &AccountInfo<'info>,
+    tag_hash: &[u8; 32],
+    increment: bool,
+) -> Result<()> {
+    let (expected, bump) =
+        Pubkey::find_program_address(&[SEED_TAG_COUNTER, tag_hash.as_ref()], &crate::ID);
+    require!(
+        counter_info.key() == expected,
+        RegistryError::TagCounterMismatch
+    );
+
+    let mut counter = if counter_info.owner == &crate::ID {
+        TagCounter::try_deserialize(&mut &counter_info.try_borrow_data()?[..])?
+    } else {
+        create_pda_account(
+            counter_info,
+            payer,
+            system_program_info,
+            TagCounter::DISCRIMINATOR.len() + TagCounter::INIT_SPACE,
+            &[SEED_TAG_COUNTER, tag_hash.as_ref(), &[bump]],
+        )?;
+        TagCounter {
+            tag_hash: *tag_hash,
+            agent_count: 0,
+            bump,
+        }
+    };
+
+    counter.agent_count = if increment {
+        counter.agent_count.checked_add(1).ok_or(RegistryError::Overflow)?
+    } else {
+        counter.agent_count.saturating_sub(1)
+    };
+
+    let mut data = counter_info.try_borrow_mut_data()?;
+    counter.try_serialize(&mut &mut data[..])?;
+    Ok(())
+}
+
+/// Build the message that wallet owner must sign for set_agent_wallet
+fn build_wallet_set_message(
+    asset: Pubkey,
+    new_wallet: Pubkey,
+    owner: Pubkey,
+    deadline: i64,
+) -> Vec<u8> {
+    let mut message = Vec::with_capacity(WALLET_SET_MESSAGE_PREFIX.len() + 32 + 32 + 32 + 8);
+    message.extend_from_slice(WALLET_SET_MESSAGE_PREFIX);
+    message.extend_from_slice(asset.as_ref());
+    message.extend_from_slice(new_wallet.as_ref());
+    message.extend_from_slice(owner.as_ref());
+    message.extend_from_slice(&deadline.to_le_bytes());
+    message
+}
+
+/// Build the endpoint challenge message the endpoint key must sign for verify_endpoint
+fn build_endpoint_verify_message(
+    asset: Pubkey,
+    key_hash: &[u8; 16],
+    value_hash: &[u8; 32],
+    nonce: &[u8; 32],
+) -> Vec<u8> {
+    let mut message = Vec::with_capacity(ENDPOINT_VERIFY_MESSAGE_PREFIX.len() + 32 + 16 + 64);
+    message.extend_from_slice(ENDPOINT_VERIFY_MESSAGE_PREFIX);
+    message.extend_from_slice(asset.as_ref());
+    message.extend_from_slice(key_hash);
+    message.extend_from_slice(value_hash);
+    message.extend_from_slice(nonce);
+    message
+}
+
+/// Build the message an EVM owner signs to authorize a Solana delegate for an asset
+fn build_evm_register_message(asset: Pubkey, delegate: Pubkey, deadline: i64) -> Vec<u8> {
+    let mut message = Vec::with_capacity(EVM_REGISTER_MESSAGE_PREFIX.len() + 32 + 32 + 8);
+    message.extend_from_slice(EVM_REGISTER_MESSAGE_PREFIX);
+    message.extend_from_slice(asset.as_ref());
+    message.extend_from_slice(delegate.as_ref());
+    message.extend_from_slice(&deadline.to_le_bytes());
+    message
+}
+
+/// Recover the signer of an eth_sign (personal_sign) message and compare it to `evm_address`
+fn verify_evm_signature(
+    evm_address: &[u8; 20],
+    message: &[u8],
+    signature: &[u8; 64],
+    recovery_id: u8,
+) -> Result<()> {
+    let length = message.len().to_string();
+    let digest = keccak::hashv(&[ETH_SIGNED_MESSAGE_PREFIX, length.as_bytes(), message]);
+    verify_evm_digest(evm_address, &digest.0, signature, recovery_id)
+}
+
+/// Recover the signer of a 32-byte digest (e.g. EIP-712) and compare it to `evm_address`
+///
+/// Accepts recovery ids in both raw (0/1) and Ethereum (27/28) form. High-s signatures are
+/// rejected (EIP-2) so each signature has a single valid encoding.
+pub fn verify_evm_digest(
+    evm_address: &[u8; 20],
+    digest: &[u8; 32],
+    signature: &[u8; 64],
+    recovery_id: u8,
+) -> Result<()> {
+    require!(
+        signature[32..] <= SECP256K1_HALF_ORDER[..],
+        RegistryError::InvalidEvmSignature
+    );
+    let recovery_id = if recovery_id >= 27 {
+        recovery_id - 27
+    } else {
+        recovery_id
+    };
+
+    let pubkey = secp256k1_recover(digest, recovery_id, signature)
+        .map_err(|_| RegistryError::InvalidEvmSignature)?;
+    let recovered = keccak::hash(&pubkey.to_bytes()).0;
+    require!(
+        recovered[12..] == evm_address[..],
+        RegistryError::InvalidEvmSignature
+    );
+    Ok(())
+}
+
+fn build_did_set_message(asset: Pubkey, owner: Pubkey, deadline: i64, did: &str) -> Vec<u8> {
+    let mut message = Vec::with_capacity(DID_SET_MESSAGE_PREFIX.len() + 32 + 32 + 8 + did.len());
+    message.extend_from_slice(DID_SET_MESSAGE_PREFIX);
+    message.extend_from_slice(asset.as_ref());
+    message.extend_from_slice(owner.as_ref());
+    message.extend_from_slice(&deadline.to_le_bytes());
+    message.extend_from_slice(did.as_bytes());
+    message
+}
+
+/// Validate DID syntax; for did:sol the identifier must be the verification key
+/// Accepted: did:sol:<base58> | did:sol:<network>:<base58> | did:web:<domain>[:path]
+fn validate_did(did: &str, verification_key: &Pubkey) -> Result<()> {
+    require!(
+        did.len() <= AgentDid::MAX_DID_LENGTH && did.is_ascii(),
+        RegistryError::InvalidDid
+    );
+
+    if let Some(id) = did.strip_prefix("did:sol:") {
+        let key = id.rsplit(':').next().unwrap_or_default();
+        require!(
+            key == verification_key.to_string(),
+            RegistryError::InvalidDid
+        );
+    } else if let Some(domain) = did.strip_prefix("did:web:") {
+        require!(
+            !domain.is_empty() && !domain.contains(char::is_whitespace),
+            RegistryError::InvalidDid
+        );
+    } else {
+        return Err(RegistryError::InvalidDid.into());
+    }
+
+    Ok(())
+}
+
+/// Compute the next expiry: one period after max(now, current_valid_until).
+/// Returns 0 (never expires) when the registry has expiry disabled.
+fn registration_expiry(period: i64, current_valid_until: i64) -> Result<i64> {
+    if period == 0 {
+        return Ok(0);
+    }
+    let now = Clock::get()?.unix_timestamp;
+    let valid_until = now
+        .max(current_valid_until)
+        .checked_add(period)
+        .ok_or(RegistryError::Overflow)?;
+    Ok(valid_until)
+}
+
+/// Transfer the registry's registration fee from `payer` into the treasury.
+/// No-op when the fee is 0; otherwise the treasury for this registry must be provided.
+/// Returns the share paid to `referrer` (registry.referral_share_bps of the fee).
+fn collect_registration_fee<'info>(
+    registry: &RegistryConfig,
+    treasury: Option<&mut Account<'info, Treasury>>,
+    payer: &AccountInfo<'info>,
+    system_program_info: &AccountInfo<'info>,
+    asset: Pubkey,
+    referrer: Option<&AccountInfo<'info>>,
+) -> Result<u64> {
+    let fee = registry.registration_fee;
+    if fee == 0 {
+        return Ok(0);
+    }
+
+    let treasury = treasury.ok_or(RegistryError::InvalidTreasury)?;
+    require!(
+        treasury.collection == registry.collection,
+        RegistryError::InvalidTreasury
+    );
+
+    let share = match referrer {
+        Some(referrer) => {
+            let share = (fee as u128 * registry.referral_share_bps as u128 / 10_000) as u64;
+            if share > 0 {
+                system_program::transfer(
+                    CpiContext::new(
+                        system_program_info.clone(),
+                        system_program::Transfer {
+                            from: payer.clone(),
+                            to: referrer.clone(),
+                        },
+                    ),
+                    share,
+                )?;
+            }
+            share
+        }
+        None => 0,
+    };
+    let amount = fee - share;
+
+    system_program::transfer(
+        CpiContext::new(
+            system_program_info.clone(),
+            system_program::Transfer {
+                from: payer.clone(),
+                to: treasury.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    treasury.total_collected = treasury
+        .total_collected
+        .checked_add(amount)
+        .ok_or(RegistryError::Overflow)?;
+
+    emit!(RegistrationFeeCollected {
+        asset,
+        payer: *payer.key,
+        amount,
+    });
+
+    Ok(share)
+}
+
+/// Create the ["referral", asset] PDA attributing a registration to `referrer`
+fn record_referral<'info>(
+    referral_info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program_info: &AccountInfo<'info>,
+    asset: Pubkey,
+    collection: Pubkey,
+    referrer: Pubkey,
+    fee_share: u64,
+) -> Result<()> {
+    let (expected, bump) =
+        Pubkey::find_program_address(&[SEED_REFERRAL, asset.as_ref()], &crate::ID);
+    require!(referral_info.key() == expected, RegistryError::InvalidReferral);
+
+    create_pda_account(
+        referral_info,
+        payer,
+        system_program_info,
+        Referral::DISCRIMINATOR.len() + Referral::INIT_SPACE,
+        &[SEED_REFERRAL, asset.as_ref(), &[bump]],
+    )?;
+
+    let account = Referral {
+        asset,
+        referrer,
+        collection,
+        fee_share,
+        created_at: Clock::get()?.unix_timestamp,
+        bump,
+    };
+    let mut data = referral_info.try_borrow_mut_data()?;
+    account.try_serialize(&mut &mut data[..])?;
+
+    emit!(AgentReferred {
+        asset,
+        collection,
+        referrer,
+        fee_share,
+    });
+
+    Ok(())
+}
+
+/// Shrink a freshly initialized AgentAccount to COMPACT_SPACE and refund the rent
+/// difference to the payer.
+fn shrink_to_compact<'info>(
+    agent_info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+) -> Result<()> {
+    let rent = Rent::get()?;
+    let full_space = AgentAccount::DISCRIMINATOR.len() + AgentAccount::INIT_SPACE;
+    let refund = rent
+        .minimum_balance(full_space)
+        .saturating_sub(rent.minimum_balance(AgentAccount::COMPACT_SPACE));
+
+    agent_info.realloc(AgentAccount::COMPACT_SPACE, false)?;
+
+    let agent_balance = agent_info
+        .lamports()
+        .checked_sub(refund)
+        .ok_or(RegistryError::Overflow)?;
+    let payer_balance = payer.lamports().checked_add(refund).ok_or(RegistryError::Overflow)?;
+    **agent_info.try_borrow_mut_lamports()? = agent_balance;
+    **payer.try_borrow_mut_lamports()? = payer_balance;
+
+    Ok(())
+}
+
+/// Enforce the registry allowlist when one is set (zero root = open registration).
+fn check_allowlist(registry: &RegistryConfig, owner: &Pubkey, proof: &[[u8; 32]]) -> Result<()> {
+    if registry.allowlist_root == [0u8; 32] {
+        return Ok(());
+    }
+    require!(
+        verify_allowlist_proof(&registry.allowlist_root, owner, proof),
+        RegistryError::NotAllowlisted
+    );
+    Ok(())
+}
+
+/// Lock the registry's registration bond into the new AgentAccount.
+/// Returns (bond_amount, bond_unlocks_at); (0, 0) when the registry has no bond.
+fn lock_registration_bond<'info>(
+    registry: &RegistryConfig,
+    agent_info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program_info: &AccountInfo<'info>,
+    asset: Pubkey,
+) -> Result<(u64, i64)> {
+    let bond = registry.registration_bond;
+    if bond == 0 {
+        return Ok((0, 0));
+    }
+
+    system_program::transfer(
+        CpiContext::new(
+            system_program_info.clone(),
+            system_program::Transfer {
+                from: payer.clone(),
+                to: agent_info.clone(),
+            },
+        ),
+        bond,
+    )?;
+
+    let unlocks_at = Clock::get()?
+        .unix_timestamp
+        .checked_add(registry.bond_cooldown)
+        .ok_or(RegistryError::Overflow)?;
+
+    emit!(RegistrationBondLocked {
+        asset,
+        payer: *payer.key,
+        amount: bond,
+        unlocks_at,
+    });
+
+    Ok((bond, unlocks_at))
+}
+
+/// Let Core approve the next transfer by writing Pass into the gate's oracle result.
+/// Returns the previous result so close_transfer_gate can restore it.
+fn open_transfer_gate(gate_info: Option<&AccountInfo>) -> Result<Option<u8>> {
+    let Some(gate_info) = gate_info else {
+        return Ok(None);
+    };
+    let mut data = gate_info.try_borrow_mut_data()?;
+    let previous = data[TransferGate::TRANSFER_RESULT_OFFSET];
+    data[TransferGate::TRANSFER_RESULT_OFFSET] = TransferGate::RESULT_PASS;
+    Ok(Some(previous))
+}
+
+/// Oracle account Core reads on every transfer once the collection has a gate installed
+fn transfer_gate_accounts<'a, 'info>(
+    gate_info: Option<&'a AccountInfo<'info>>,
+) -> Vec<(&'a AccountInfo<'info>, bool, bool)> {
+    gate_info.map(|gate| (gate, false, false)).into_iter().collect()
+}
+
+/// Restore the gate's transfer result after the registry's transfer CPI
+fn close_transfer_gate(gate_info: Option<&AccountInfo>, previous: Option<u8>) -> Result<()> {
+    if let (Some(gate_info), Some(previous)) = (gate_info, previous) {
+        gate_info.try_borrow_mut_data()?[TransferGate::TRANSFER_RESULT_OFFSET] = previous;
+    }
+    Ok(())
+}
+
+/// Emit StaleOwnerDetected when the cached owner differs from the authoritative one.
+/// Returns whether drift was found.
+fn detect_stale_owner(agent: &AgentAccount, live_owner: Pubkey) -> Result<bool> {
+    if agent.owner == live_owner {
+        return Ok(false);
+    }
+
+    emit!(StaleOwnerDetected {
+        asset: agent.asset,
+        cached_owner: agent.owner,
+        live_owner,
+        last_synced_slot: agent.owner_synced_slot,
+        detected_slot: Clock::get()?.slot,
+    });
+
+    Ok(true)
+}
+
+/// Pay the registry's sync bounty from its treasury (skipped when unfunded)
+fn pay_sync_bounty(
+    registry: &RegistryConfig,
+    treasury: &mut Account<Treasury>,
+    cranker: &AccountInfo,
+    asset: Pubkey,
+) -> Result<()> {
+    let amount = registry.sync_bounty;
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let treasury_info = treasury.to_account_info();
+    let min_balance = Rent::get()?.minimum_balance(treasury_info.data_len());
+    if treasury_info.lamports() < min_balance.saturating_add(amount) {
+        msg!("Treasury cannot cover sync bounty, skipping");
+        return Ok(());
+    }
+
+    move_treasury_lamports(&treasury_info, cranker, amount)?;
+    treasury.total_withdrawn = treasury
+        .total_withdrawn
+        .checked_add(amount)
+        .ok_or(RegistryError::Overflow)?;
+
+    emit!(SyncBountyPaid {
+        asset,
+        cranker: *cranker.key,
+        amount,
+    });
+
+    Ok(())
+}
+
+/// Move lamports out of the program-owned treasury (caller checks rent exemption)
+fn move_treasury_lamports(
+    treasury_info: &AccountInfo,
+    recipient_info: &AccountInfo,
+    amount: u64,
+) -> Result<()> {
+    let treasury_balance = treasury_info
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(RegistryError::InsufficientTreasuryBalance)?;
+    let recipient_balance = recipient_info
+        .lamports()
+        .checked_add(amount)
+        .ok_or(RegistryError::Overflow)?;
+    **treasury_info.try_borrow_mut_lamports()? = treasury_balance;
+    **recipient_info.try_borrow_mut_lamports()? = recipient_balance;
+    Ok(())
+}
+
+/// Move an asset between OwnerIndex pages after an owner change (no-op when unchanged).
+/// Either page may be omitted; reindex_owner repairs the omitted side afterwards.
+fn move_owner_index(
+    from: Option<&mut OwnerIndex>,
+    to: Option<&mut OwnerIndex>,
+    asset: Pubkey,
+    old_owner: Pubkey,
+    new_owner: Pubkey,
+) -> Result<()> {
+    if old_owner == new_owner {
+        return Ok(());
+    }
+    if let Some(index) = from {
+        remove_owner_index_entry(index, old_owner, asset)?;
+    }
+    if let Some(index) = to {
+        add_owner_index_entry(index, new_owner, asset)?;
+    }
+    Ok(())
+}
+
+fn add_owner_index_entry(index: &mut OwnerIndex, owner: Pubkey, asset: Pubkey) -> Result<()> {
+    require!(index.owner == owner, RegistryError::OwnerIndexMismatch);
+    if index.assets.contains(&asset) {
+        return Ok(());
+    }
+    require!(!index.is_full(), RegistryError::OwnerIndexFull);
+    index.assets.push(asset);
+    Ok(())
+}
+
+fn remove_owner_index_entry(index: &mut OwnerIndex, owner: Pubkey, asset: Pubkey) -> Result<()> {
+    require!(index.owner == owner, RegistryError::OwnerIndexMismatch);
+    let position = index
+        .assets
+        .iter()
+        .position(|a| *a == asset)
+        .ok_or(RegistryError::OwnerIndexMismatch)?;
+    index.assets.swap_remove(position);
+    Ok(())
+}
+
+/// Update cached owner from an authoritative source, resetting the wallet on change.
+fn apply_owner_sync(agent: &mut AgentAccount, new_owner: Pubkey) -> Result<()> {
+    let old_owner = agent.owner;
+    let asset = agent.asset;
+    agent.owner_synced_slot = Clock::get()?.slot;
+
+    // Only update if owner changed
+    if old_owner != new_owner {
+        agent.owner = new_owner;
+
+        // Reset wallet on ownership change (security: prevents old owner's wallet from being used)
+        let old_wallet = agent.agent_wallet;
+        if old_wallet.is_some() {
+            agent.agent_wallet = None;
+            emit!(WalletResetOnOwnerSync {
+                asset,
+                old_wallet,
+                new_wallet: Pubkey::default(),
+                owner_after_sync: new_owner,
+            });
+        }
+
+        emit!(AgentOwnerSynced {
+            asset,
+            old_owner,
+            new_owner,
+        });
+
+        msg!("Agent owner synced for asset {}: {} -> {} (wallet reset)", asset, old_owner, new_owner);
+    } else {
+        msg!("Agent owner unchanged for asset {}", asset);
+    }
+
+    Ok(())
+}
+
+/// Update cached owner after a registry-driven transfer and reset the wallet.
+fn apply_transfer(
+    agent: &mut AgentAccount,
+    old_owner: Pubkey,
+    new_owner: Pubkey,
+    updated_by: Pubkey,
+) -> Result<()> {
+    let asset = agent.asset;
+    let old_wallet = agent.agent_wallet;
+    agent.owner = new_owner;
+    agent.owner_synced_slot = Clock::get()?.slot;
+    agent.agent_wallet = None; // Security: reset wallet on transfer
+
+    // Emit wallet reset event if there was a wallet
+    if old_wallet.is_some() {
+        emit!(WalletUpdated {
+            asset,
+            old_wallet,
+            new_wallet: Pubkey::default(),
+            updated_by,
+        });
+        msg!("Agent wallet reset on transfer");
+    }
+
+    emit!(AgentOwnerSynced {
+        asset,
+        old_owner,
+        new_owner,
+    });
+
+    Ok(())
+}
+
+/// Consume a council proposal authorizing `action_hash` on behalf of the Core owner.
+/// The council is only honored while its configuring owner still holds the asset.
+fn authorize_council_action(
+    asset_info: &AccountInfo,
+    signer: &Pubkey,
+    council: Option<&OwnerCouncil>,
+    proposal: Option<&mut CouncilProposal>,
+    action_hash: &[u8; 32],
+) -> Result<()> {
+    let council = council.ok_or(RegistryError::Unauthorized)?;
+    let proposal = proposal.ok_or(RegistryError::Unauthorized)?;
+
+    require!(
+        proposal.asset == council.asset && council.owner == get_core_owner(asset_info)?,
+        RegistryError::CouncilMismatch
+    );
+    require!(
+        council.member_index(signer).is_some(),
+        RegistryError::NotCouncilMember
+    );
+    require!(
+        !proposal.executed && proposal.epoch == council.epoch,
+        RegistryError::CouncilProposalStale
+    );
+    require!(
+        proposal.action_hash == *action_hash,
+        RegistryError::CouncilActionMismatch
+    );
+    require!(
+        council.threshold > 0 && approval_count(proposal.approvals) >= council.threshold,
+        RegistryError::CouncilThresholdNotMet
+    );
+
+    proposal.executed = true;
+
+    emit!(CouncilActionExecuted {
+        asset: proposal.asset,
+        proposal_id: proposal.proposal_id,
+        action: proposal.action,
+        executed_by: *signer,
+    });
+
+    Ok(())
+}
+
+/// Store a verified agent wallet and sync the cached owner.
+/// Keeping owner/wallet updates in one instruction avoids stale cached-owner state.
+fn store_agent_wallet(
+    agent: &mut AgentAccount,
+    new_wallet: Pubkey,
+    new_owner: Pubkey,
+) -> Result<()> {
+    let asset = agent.asset;
+    let old_wallet = agent.agent_wallet;
+    let old_owner = agent.owner;
+
+    agent.agent_wallet = Some(new_wallet);
+    agent.owner = new_owner; // Implicit sync
+    agent.owner_synced_slot = Clock::get()?.slot;
+
+    emit!(WalletUpdated {
+        asset,
+        old_wallet,
+        new_wallet,
+        updated_by: new_owner,
+    });
+
+    if old_owner != new_owner {
+        emit!(AgentOwnerSynced {
+            asset,
+            old_owner,
+            new_owner,
+        });
+    }
+
+    Ok(())
+}
+
+fn validate_collection_pointer(col: &str) -> Result<()> {
+    require!(
+        col.len() <= AgentAccount::MAX_COL_LENGTH,
+        RegistryError::InvalidCollectionPointer
+    );
+    require!(
+        col.starts_with(COLLECTION_POINTER_PREFIX),
+        RegistryError::InvalidCollectionPointer
+    );
+
+    let cid = &col[COLLECTION_POINTER_PREFIX.len()..];
+    require!(!cid.is_empty(), RegistryError::InvalidCollectionPointer);
+    require!(
+        cid.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit()),
+        RegistryError::InvalidCollectionPointer
+    );
+
+    Ok(())
+}
+
+/// Verify Ed25519 signature via sysvar introspection
+/// SECURITY: Ed25519 instruction MUST be immediately before this instruction (current_index - 1)
+pub(crate) fn verify_ed25519_signature(
+    instructions_sysvar: &AccountInfo,
+    expected_signer: Pubkey,
+    expected_message: &[u8],
+) -> Result<()> {
+    let current_idx = load_current_index_checked(instructions_sysvar)
+        .map_err(|_| RegistryError::MissingSignatureVerification)?;
+
+    // SECURITY FIX: Ed25519 instruction MUST be at index (current_index - 1)
+    // This prevents signature reuse attacks where an attacker places a valid
+    // signature earlier in the transaction for a different purpose
+    require!(current_idx >= 1, RegistryError::MissingSignatureVerification);
+    let ed25519_idx = (current_idx - 1) as usize;
+
+    let ix = load_instruction_at_checked(ed25519_idx, instructions_sysvar)
+        .map_err(|_| RegistryError::MissingSignatureVerification)?;
+
+    // Must be Ed25519 program
+    require!(
+        ix.program_id == ed25519_program::ID,
+        RegistryError::MissingSignatureVerification
+    );
+
+    // Validate instruction data length (header is 16 bytes minimum)
+    require!(ix.data.len() >= 16, RegistryError::InvalidSignature);
+
+    let num_signatures = ix.data[0];
+    require!(num_signatures == 1, RegistryError::InvalidSignature);
+
+    // SECURITY FIX: Verify all instruction indices are u16::MAX (0xFFFF)
+    // This ensures signature, pubkey, and message are INLINE in this instruction,
+    // not referenced from another instruction in the transaction.
+    // Without this check, an attacker could craft an Ed25519 instruction that
+    // references data from a different instruction, bypassing our validation.
+    let sig_instruction_index = u16::from_le_bytes([ix.data[4], ix.data[5]]);
+    let pubkey_instruction_index = u16::from_le_bytes([ix.data[8], ix.data[9]]);
+    let msg_instruction_index = u16::from_le_bytes([ix.data[14], ix.data[15]]);
+
+    require!(
+        sig_instruction_index == u16::MAX
+            && pubkey_instruction_index == u16::MAX
+            && msg_instruction_index == u16::MAX,
+        RegistryError::InvalidSignature
+    );
+
+    let signature_offset = u16::from_le_bytes([ix.data[2], ix.data[3]]) as usize;
+    let pubkey_offset = u16::from_le_bytes([ix.data[6], ix.data[7]]) as usize;
+    let message_offset = u16::from_le_bytes([ix.data[10], ix.data[11]]) as usize;
+    let message_size = u16::from_le_bytes([ix.data[12], ix.data[13]]) as usize;
+
+    // Validate bounds
+    require!(
+        pubkey_offset + 32 <= ix.data.len()
+            && message_offset + message_size <= ix.data.len()
+            && signature_offset + 64 <= ix.data.len(),
+        RegistryError::InvalidSignature
+    );
+
+    // Verify pubkey matches expected signer
+    let pubkey_bytes: [u8; 32] = ix.data[pubkey_offset..pubkey_offset + 32]
+        .try_into()
+        .map_err(|_| RegistryError::InvalidSignature)?;
+    let signer = Pubkey::from(pubkey_bytes);
+    require!(signer == expected_signer, RegistryError::InvalidSignature);
+
+    // Verify message matches expected message
+    let message = &ix.data[message_offset..message_offset + message_size];
+    require!(message == expected_message, RegistryError::InvalidSignature);
+
+    Ok(())
+}
+
+/// Create Core asset via CPI
+#[inline(never)]
+fn create_core_asset_cpi<'info>(
+    mpl_core_program: &AccountInfo<'info>,
+    asset: &AccountInfo<'info>,
+    collection: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    owner: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    name: String,
+    uri: String,
+    soulbound: bool,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let mut builder = CreateV2CpiBuilder::new(mpl_core_program);
+    builder
+        .asset(asset)
+        .collection(Some(collection))
+        .payer(payer)
+        .owner(Some(owner))
+        .authority(Some(authority))
+        .system_program(system_program)
+        .name(name)
+        .uri(uri);
+    // Permanent plugins can only be added at creation; the registry (update authority)
+    // is the only party able to lift the freeze, which it does solely on deregister
+    if soulbound {
+        builder.plugins(vec![PluginAuthorityPair {
+            plugin: Plugin::PermanentFreezeDelegate(PermanentFreezeDelegate { frozen: true }),
+            authority: Some(PluginAuthority::UpdateAuthority),
+        }]);
+    }
+    builder.invoke_signed(signer_seeds)?;
+    Ok(())
+}
+
+/// Initialize ATOM stats via CPI (owner signs as payer, asset must already exist)
+#[inline(never)]
+fn initialize_atom_stats_cpi<'info>(
+    atom_engine_program: &AccountInfo<'info>,
+    owner: &AccountInfo<'info>,
+    asset: &AccountInfo<'info>,
+    collection: &AccountInfo<'info>,
+    atom_config: &AccountInfo<'info>,
+    atom_stats: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    require!(
+        atom_engine_program.key() == atom_engine::ID,
+        RegistryError::InvalidProgram
+    );
+
+    // SECURITY: Validate that atom_stats is the correct PDA for this asset
+    let (expected_atom_stats, _bump) = Pubkey::find_program_address(
+        &[b"atom_stats", asset.key().as_ref()],
+        &atom_engine::ID,
+    );
+    require!(
+        atom_stats.key() == expected_atom_stats,
+        RegistryError::InvalidAtomStatsAccount
+    );
+
+    let cpi_accounts = atom_engine::cpi::accounts::InitializeStats {
+        owner: owner.clone(),
+        asset: asset.clone(),
+        collection: collection.clone(),
+        config: atom_config.clone(),
+        stats: atom_stats.clone(),
+        system_program: system_program.clone(),
+    };
+    atom_engine::cpi::initialize_stats(CpiContext::new(
+        atom_engine_program.clone(),
+        cpi_accounts,
+    ))?;
+    Ok(())
+}
+
+/// Update Core asset URI via CPI
+#[inline(never)]
+fn update_core_asset_uri_cpi<'info>(
+    mpl_core_program: &AccountInfo<'info>,
+    asset: &AccountInfo<'info>,
+    collection: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    new_uri: String,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    UpdateV1CpiBuilder::new(mpl_core_program)
+        .asset(asset)
+        .collection(Some(collection))
+        .payer(payer)
+        .authority(Some(authority))
+        .system_program(system_program)
+        .new_uri(new_uri)
+        .invoke_signed(signer_seeds)?;
+    Ok(())
+}
+
+// ============================================================================
+// Single Collection Instructions
+// ============================================================================
+
+/// Initialize the registry with root config and base collection
+pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
+    let root = &mut ctx.accounts.root_config;
+    let registry = &mut ctx.accounts.registry_config;
+    let collection_key = ctx.accounts.collection.key();
+
+    // Initialize root config
+    root.base_collection = collection_key;
+    root.authority = ctx.accounts.authority.key();
+    root.bump = ctx.bumps.root_config;
+    root.next_registry_id = 0;
+
+    // Initialize registry config
+    init_registry_config(
+        registry,
+        root,
+        collection_key,
+        ctx.accounts.authority.key(),
+        ctx.bumps.registry_config,
+        RegistryConfig::REGISTRY_TYPE_BASE,
+    )?;
+
+    // Create Metaplex Core Collection
+    create_registry_collection_cpi(
+        &ctx.accounts.mpl_core_program.to_account_info(),
+        &ctx.accounts.collection.to_account_info(),
+        &ctx.accounts.authority.to_account_info(),
+        &registry.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        "8004 Agent Registry".to_string(),
+        String::new(),
+        ctx.bumps.registry_config,
+    )?;
+
+    emit!(RegistryInitialized {
+        collection: collection_key,
+        authority: ctx.accounts.authority.key(),
+    });
+
+    msg!("Registry initialized with collection: {}", collection_key);
+
+    Ok(())
+}
+
+/// Create an additional protocol-operated base registry with its own Core collection
+pub fn create_base_registry(
+    ctx: Context<CreateBaseRegistry>,
+    name: String,
+    uri: String,
+) -> Result<()> {
+    validate_registry_params(&name, &uri)?;
+
+    let registry = &mut ctx.accounts.registry_config;
+    let collection_key = ctx.accounts.collection.key();
+    let authority = ctx.accounts.authority.key();
+
+    init_registry_config(
+        registry,
+        &mut ctx.accounts.root_config,
+        collection_key,
+        authority,
+        ctx.bumps.registry_config,
+        RegistryConfig::REGISTRY_TYPE_BASE,
+    )?;
+
+    create_registry_collection_cpi(
+        &ctx.accounts.mpl_core_program.to_account_info(),
+        &ctx.accounts.collection.to_account_info(),
+        &ctx.accounts.authority.to_account_info(),
+        &registry.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        name,
+        uri,
+        ctx.bumps.registry_config,
+    )?;
+
+    emit!(BaseRegistryCreated {
+        registry: registry.key(),
+        collection: collection_key,
+        created_by: authority,
+    });
+
+    msg!("Base registry created with collection: {}", collection_key);
+
+    Ok(())
+}
+
+/// Create an organization-operated user registry with its own Core collection
+///
+/// The creator becomes the registry authority (fees, expiry, freeze) for agents
+/// registered into this collection, isolated from the base registry.
+pub fn create_user_registry(
+    ctx: Context<CreateUserRegistry>,
+    name: String,
+    uri: String,
+) -> Result<()> {
+    validate_registry_params(&name, &uri)?;
+
+    let registry = &mut ctx.accounts.registry_config;
+    let collection_key = ctx.accounts.collection.key();
+    let owner = ctx.accounts.owner.key();
+
+    init_registry_config(
+        registry,
+        &mut ctx.accounts.root_config,
+        collection_key,
+        owner,
+        ctx.bumps.registry_config,
+        RegistryConfig::REGISTRY_TYPE_USER,
+    )?;
+
+    create_registry_collection_cpi(
+        &ctx.accounts.mpl_core_program.to_account_info(),
+        &ctx.accounts.collection.to_account_info(),
+        &ctx.accounts.owner.to_account_info(),
+        &registry.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        name.clone(),
+        uri,
+        ctx.bumps.registry_config,
+    )?;
+
+    emit!(UserRegistryCreated {
+        registry: registry.key(),
+        collection: collection_key,
+        owner,
+        name,
+    });
+
+    msg!("User registry created with collection: {}", collection_key);
+
+    Ok(())
+}
+
+/// Register agents into a project's own Core collection
+///
+/// The current update authority hands the collection's update authority to the
+/// registry config PDA and becomes the registry authority, exactly like a registry
+/// created with create_user_registry.
+pub fn approve_collection(ctx: Context<ApproveCollection>) -> Result<()> {
+    let collection_info = ctx.accounts.collection.to_account_info();
+    require!(
+        *collection_info.owner == mpl_core::ID,
+        RegistryError::InvalidCollection
+    );
+    let name = {
+        let data = collection_info.try_borrow_data()?;
+        let collection = BaseCollectionV1::from_bytes(&data)
+            .map_err(|_| RegistryError::InvalidCollection)?;
+        require!(
+            collection.update_authority == ctx.accounts.collection_authority.key(),
+            RegistryError::Unauthorized
+        );
+        collection.name
+    };
+
+    let collection_key = ctx.accounts.collection.key();
+    let authority = ctx.accounts.collection_authority.key();
+
+    UpdateCollectionV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+        .collection(&collection_info)
+        .payer(&ctx.accounts.collection_authority.to_account_info())
+        .authority(Some(&ctx.accounts.collection_authority.to_account_info()))
+        .new_update_authority(Some(&ctx.accounts.registry_config.to_account_info()))
+        .system_program(&ctx.accounts.system_program.to_account_info())
+        .invoke()?;
+
+    let registry = &mut ctx.accounts.registry_config;
+    init_registry_config(
+        registry,
+        &mut ctx.accounts.root_config,
+        collection_key,
+        authority,
+        ctx.bumps.registry_config,
+        RegistryConfig::REGISTRY_TYPE_USER,
+    )?;
+
+    emit!(UserRegistryCreated {
+        registry: registry.key(),
+        collection: collection_key,
+        owner: authority,
+        name,
+    });
+
+    msg!("Existing collection {} approved as user registry", collection_key);
+
+    Ok(())
+}
+
+/// Publish the deployment feature flags (RegistryFeatures::FEATURE_* bits)
+pub fn set_registry_features(
+    ctx: Context<SetRegistryFeatures>,
+    version: u16,
+    features: u64,
+) -> Result<()> {
+    let flags = &mut ctx.accounts.registry_features;
+    flags.version = version;
+    flags.features = features;
+    flags.updated_at = Clock::get()?.unix_timestamp;
+    flags.bump = ctx.bumps.registry_features;
+
+    emit!(RegistryFeaturesUpdated {
+        version,
+        features,
+        updated_by: ctx.accounts.authority.key(),
+    });
+
+    Ok(())
+}
+
+/// Fill a new registry config and allocate its registry id (and agent id range)
+fn init_registry_config(
+    registry: &mut RegistryConfig,
+    root: &mut RootConfig,
+    collection: Pubkey,
+    authority: Pubkey,
+    bump: u8,
+    registry_type: u8,
+) -> Result<()> {
+    let registry_id = root.next_registry_id;
+    require!(
+        registry_id < 1 << (64 - RegistryConfig::AGENT_ID_SEQUENCE_BITS),
+        RegistryError::Overflow
+    );
+    root.next_registry_id = registry_id + 1;
+
+    registry.collection = collection;
+    registry.authority = authority;
+    registry.bump = bump;
+    registry.registration_period = 0;
+    registry.token2022_enabled = false;
+    registry.registration_fee = 0;
+    registry.registry_type = registry_type;
+    registry.max_metadata_chunks = RegistryConfig::DEFAULT_MAX_METADATA_CHUNKS;
+    registry.sync_bounty = 0;
+    registry.allowed_uri_schemes = URI_SCHEMES_ALL;
+    registry.pending_authority = None;
+    registry.registration_bond = 0;
+    registry.bond_cooldown = 0;
+    registry.allowlist_root = [0u8; 32];
+    registry.referral_share_bps = 0;
+    registry.royalty_bps = 0;
+    registry.royalty_route = RegistryConfig::ROYALTY_ROUTE_TREASURY;
+    registry.dispute_window = 0;
+    registry.feedback_index_mode = RegistryConfig::FEEDBACK_INDEX_GLOBAL;
+    registry.feedback_payment_mint = Pubkey::default();
+    registry.min_feedback_payment = 0;
+    registry.allow_self_feedback = false;
+    registry.feedback_cooldown_slots = 0;
+    registry.feedback_bond = 0;
+    registry.feedback_bond_window = 0;
+    registry.strict_feedback_tags = false;
+    registry.feedback_reveal_delay = 0;
+    registry.registry_id = registry_id;
+    registry.next_agent_id = RegistryConfig::first_agent_id(registry_id);
+    registry.total_agents = 0;
+    Ok(())
+}
+
+fn validate_registry_params(name: &str, uri: &str) -> Result<()> {
+    require!(
+        name.len() <= RegistryConfig::MAX_NAME_LENGTH,
+        RegistryError::RegistryNameTooLong
+    );
+    require!(
+        uri.len() <= AgentAccount::MAX_URI_LENGTH,
+        RegistryError::UriTooLong
+    );
+    Ok(())
+}
+
+/// Create a registry Core collection with the RegistryConfig PDA as update authority
+#[inline(never)]
+fn create_registry_collection_cpi<'info>(
+    mpl_core_program: &AccountInfo<'info>,
+    collection: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    registry_config: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    name: String,
+    uri: String,
+    registry_bump: u8,
+) -> Result<()> {
+    let collection_key = collection.key();
+    CreateCollectionV2CpiBuilder::new(mpl_core_program)
+        .collection(collection)
+        .payer(payer)
+        .update_authority(Some(registry_config))
+        .system_program(system_program)
+        .name(name)
+        .uri(uri)
+        .invoke_signed(&[&[
+            SEED_REGISTRY_CONFIG,
+            collection_key.as_ref(),
+            &[registry_bump],
+        ]])?;
+    Ok(())
+}
+
+/// Registration variants handled by register_inner
+#[derive(Default)]
+struct RegisterOptions<'a> {
+    atom_enabled: bool,
+    soulbound: bool,
+    compact: bool,
+    allowlist_proof: &'a [[u8; 32]],
+    referrer: Option<Pubkey>,
+    /// EVM owner address already authenticated by the caller (zero = none)
+    evm_owner: [u8; 20],
+}
+
+fn register_inner(
+    ctx: Context<Register>,
+    agent_uri: String,
+    options: RegisterOptions,
+) -> Result<()> {
+    let RegisterOptions {
+        atom_enabled,
+        soulbound,
+        compact,
+        allowlist_proof,
+        referrer,
+        evm_owner,
+    } = options;
+    require!(
+        agent_uri.len() <= AgentAccount::MAX_URI_LENGTH,
+        RegistryError::UriTooLong
+    );
+
+    let registry = &ctx.accounts.registry_config;
+    validate_uri_scheme(&agent_uri, registry.allowed_uri_schemes)?;
+    check_allowlist(registry, &ctx.accounts.owner.key(), allowlist_proof)?;
+    let asset = ctx.accounts.asset.key();
+    let collection_key = ctx.accounts.collection.key();
+    let valid_until = registration_expiry(registry.registration_period, 0)?;
+
+    let referrer_info = match referrer {
+        Some(referrer) => {
+            let info = ctx.accounts.referrer.as_ref().ok_or(RegistryError::InvalidReferral)?;
+            require!(
+                info.key() == referrer && referrer != ctx.accounts.owner.key(),
+                RegistryError::InvalidReferral
+            );
+            Some(info.to_account_info())
+        }
+        None => None,
+    };
+
+    let fee_share = collect_registration_fee(
+        registry,
+        ctx.accounts.treasury.as_mut(),
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        asset,
+        referrer_info.as_ref(),
+    )?;
+
+    if let Some(referrer) = referrer {
+        let referral = ctx.accounts.referral.as_ref().ok_or(RegistryError::InvalidReferral)?;
+        record_referral(
+            &referral.to_account_info(),
+            &ctx.accounts.payer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            asset,
+            collection_key,
+            referrer,
+            fee_share,
+        )?;
+    }
+
+    // Create Core asset
+    create_core_asset_cpi(
+        &ctx.accounts.mpl_core_program.to_account_info(),
+        &ctx.accounts.asset.to_account_info(),
+        &ctx.accounts.collection.to_account_info(),
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.owner.to_account_info(),
+        &registry.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        "Agent".to_string(),
+        if agent_uri.is_empty() {
+            String::new()
+        } else {
+            agent_uri.clone()
+        },
+        soulbound,
+        &[&[
+            SEED_REGISTRY_CONFIG,
+            collection_key.as_ref(),
+            &[registry.bump],
+        ]],
+    )?;
+
+    // Initialize ATOM stats in the same transaction when accounts are provided
+    if atom_enabled {
+        if let (Some(atom_config), Some(atom_stats), Some(atom_engine_program)) = (
+            ctx.accounts.atom_config.as_ref(),
+            ctx.accounts.atom_stats.as_ref(),
+            ctx.accounts.atom_engine_program.as_ref(),
+        ) {
+            initialize_atom_stats_cpi(
+                atom_engine_program,
+                &ctx.accounts.owner.to_account_info(),
+                &ctx.accounts.asset.to_account_info(),
+                &ctx.accounts.collection.to_account_info(),
+                atom_config,
+                atom_stats,
+                &ctx.accounts.system_program.to_account_info(),
+            )?;
+        }
+    }
+
+    let (bond_amount, bond_unlocks_at) = lock_registration_bond(
+        &ctx.accounts.registry_config,
+        &ctx.accounts.agent_account.to_account_info(),
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        asset,
+    )?;
+
+    // Initialize agent account
+    let agent = &mut ctx.accounts.agent_account;
+    agent.collection = collection_key;
+    agent.creator = ctx.accounts.owner.key();
+    agent.owner = ctx.accounts.owner.key();
+    agent.asset = asset;
+    agent.bump = ctx.bumps.agent_account;
+    agent.atom_enabled = atom_enabled;
+    agent.agent_wallet = None;
+    agent.feedback_digest = [0u8; 32];
+    agent.feedback_count = 0;
+    agent.response_digest = [0u8; 32];
+    agent.response_count = 0;
+    agent.revoke_digest = [0u8; 32];
+    agent.revoke_count = 0;
+    agent.parent_asset = None;
+    agent.parent_locked = false;
+    agent.col_locked = false;
+    agent.frozen = false;
+    agent.valid_until = valid_until;
+    agent.token_backed = false;
+    agent.soulbound = soulbound;
+    agent.agent_id = assign_agent_id(
+        &mut ctx.accounts.registry_config,
+        &mut ctx.accounts.agent_id_index,
+        asset,
+        ctx.bumps.agent_id_index,
+    )?;
+    agent.owner_synced_slot = Clock::get()?.slot;
+    agent.bond_amount = bond_amount;
+    agent.bond_unlocks_at = bond_unlocks_at;
+    agent.schema_version = AgentAccount::SCHEMA_VERSION;
+    agent.status = AgentAccount::STATUS_ACTIVE;
+    agent.trust_tier = 0;
+    agent.atom_feedback_count = 0;
+    agent.reputation_synced_slot = 0;
+    agent.uri_hash = AgentAccount::compute_uri_hash(&agent_uri);
+    agent.compact = compact;
+    agent.last_feedback_at = 0;
+    agent.evm_owner = evm_owner;
+    agent.feedback_auth_required = false;
+    agent.score_sum = 0;
+    agent.scored_count = 0;
+    agent.decayed_score_sum = 0;
+    agent.decayed_weight = 0;
+    agent.decayed_at = 0;
+    agent.decay_halvings = 0;
+    agent.score_histogram = [0; SKETCH_BUCKETS];
+    agent.uri_content_type = AgentAccount::URI_CONTENT_UNKNOWN;
+    agent.uri_content_length = 0;
+    agent.agent_uri = if compact {
+        String::new()
+    } else {
+        agent_uri.clone()
+    };
+    agent.nft_name = "Agent".to_string();
+    agent.col = String::new();
+    agent.nft_symbol = String::new();
+
+    emit!(AgentRegistered {
+        asset,
+        collection: collection_key,
+        registry: ctx.accounts.registry_config.key(),
+        owner: ctx.accounts.owner.key(),
+        agent_id: agent.agent_id,
+        uri_hash: agent.uri_hash,
+        created_slot: agent.owner_synced_slot,
+        atom_enabled: agent.atom_enabled,
+        token_backed: false,
+        soulbound,
+        agent_uri,
+    });
+
+    if compact {
+        shrink_to_compact(
+            &ctx.accounts.agent_account.to_account_info(),
+            &ctx.accounts.payer.to_account_info(),
+        )?;
+    }
+
+    if let Some(index) = ctx.accounts.owner_index.as_deref_mut() {
+        add_owner_index_entry(index, ctx.accounts.owner.key(), asset)?;
+    }
+
+    msg!("Agent registered: {} in collection {}", asset, collection_key);
+
+    Ok(())
+}
+
+/// Register agent in a registry collection (base or user registry)
+pub fn register(ctx: Context<Register>, agent_uri: String) -> Result<()> {
+    register_inner(
+        ctx,
+        agent_uri,
+        RegisterOptions {
+            atom_enabled: true,
+            ..Default::default()
+        },
+    )
+}
+
+/// Register agent with explicit ATOM setting (default is true)
+pub fn register_with_options(
+    ctx: Context<Register>,
+    agent_uri: String,
+    atom_enabled: bool,
+) -> Result<()> {
+    register_inner(
+        ctx,
+        agent_uri,
+        RegisterOptions {
+            atom_enabled,
+            ..Default::default()
+        },
+    )
+}
+
+/// Register agent and create its metadata PDAs in one instruction
+///
+/// remaining_accounts: one writable ["agent_meta", asset, SHA256(key)[0..16]] PDA per
+/// `metadata` entry, in the same order. ATOM stats are initialized when the optional
+/// ATOM accounts are provided, exactly like register_with_options.
+pub fn register_with_metadata<'info>(
+    ctx: Context<'_, '_, 'info, 'info, Register<'info>>,
+    agent_uri: String,
+    atom_enabled: bool,
+    metadata: Vec<MetadataInput>,
+) -> Result<()> {
+    require!(
+        metadata.len() <= MetadataInput::MAX_ENTRIES
+            && metadata.len() == ctx.remaining_accounts.len(),
+        RegistryError::MetadataAccountMismatch
+    );
+
+    let remaining = ctx.remaining_accounts;
+    let asset = ctx.accounts.asset.key();
+    let payer = ctx.accounts.payer.to_account_info();
+    let system_program_info = ctx.accounts.system_program.to_account_info();
+
+    register_inner(
+        ctx,
+        agent_uri,
+        RegisterOptions {
+            atom_enabled,
+            ..Default::default()
+        },
+    )?;
+
+    for (entry, metadata_info) in metadata.iter().zip(remaining.iter()) {
+        create_metadata_entry_account(metadata_info, &payer, &system_program_info, asset, entry)?;
+    }
+
+    Ok(())
+}
+
+/// Register a soulbound (non-transferable) agent
+///
+/// Installs a frozen Core PermanentFreezeDelegate so the asset cannot move even
+/// outside the registry; transfer_agent and propose_transfer reject it as well.
+pub fn register_soulbound(
+    ctx: Context<Register>,
+    agent_uri: String,
+    atom_enabled: bool,
+) -> Result<()> {
+    register_inner(
+        ctx,
+        agent_uri,
+        RegisterOptions {
+            atom_enabled,
+            soulbound: true,
+            ..Default::default()
+        },
+    )
+}
+
+/// Register agent in a gated registry with a Merkle proof for the owner wallet
+pub fn register_allowlisted(
+    ctx: Context<Register>,
+    agent_uri: String,
+    atom_enabled: bool,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    register_inner(
+        ctx,
+        agent_uri,
+        RegisterOptions {
+            atom_enabled,
+            allowlist_proof: &proof,
+            ..Default::default()
+        },
+    )
+}
+
+/// Register agent crediting a referrer
+///
+/// Creates the ["referral", asset] PDA and, when a registration fee is charged, pays
+/// `referral_share_bps` of it to the referrer instead of the treasury.
+pub fn register_with_referral(
+    ctx: Context<Register>,
+    agent_uri: String,
+    atom_enabled: bool,
+    referrer: Pubkey,
+) -> Result<()> {
+    register_inner(
+        ctx,
+        agent_uri,
+        RegisterOptions {
+            atom_enabled,
+            referrer: Some(referrer),
+            ..Default::default()
+        },
+    )
+}
+
+/// Register a rent-optimized agent that stores only the URI hash
+///
+/// The URI is emitted in AgentRegistered (and UriUpdated on change) and mirrored on
+/// the Core asset; the AgentAccount keeps uri_hash and is shrunk to COMPACT_SPACE,
+/// refunding the difference to the payer.
+pub fn register_compact(
+    ctx: Context<Register>,
+    agent_uri: String,
+    atom_enabled: bool,
+) -> Result<()> {
+    register_inner(
+        ctx,
+        agent_uri,
+        RegisterOptions {
+            atom_enabled,
+            compact: true,
+            ..Default::default()
+        },
+    )
+}
+
+/// Register an agent on behalf of an Ethereum owner
+///
+/// The EVM address signs (eth_sign) `8004_EVM_REGISTER: || asset || owner || deadline`,
+/// authorizing the Solana `owner` signer to hold and operate the agent. The address is
+/// stored in AgentAccount.evm_owner so ERC-8004 identities keep continuity.
+pub fn register_for_evm_owner(
+    ctx: Context<Register>,
+    agent_uri: String,
+    atom_enabled: bool,
+    evm_address: [u8; 20],
+    signature: [u8; 64],
+    recovery_id: u8,
+    deadline: i64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    require!(
+        clock.unix_timestamp <= deadline,
+        RegistryError::DeadlineExpired
+    );
+    require!(
+        deadline <= clock.unix_timestamp + MAX_DEADLINE_WINDOW,
+        RegistryError::DeadlineTooFar
+    );
+    require!(
+        evm_address != [0u8; 20],
+        RegistryError::InvalidEvmSignature
+    );
+
+    let asset = ctx.accounts.asset.key();
+    let delegate = ctx.accounts.owner.key();
+    let message = build_evm_register_message(asset, delegate, deadline);
+    verify_evm_signature(&evm_address, &message, &signature, recovery_id)?;
+
+    register_inner(
+        ctx,
+        agent_uri,
+        RegisterOptions {
+            atom_enabled,
+            evm_owner: evm_address,
+            ..Default::default()
+        },
+    )?;
+
+    emit!(EvmOwnerLinked {
+        asset,
+        evm_owner: evm_address,
+        delegate,
+    });
+
+    Ok(())
+}
+
+/// Upgrade a legacy AgentAccount to the current layout (permissionless, payer funds rent)
+///
+/// Layout is identified by account size (see LegacyAgentAccount): the account is decoded
+/// with its version's layout, reallocated and re-serialized. Migrated agents receive the
+/// next agent id of their (already migrated) registry config.
+pub fn migrate_agent(ctx: Context<MigrateAgent>) -> Result<()> {
+    let agent_info = ctx.accounts.agent_account.to_account_info();
+    let current_space = AgentAccount::DISCRIMINATOR.len() + AgentAccount::INIT_SPACE;
+    require!(
+        agent_info.data_len() != current_space
+            && agent_info.data_len() != AgentAccount::COMPACT_SPACE,
+        RegistryError::AgentAlreadyMigrated
+    );
+
+    let legacy = LegacyAgentAccount::decode(&agent_info.try_borrow_data()?)?;
+    let asset = legacy.asset();
+    let from_version = legacy.version();
+    require!(
+        legacy.collection() == ctx.accounts.registry_config.collection,
+        RegistryError::InvalidCollection
+    );
+
+    realloc_with_rent(
+        &agent_info,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        current_space,
+    )?;
+
+    let agent_id = assign_agent_id(
+        &mut ctx.accounts.registry_config,
+        &mut ctx.accounts.agent_id_index,
+        asset,
+        ctx.bumps.agent_id_index,
+    )?;
+    let agent = legacy.into_current(agent_id);
+    let mut data = agent_info.try_borrow_mut_data()?;
+    agent.try_serialize(&mut &mut data[..])?;
+
+    emit!(AgentMigrated {
+        asset,
+        agent_id,
+        from_version,
+        to_version: AgentAccount::SCHEMA_VERSION,
+    });
+
+    Ok(())
+}
+
+/// Upgrade a v0.6.0 MetadataEntryPda to the current layout (permissionless, payer funds rent)
+///
+/// The v0 layout is identified by account size. Its tail after the value was never written,
+/// so it is decoded with MetadataEntryPdaV0 rather than read as expires_at / verified_key.
+pub fn migrate_metadata_entry(
+    ctx: Context<MigrateMetadataEntry>,
+    key_hash: [u8; 16],
+) -> Result<()> {
+    let entry_info = ctx.accounts.metadata_entry.to_account_info();
+    require!(
+        entry_info.data_len() != MetadataEntryPda::SPACE,
+        RegistryError::MetadataEntryAlreadyMigrated
+    );
+    require!(
+        entry_info.data_len() == MetadataEntryPdaV0::SPACE,
+        RegistryError::UnsupportedMetadataLayout
+    );
+
+    let legacy = {
+        let data = entry_info.try_borrow_data()?;
+        require!(
+            data[..8] == *MetadataEntryPda::DISCRIMINATOR,
+            RegistryError::UnsupportedMetadataLayout
+        );
+        MetadataEntryPdaV0::deserialize(&mut &data[8..])?
+    };
+
+    realloc_with_rent(
+        &entry_info,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        MetadataEntryPda::SPACE,
+    )?;
+    let entry = legacy.into_current();
+    let mut data = entry_info.try_borrow_mut_data()?;
+    entry.try_serialize(&mut &mut data[..])?;
+
+    emit!(MetadataEntryMigrated {
+        asset: entry.asset,
+        key_hash,
+    });
+
+    Ok(())
+}
+
+/// Upgrade the v0.6.0 RootConfig to the current layout (permissionless, payer funds rent)
+///
+/// The v0.6.0 base registry keeps registry id 0; its agent ids start at 0 and legacy
+/// agents receive theirs from migrate_agent.
+pub fn migrate_root_config(ctx: Context<MigrateRootConfig>) -> Result<()> {
+    let root_info = ctx.accounts.root_config.to_account_info();
+    let current_space = RootConfig::DISCRIMINATOR.len() + RootConfig::INIT_SPACE;
+    require!(
+        root_info.data_len() != current_space,
+        RegistryError::ConfigAlreadyMigrated
+    );
+    require!(
+        root_info.data_len() == RootConfigV0::SPACE,
+        RegistryError::UnsupportedConfigLayout
+    );
+
+    let legacy = {
+        let data = root_info.try_borrow_data()?;
+        require!(
+            data[..8] == *RootConfig::DISCRIMINATOR,
+            RegistryError::UnsupportedConfigLayout
+        );
+        RootConfigV0::deserialize(&mut &data[8..])?
+    };
+
+    realloc_with_rent(
+        &root_info,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        current_space,
+    )?;
+    let root = legacy.into_current();
+    let mut data = root_info.try_borrow_mut_data()?;
+    root.try_serialize(&mut &mut data[..])?;
+
+    emit!(RootConfigMigrated {
+        base_collection: root.base_collection,
+        next_registry_id: root.next_registry_id,
+    });
+
+    Ok(())
+}
+
+/// Upgrade a v0.6.0 RegistryConfig to the current layout (permissionless, payer funds rent)
+///
+/// The v0 layout (collection, authority, bump) is identified by account size; the config
+/// is reallocated and the fields added since take the defaults of a new registry.
+pub fn migrate_registry_config(ctx: Context<MigrateRegistryConfig>) -> Result<()> {
+    let config_info = ctx.accounts.registry_config.to_account_info();
+    let current_space = RegistryConfig::DISCRIMINATOR.len() + RegistryConfig::INIT_SPACE;
+    require!(
+        config_info.data_len() != current_space,
+        RegistryError::ConfigAlreadyMigrated
+    );
+    require!(
+        config_info.data_len() == RegistryConfigV0::SPACE,
+        RegistryError::UnsupportedConfigLayout
+    );
+
+    let legacy = {
+        let data = config_info.try_borrow_data()?;
+        require!(
+            data[..8] == *RegistryConfig::DISCRIMINATOR,
+            RegistryError::UnsupportedConfigLayout
+        );
+        RegistryConfigV0::deserialize(&mut &data[8..])?
+    };
+
+    realloc_with_rent(
+        &config_info,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        current_space,
+    )?;
+    let registry = legacy.into_current();
+    let mut data = config_info.try_borrow_mut_data()?;
+    registry.try_serialize(&mut &mut data[..])?;
+
+    emit!(RegistryConfigMigrated {
+        collection: registry.collection,
+        authority: registry.authority,
+    });
+
+    Ok(())
+}
+
+/// Create a program-owned PDA of `space` bytes, funded by `payer`
+///
+/// Works like Anchor's `init`: an address that already holds lamports (anyone can transfer
+/// to a PDA) is topped up, allocated and assigned instead of failing `create_account`.
+pub(crate) fn create_pda_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program_info: &AccountInfo<'info>,
+    space: usize,
+    signer_seeds: &[&[u8]],
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(space);
+    let current_lamports = account.lamports();
+    if current_lamports == 0 {
+        return system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program_info.clone(),
+                system_program::CreateAccount {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+                &[signer_seeds],
+            ),
+            rent,
+            space as u64,
+            &crate::ID,
+        );
+    }
+
+    let top_up = rent.saturating_sub(current_lamports);
+    if top_up > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program_info.clone(),
+                system_program::Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+    system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program_info.clone(),
+            system_program::Allocate {
+                account_to_allocate: account.clone(),
+            },
+            &[signer_seeds],
+        ),
+        space as u64,
+    )?;
+    system_program::assign(
+        CpiContext::new_with_signer(
+            system_program_info.clone(),
+            system_program::Assign {
+                account_to_assign: account.clone(),
+            },
+            &[signer_seeds],
+        ),
+        &crate::ID,
+    )
+}
+
+/// Top up rent from `payer` and grow a program account to `space` bytes
+fn realloc_with_rent<'info>(
+    account: &AccountInfo<'info>,
     payer: &AccountInfo<'info>,
-    owner: &AccountInfo<'info>,
-    authority: &AccountInfo<'info>,
-    system_program: &AccountInfo<'info>,
-    name: String,
-    uri: String,
-    signer_seeds: &[&[&[u8]]],
+    system_program_info: &AccountInfo<'info>,
+    space: usize,
 ) -> Result<()> {
-    CreateV2CpiBuilder::new(mpl_core_program)
-        .asset(asset)
-        .collection(Some(collection))
-        .payer(payer)
-        .owner(Some(owner))
-        .authority(Some(authority))
-        .system_program(system_program)
-        .name(name)
-        .uri(uri)
-        .invoke_signed(signer_seeds)?;
+    let rent_shortfall = Rent::get()?
+        .minimum_balance(space)
+        .saturating_sub(account.lamports());
+    if rent_shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program_info.clone(),
+                system_program::Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            rent_shortfall,
+        )?;
+    }
+    account.realloc(space, false)?;
+    Ok(())
+}
+
+/// Rebind an agent to a new Core asset, preserving agent_id, counters and digests
+///
+/// Used after re-mints or collection migrations. The AgentAccount moves to the PDA of
+/// the new asset (bond lamports included) and AgentIdIndex is re-pointed. Asset-keyed
+/// satellite accounts (metadata, ATOM stats) are not moved: AssetReplaced lets indexers
+/// and re-keying hooks follow the agent_id.
+pub fn replace_asset(ctx: Context<ReplaceAsset>) -> Result<()> {
+    let owner = ctx.accounts.owner.key();
+    let old = &ctx.accounts.old_agent_account;
+    require!(!old.token_backed, RegistryError::InvalidAsset);
+
+    if is_core_asset_burned(&ctx.accounts.old_asset) {
+        require!(old.owner == owner, RegistryError::Unauthorized);
+    } else {
+        verify_core_owner(&ctx.accounts.old_asset, &owner)?;
+    }
+    verify_core_owner(&ctx.accounts.new_asset, &owner)?;
+
+    let new_collection =
+        get_core_collection(&ctx.accounts.new_asset)?.ok_or(RegistryError::InvalidCollection)?;
+    require!(
+        new_collection == ctx.accounts.new_registry_config.collection,
+        RegistryError::InvalidCollection
+    );
+
+    let old_asset = old.asset;
+    let old_collection = old.collection;
+    let new_asset = ctx.accounts.new_asset.key();
+    let bond_amount = old.bond_amount;
+
+    let mut agent = (**old).clone();
+    agent.asset = new_asset;
+    agent.collection = new_collection;
+    agent.owner = owner;
+    agent.bump = ctx.bumps.new_agent_account;
+    agent.owner_synced_slot = Clock::get()?.slot;
+    let agent_id = agent.agent_id;
+    ctx.accounts.new_agent_account.set_inner(agent);
+
+    // The bond stays locked with the agent instead of leaving through the close
+    if bond_amount > 0 {
+        let old_info = ctx.accounts.old_agent_account.to_account_info();
+        let new_info = ctx.accounts.new_agent_account.to_account_info();
+        let old_balance = old_info
+            .lamports()
+            .checked_sub(bond_amount)
+            .ok_or(RegistryError::Overflow)?;
+        let new_balance = new_info
+            .lamports()
+            .checked_add(bond_amount)
+            .ok_or(RegistryError::Overflow)?;
+        **old_info.try_borrow_mut_lamports()? = old_balance;
+        **new_info.try_borrow_mut_lamports()? = new_balance;
+    }
+
+    ctx.accounts.agent_id_index.asset = new_asset;
+
+    // The agent leaves the old registry's count when it moves collections
+    if new_collection != old_collection {
+        let registry = &mut ctx.accounts.registry_config;
+        registry.total_agents = registry.total_agents.saturating_sub(1);
+        let new_registry = &mut ctx.accounts.new_registry_config;
+        new_registry.total_agents = new_registry
+            .total_agents
+            .checked_add(1)
+            .ok_or(RegistryError::Overflow)?;
+    }
+
+    emit!(AssetReplaced {
+        agent_id,
+        old_asset,
+        new_asset,
+        old_collection,
+        new_collection,
+        owner,
+        approved_by: ctx.accounts.authority.key(),
+    });
+
+    msg!("Agent {} rebound: {} -> {}", agent_id, old_asset, new_asset);
+
+    Ok(())
+}
+
+/// Deregister agent
+///
+/// Burns the Core asset via CPI (or accepts an asset that was already burned
+/// directly through Core), closes the AgentAccount and its AgentIdIndex, returning rent
+/// to the owner, and decrements the registry's total_agents.
+/// A burned asset cannot prove the cached owner is current, so in that case the rent
+/// (and any bond) goes to the registry treasury instead. Metadata PDAs are left untouched
+/// and can only be closed with delete_metadata_pda while the asset is live, so delete them
+/// before deregistering.
+pub fn deregister(ctx: Context<Deregister>) -> Result<()> {
+    let owner = ctx.accounts.owner.key();
+    let agent = &ctx.accounts.agent_account;
+    let asset = agent.asset;
+    let collection = agent.collection;
+
+    let now = Clock::get()?.unix_timestamp;
+
+    // Bond (held in agent_account lamports) is returned by the close only after cooldown
+    if agent.bond_amount > 0 {
+        require!(now >= agent.bond_unlocks_at, RegistryError::BondLocked);
+    }
+
+    // Agents with feedback stay until the registry dispute window has elapsed
+    if agent.last_feedback_at > 0 {
+        require!(
+            now >= agent
+                .last_feedback_at
+                .saturating_add(ctx.accounts.registry_config.dispute_window),
+            RegistryError::DisputeWindowOpen
+        );
+    }
+
+    if is_core_asset_burned(&ctx.accounts.asset) {
+        // Asset already burned through Core: the cached owner is the only record left and
+        // may predate a transfer, so it authorizes the close but does not receive the rent
+        require!(agent.owner == owner, RegistryError::Unauthorized);
+        let treasury = ctx
+            .accounts
+            .treasury
+            .as_mut()
+            .ok_or(RegistryError::InvalidTreasury)?;
+        let agent_info = ctx.accounts.agent_account.to_account_info();
+        let index_info = ctx.accounts.agent_id_index.to_account_info();
+        let treasury_info = treasury.to_account_info();
+        let amount = agent_info
+            .lamports()
+            .checked_add(index_info.lamports())
+            .ok_or(RegistryError::Overflow)?;
+        let treasury_balance = treasury_info
+            .lamports()
+            .checked_add(amount)
+            .ok_or(RegistryError::Overflow)?;
+        **agent_info.try_borrow_mut_lamports()? = 0;
+        **index_info.try_borrow_mut_lamports()? = 0;
+        **treasury_info.try_borrow_mut_lamports()? = treasury_balance;
+        treasury.total_collected = treasury
+            .total_collected
+            .checked_add(amount)
+            .ok_or(RegistryError::Overflow)?;
+    } else {
+        verify_core_owner(&ctx.accounts.asset, &owner)?;
+
+        // Soulbound assets are permanently frozen; lift the freeze so Core allows the burn
+        if agent.soulbound {
+            let registry = &ctx.accounts.registry_config;
+            let collection_key = ctx.accounts.collection.key();
+            let signer_seeds: &[&[&[u8]]] = &[&[
+                SEED_REGISTRY_CONFIG,
+                collection_key.as_ref(),
+                &[registry.bump],
+            ]];
+            UpdatePluginV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+                .asset(&ctx.accounts.asset.to_account_info())
+                .collection(Some(&ctx.accounts.collection.to_account_info()))
+                .payer(&ctx.accounts.owner.to_account_info())
+                .authority(Some(&registry.to_account_info()))
+                .system_program(&ctx.accounts.system_program.to_account_info())
+                .plugin(Plugin::PermanentFreezeDelegate(PermanentFreezeDelegate {
+                    frozen: false,
+                }))
+                .invoke_signed(signer_seeds)?;
+        }
+
+        BurnV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+            .asset(&ctx.accounts.asset.to_account_info())
+            .collection(Some(&ctx.accounts.collection.to_account_info()))
+            .payer(&ctx.accounts.owner.to_account_info())
+            .authority(Some(&ctx.accounts.owner.to_account_info()))
+            .system_program(Some(&ctx.accounts.system_program.to_account_info()))
+            .invoke()?;
+    }
+
+    emit!(AgentDeregistered {
+        asset,
+        collection,
+        owner,
+    });
+
+    if let Some(index) = ctx.accounts.owner_index.as_deref_mut() {
+        remove_owner_index_entry(index, owner, asset)?;
+    }
+
+    let registry = &mut ctx.accounts.registry_config;
+    registry.total_agents = registry.total_agents.saturating_sub(1);
+
+    msg!("Agent deregistered: {}", asset);
+
+    Ok(())
+}
+
+/// Freeze an agent (registry authority only)
+///
+/// While frozen, set_agent_uri, set_metadata_pda and give_feedback are rejected.
+pub fn freeze_agent(ctx: Context<FreezeAgent>) -> Result<()> {
+    set_agent_frozen(ctx, true)
+}
+
+/// Unfreeze an agent (registry authority only)
+pub fn unfreeze_agent(ctx: Context<FreezeAgent>) -> Result<()> {
+    set_agent_frozen(ctx, false)
+}
+
+/// Suspend an agent (registry authority only)
+///
+/// Suspended agents reject new feedback and cannot change their own status.
+pub fn suspend_agent(ctx: Context<FreezeAgent>) -> Result<()> {
+    set_agent_suspended(ctx, true)
+}
+
+/// Lift a suspension, restoring ACTIVE status (registry authority only)
+pub fn unsuspend_agent(ctx: Context<FreezeAgent>) -> Result<()> {
+    set_agent_suspended(ctx, false)
+}
+
+fn set_agent_suspended(ctx: Context<FreezeAgent>, suspended: bool) -> Result<()> {
+    let agent = &mut ctx.accounts.agent_account;
+    let old_status = agent.status;
+    if suspended {
+        agent.status = AgentAccount::STATUS_SUSPENDED;
+    } else {
+        require!(
+            old_status == AgentAccount::STATUS_SUSPENDED,
+            RegistryError::InvalidAgentStatus
+        );
+        agent.status = AgentAccount::STATUS_ACTIVE;
+    }
+
+    emit!(AgentStatusUpdated {
+        asset: agent.asset,
+        old_status,
+        new_status: agent.status,
+        updated_by: ctx.accounts.authority.key(),
+    });
+
+    Ok(())
+}
+
+fn set_agent_frozen(ctx: Context<FreezeAgent>, frozen: bool) -> Result<()> {
+    let agent = &mut ctx.accounts.agent_account;
+    agent.frozen = frozen;
+
+    emit!(AgentFreezeUpdated {
+        asset: agent.asset,
+        frozen,
+        updated_by: ctx.accounts.authority.key(),
+    });
+
+    msg!("Agent {} frozen: {}", agent.asset, frozen);
+
+    Ok(())
+}
+
+/// Register agent backed by a Token-2022 NFT
+///
+/// Mints a 0-decimal Token-2022 NFT whose metadata pointer targets the mint itself,
+/// initializes the token metadata (name/uri), mints 1 token to the owner's ATA and
+/// removes the mint authority so supply is fixed at one.
+///
+/// Note: URI updates, transfers and deregistration remain Metaplex Core only for now;
+/// those instructions fail closed (InvalidAsset) for token-backed agents. Use
+/// sync_token_owner to refresh the cached owner after a token transfer.
+pub fn register_token2022(
+    ctx: Context<RegisterToken2022>,
+    agent_uri: String,
+    atom_enabled: bool,
+) -> Result<()> {
+    require!(
+        agent_uri.len() <= AgentAccount::MAX_URI_LENGTH,
+        RegistryError::UriTooLong
+    );
+
+    let registry = &ctx.accounts.registry_config;
+    validate_uri_scheme(&agent_uri, registry.allowed_uri_schemes)?;
+    let mint_key = ctx.accounts.mint.key();
+    let collection_key = ctx.accounts.collection.key();
+    let owner_key = ctx.accounts.owner.key();
+    // No proof argument: gated registries only admit single-wallet allowlists here
+    check_allowlist(registry, &owner_key, &[])?;
+    let valid_until = registration_expiry(registry.registration_period, 0)?;
+    let name = "Agent".to_string();
+
+    collect_registration_fee(
+        registry,
+        ctx.accounts.treasury.as_mut(),
+        &ctx.accounts.owner.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        mint_key,
+        None,
+    )?;
+
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        SEED_REGISTRY_CONFIG,
+        collection_key.as_ref(),
+        &[registry.bump],
+    ]];
+
+    // Fund the TokenMetadata TLV entry before the token program reallocs the mint:
+    // type+length (4) + update_authority (32) + mint (32) + name/symbol/uri (4 + len each)
+    // + empty additional_metadata vec (4)
+    let metadata_len = 4 + 32 + 32 + (4 + name.len()) + 4 + (4 + agent_uri.len()) + 4;
+    let mint_info = ctx.accounts.mint.to_account_info();
+    let required_lamports = Rent::get()?.minimum_balance(mint_info.data_len() + metadata_len);
+    let extra_lamports = required_lamports.saturating_sub(mint_info.lamports());
+    if extra_lamports > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.owner.to_account_info(),
+                    to: mint_info.clone(),
+                },
+            ),
+            extra_lamports,
+        )?;
+    }
+
+    token_metadata_initialize(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TokenMetadataInitialize {
+                program_id: ctx.accounts.token_program.to_account_info(),
+                metadata: mint_info.clone(),
+                update_authority: registry.to_account_info(),
+                mint: mint_info.clone(),
+                mint_authority: registry.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        name.clone(),
+        String::new(),
+        agent_uri.clone(),
+    )?;
+
+    mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: mint_info.clone(),
+                to: ctx.accounts.owner_token_account.to_account_info(),
+                authority: registry.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        1,
+    )?;
+
+    // Fix supply at one
+    set_authority(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            SetAuthority {
+                current_authority: registry.to_account_info(),
+                account_or_mint: mint_info,
+            },
+            signer_seeds,
+        ),
+        AuthorityType::MintTokens,
+        None,
+    )?;
+
+    let (bond_amount, bond_unlocks_at) = lock_registration_bond(
+        &ctx.accounts.registry_config,
+        &ctx.accounts.agent_account.to_account_info(),
+        &ctx.accounts.owner.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        mint_key,
+    )?;
+
+    let agent = &mut ctx.accounts.agent_account;
+    agent.collection = collection_key;
+    agent.creator = owner_key;
+    agent.owner = owner_key;
+    agent.asset = mint_key;
+    agent.bump = ctx.bumps.agent_account;
+    agent.atom_enabled = atom_enabled;
+    agent.agent_wallet = None;
+    agent.feedback_digest = [0u8; 32];
+    agent.feedback_count = 0;
+    agent.response_digest = [0u8; 32];
+    agent.response_count = 0;
+    agent.revoke_digest = [0u8; 32];
+    agent.revoke_count = 0;
+    agent.parent_asset = None;
+    agent.parent_locked = false;
+    agent.col_locked = false;
+    agent.frozen = false;
+    agent.valid_until = valid_until;
+    agent.token_backed = true;
+    agent.soulbound = false;
+    agent.agent_id = assign_agent_id(
+        &mut ctx.accounts.registry_config,
+        &mut ctx.accounts.agent_id_index,
+        mint_key,
+        ctx.bumps.agent_id_index,
+    )?;
+    agent.owner_synced_slot = Clock::get()?.slot;
+    agent.bond_amount = bond_amount;
+    agent.bond_unlocks_at = bond_unlocks_at;
+    agent.schema_version = AgentAccount::SCHEMA_VERSION;
+    agent.status = AgentAccount::STATUS_ACTIVE;
+    agent.trust_tier = 0;
+    agent.atom_feedback_count = 0;
+    agent.reputation_synced_slot = 0;
+    agent.uri_hash = AgentAccount::compute_uri_hash(&agent_uri);
+    agent.compact = false;
+    agent.last_feedback_at = 0;
+    agent.evm_owner = [0u8; 20];
+    agent.feedback_auth_required = false;
+    agent.score_sum = 0;
+    agent.scored_count = 0;
+    agent.decayed_score_sum = 0;
+    agent.decayed_weight = 0;
+    agent.decayed_at = 0;
+    agent.decay_halvings = 0;
+    agent.score_histogram = [0; SKETCH_BUCKETS];
+    agent.uri_content_type = AgentAccount::URI_CONTENT_UNKNOWN;
+    agent.uri_content_length = 0;
+    agent.agent_uri = agent_uri;
+    agent.nft_name = name;
+    agent.col = String::new();
+    agent.nft_symbol = String::new();
+
+    emit!(AgentRegistered {
+        asset: mint_key,
+        collection: collection_key,
+        registry: ctx.accounts.registry_config.key(),
+        owner: owner_key,
+        agent_id: agent.agent_id,
+        uri_hash: agent.uri_hash,
+        created_slot: agent.owner_synced_slot,
+        atom_enabled,
+        token_backed: true,
+        soulbound: false,
+        agent_uri: agent.agent_uri.clone(),
+    });
+
+    if let Some(index) = ctx.accounts.owner_index.as_deref_mut() {
+        add_owner_index_entry(index, owner_key, mint_key)?;
+    }
+
+    msg!("Token-2022 agent registered: {} in collection {}", mint_key, collection_key);
+
+    Ok(())
+}
+
+/// Create the treasury PDA that receives registration fees
+pub fn initialize_treasury(ctx: Context<InitializeTreasury>) -> Result<()> {
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.collection = ctx.accounts.registry_config.collection;
+    treasury.total_collected = 0;
+    treasury.total_withdrawn = 0;
+    treasury.bump = ctx.bumps.treasury;
+
+    msg!("Treasury initialized for collection {}", treasury.collection);
+
+    Ok(())
+}
+
+/// Set the registration fee in lamports (0 disables fees)
+pub fn set_registration_fee(ctx: Context<SetRegistrationFee>, registration_fee: u64) -> Result<()> {
+    let registry = &mut ctx.accounts.registry_config;
+    registry.registration_fee = registration_fee;
+
+    emit!(RegistrationFeeUpdated {
+        collection: registry.collection,
+        registration_fee,
+    });
+
+    Ok(())
+}
+
+/// Add an attestor allowed to issue agent verifications in this registry
+pub fn add_attestor(ctx: Context<AddAttestor>) -> Result<()> {
+    let collection = ctx.accounts.registry_config.collection;
+    let attestor = ctx.accounts.attestor.key();
+
+    let record = &mut ctx.accounts.attestor_record;
+    record.collection = collection;
+    record.attestor = attestor;
+    record.added_at = Clock::get()?.unix_timestamp;
+    record.bump = ctx.bumps.attestor_record;
+
+    emit!(AttestorUpdated {
+        collection,
+        attestor,
+        active: true,
+    });
+
+    Ok(())
+}
+
+/// Remove an attestor (existing verifications remain until overwritten)
+pub fn remove_attestor(ctx: Context<RemoveAttestor>) -> Result<()> {
+    emit!(AttestorUpdated {
+        collection: ctx.accounts.attestor_record.collection,
+        attestor: ctx.accounts.attestor_record.attestor,
+        active: false,
+    });
+
+    Ok(())
+}
+
+/// Set an agent's verification level and attestation hash (attestor only)
+///
+/// Level 0 revokes the verification while keeping the account for history.
+pub fn set_verification(
+    ctx: Context<SetVerification>,
+    level: u8,
+    attestation_hash: [u8; 32],
+) -> Result<()> {
+    let asset = ctx.accounts.asset.key();
+    let attestor = ctx.accounts.attestor.key();
+
+    let verification = &mut ctx.accounts.agent_verification;
+    verification.asset = asset;
+    verification.level = level;
+    verification.attestation_hash = attestation_hash;
+    verification.attestor = attestor;
+    verification.verified_at = Clock::get()?.unix_timestamp;
+    verification.bump = ctx.bumps.agent_verification;
+
+    emit!(AgentVerificationSet {
+        asset,
+        attestor,
+        level,
+        attestation_hash,
+    });
+
+    msg!("Verification level {} set for asset {}", level, asset);
+
+    Ok(())
+}
+
+/// Set the secondary-sale royalty policy for the registry collection
+///
+/// Installs (or updates) a collection-level Core Royalties plugin paying `basis_points`
+/// to the treasury PDA. With ROYALTY_ROUTE_CREATOR, apply_creator_royalty redirects each
+/// asset's royalties to its creator. While `basis_points` > 0, owners can no longer set
+/// their own royalties; 0 hands royalties back to owners (collection plugin at 0 bps).
+pub fn set_collection_royalties(
+    ctx: Context<SetCollectionRoyalties>,
+    basis_points: u16,
+    route: u8,
+) -> Result<()> {
+    require!(
+        basis_points <= 10_000 && route <= RegistryConfig::ROYALTY_ROUTE_CREATOR,
+        RegistryError::InvalidRoyalties
+    );
+
+    let treasury = ctx.accounts.treasury.key();
+    let plugin = Plugin::Royalties(Royalties {
+        basis_points,
+        creators: vec![Creator {
+            address: treasury,
+            percentage: 100,
+        }],
+        rule_set: RuleSet::None,
+    });
+
+    let collection_key = ctx.accounts.collection.key();
+    let registry_bump = ctx.accounts.registry_config.bump;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        SEED_REGISTRY_CONFIG,
+        collection_key.as_ref(),
+        &[registry_bump],
+    ]];
+
+    let exists = fetch_plugin::<BaseCollectionV1, Royalties>(
+        &ctx.accounts.collection.to_account_info(),
+        PluginType::Royalties,
+    )
+    .is_ok();
+
+    if exists {
+        UpdateCollectionPluginV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+            .collection(&ctx.accounts.collection.to_account_info())
+            .payer(&ctx.accounts.authority.to_account_info())
+            .authority(Some(&ctx.accounts.registry_config.to_account_info()))
+            .system_program(&ctx.accounts.system_program.to_account_info())
+            .plugin(plugin)
+            .invoke_signed(signer_seeds)?;
+    } else {
+        AddCollectionPluginV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+            .collection(&ctx.accounts.collection.to_account_info())
+            .payer(&ctx.accounts.authority.to_account_info())
+            .authority(Some(&ctx.accounts.registry_config.to_account_info()))
+            .system_program(&ctx.accounts.system_program.to_account_info())
+            .plugin(plugin)
+            .init_authority(PluginAuthority::UpdateAuthority)
+            .invoke_signed(signer_seeds)?;
+    }
+
+    let registry = &mut ctx.accounts.registry_config;
+    registry.royalty_bps = basis_points;
+    registry.royalty_route = route;
+
+    emit!(CollectionRoyaltiesUpdated {
+        collection: collection_key,
+        basis_points,
+        route,
+        treasury,
+    });
+
+    Ok(())
+}
+
+/// Set the post-feedback dispute window that blocks deregistration (seconds, 0 = off)
+pub fn set_dispute_window(ctx: Context<SetDisputeWindow>, dispute_window: i64) -> Result<()> {
+    require!(dispute_window >= 0, RegistryError::InvalidDisputeWindow);
+
+    let registry = &mut ctx.accounts.registry_config;
+    registry.dispute_window = dispute_window;
+
+    emit!(DisputeWindowUpdated {
+        collection: registry.collection,
+        dispute_window,
+    });
+
+    Ok(())
+}
+
+/// Select how feedback_index is assigned (registry authority only)
+///
+/// Applies to new feedback only; indexes already emitted keep their meaning.
+pub fn set_feedback_index_mode(ctx: Context<SetFeedbackIndexMode>, mode: u8) -> Result<()> {
+    require!(
+        mode <= RegistryConfig::FEEDBACK_INDEX_PER_CLIENT,
+        RegistryError::InvalidFeedbackIndexMode
+    );
+
+    let registry = &mut ctx.accounts.registry_config;
+    registry.feedback_index_mode = mode;
+
+    emit!(FeedbackIndexModeUpdated {
+        collection: registry.collection,
+        mode,
+    });
+
+    Ok(())
+}
+
+/// Configure paid feedback: accepted mint and minimum amount (registry authority only)
+///
+/// Pubkey::default() as mint disables give_feedback_paid.
+pub fn set_feedback_payment(
+    ctx: Context<SetFeedbackPayment>,
+    mint: Pubkey,
+    min_amount: u64,
+) -> Result<()> {
+    let registry = &mut ctx.accounts.registry_config;
+    registry.feedback_payment_mint = mint;
+    registry.min_feedback_payment = min_amount;
+
+    emit!(FeedbackPaymentUpdated {
+        collection: registry.collection,
+        mint,
+        min_amount,
+    });
+
+    Ok(())
+}
+
+/// Allow or forbid self-feedback in this registry (registry authority only)
+///
+/// Intended for test environments; production registries should keep it disabled.
+pub fn set_allow_self_feedback(ctx: Context<SetAllowSelfFeedback>, allowed: bool) -> Result<()> {
+    let registry = &mut ctx.accounts.registry_config;
+    registry.allow_self_feedback = allowed;
+
+    emit!(SelfFeedbackPolicyUpdated {
+        collection: registry.collection,
+        allowed,
+    });
+
     Ok(())
 }
 
-/// Update Core asset URI via CPI
-#[inline(never)]
-fn update_core_asset_uri_cpi<'info>(
-    mpl_core_program: &AccountInfo<'info>,
-    asset: &AccountInfo<'info>,
-    collection: &AccountInfo<'info>,
-    payer: &AccountInfo<'info>,
-    authority: &AccountInfo<'info>,
-    system_program: &AccountInfo<'info>,
-    new_uri: String,
-    signer_seeds: &[&[&[u8]]],
+/// Set the per client-agent feedback cooldown in slots (registry authority only)
+pub fn set_feedback_cooldown(ctx: Context<SetFeedbackCooldown>, cooldown_slots: u64) -> Result<()> {
+    let registry = &mut ctx.accounts.registry_config;
+    registry.feedback_cooldown_slots = cooldown_slots;
+
+    emit!(FeedbackCooldownUpdated {
+        collection: registry.collection,
+        cooldown_slots,
+    });
+
+    Ok(())
+}
+
+/// Configure per-feedback client bonds and their challenge window (registry authority only)
+pub fn set_feedback_bond(
+    ctx: Context<SetFeedbackBond>,
+    amount: u64,
+    challenge_window: i64,
 ) -> Result<()> {
-    UpdateV1CpiBuilder::new(mpl_core_program)
-        .asset(asset)
-        .collection(Some(collection))
-        .payer(payer)
-        .authority(Some(authority))
-        .system_program(system_program)
-        .new_uri(new_uri)
-        .invoke_signed(signer_seeds)?;
+    require!(challenge_window >= 0, RegistryError::InvalidDisputeWindow);
+
+    let registry = &mut ctx.accounts.registry_config;
+    registry.feedback_bond = amount;
+    registry.feedback_bond_window = challenge_window;
+
+    emit!(FeedbackBondUpdated {
+        collection: registry.collection,
+        amount,
+        challenge_window,
+    });
+
     Ok(())
 }
 
-// ============================================================================
-// Single Collection Instructions
-// ============================================================================
+/// Set the delay before committed feedback can be revealed unilaterally (registry authority only)
+pub fn set_feedback_reveal_delay(ctx: Context<SetFeedbackRevealDelay>, delay: i64) -> Result<()> {
+    require!(delay >= 0, RegistryError::InvalidDisputeWindow);
 
-/// Initialize the registry with root config and base collection
-pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
-    let root = &mut ctx.accounts.root_config;
     let registry = &mut ctx.accounts.registry_config;
-    let collection_key = ctx.accounts.collection.key();
+    registry.feedback_reveal_delay = delay;
 
-    // Initialize root config
-    root.base_collection = collection_key;
-    root.authority = ctx.accounts.authority.key();
-    root.bump = ctx.bumps.root_config;
+    emit!(FeedbackRevealDelayUpdated {
+        collection: registry.collection,
+        delay,
+    });
 
-    // Initialize registry config
-    registry.collection = collection_key;
-    registry.authority = ctx.accounts.authority.key();
-    registry.bump = ctx.bumps.registry_config;
+    Ok(())
+}
 
-    // Create Metaplex Core Collection
-    CreateCollectionV2CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info())
+/// Require registered canonical tags on feedback (registry authority only)
+pub fn set_strict_feedback_tags(ctx: Context<SetStrictFeedbackTags>, strict: bool) -> Result<()> {
+    let registry = &mut ctx.accounts.registry_config;
+    registry.strict_feedback_tags = strict;
+
+    emit!(StrictFeedbackTagsUpdated {
+        collection: registry.collection,
+        strict,
+    });
+
+    Ok(())
+}
+
+/// Set the share of the registration fee paid to referrers (basis points)
+pub fn set_referral_share(ctx: Context<SetReferralShare>, referral_share_bps: u16) -> Result<()> {
+    require!(
+        referral_share_bps <= 10_000,
+        RegistryError::InvalidReferralShare
+    );
+
+    let registry = &mut ctx.accounts.registry_config;
+    registry.referral_share_bps = referral_share_bps;
+
+    emit!(ReferralShareUpdated {
+        collection: registry.collection,
+        referral_share_bps,
+    });
+
+    Ok(())
+}
+
+/// Set the refundable registration bond and its cooldown (applies to new agents only)
+pub fn set_registration_bond(
+    ctx: Context<SetRegistrationBond>,
+    registration_bond: u64,
+    bond_cooldown: i64,
+) -> Result<()> {
+    require!(bond_cooldown >= 0, RegistryError::InvalidBondCooldown);
+
+    let registry = &mut ctx.accounts.registry_config;
+    registry.registration_bond = registration_bond;
+    registry.bond_cooldown = bond_cooldown;
+
+    emit!(RegistrationBondUpdated {
+        collection: registry.collection,
+        registration_bond,
+        bond_cooldown,
+    });
+
+    Ok(())
+}
+
+/// Set the registration allowlist root (zero root reopens permissionless registration)
+pub fn set_allowlist_root(ctx: Context<SetAllowlistRoot>, allowlist_root: [u8; 32]) -> Result<()> {
+    let registry = &mut ctx.accounts.registry_config;
+    registry.allowlist_root = allowlist_root;
+
+    emit!(AllowlistRootUpdated {
+        collection: registry.collection,
+        allowlist_root,
+    });
+
+    Ok(())
+}
+
+/// Slash an agent's registration bond into the treasury (confirmed scam agents)
+pub fn slash_registration_bond(ctx: Context<SlashRegistrationBond>) -> Result<()> {
+    let amount = ctx.accounts.agent_account.bond_amount;
+    require!(amount > 0, RegistryError::NoRegistrationBond);
+
+    let agent_info = ctx.accounts.agent_account.to_account_info();
+    let treasury_info = ctx.accounts.treasury.to_account_info();
+    let agent_balance = agent_info
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(RegistryError::Overflow)?;
+    let treasury_balance = treasury_info
+        .lamports()
+        .checked_add(amount)
+        .ok_or(RegistryError::Overflow)?;
+    **agent_info.try_borrow_mut_lamports()? = agent_balance;
+    **treasury_info.try_borrow_mut_lamports()? = treasury_balance;
+
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.total_collected = treasury
+        .total_collected
+        .checked_add(amount)
+        .ok_or(RegistryError::Overflow)?;
+
+    let agent = &mut ctx.accounts.agent_account;
+    agent.bond_amount = 0;
+    agent.bond_unlocks_at = 0;
+
+    emit!(RegistrationBondSlashed {
+        asset: agent.asset,
+        amount,
+        slashed_by: ctx.accounts.authority.key(),
+    });
+
+    Ok(())
+}
+
+/// Update the registry collection name and/or URI
+///
+/// The collection is created with an empty URI; this lets the registry authority set
+/// display metadata for wallets and explorers. `None` leaves a field unchanged.
+pub fn update_collection(
+    ctx: Context<UpdateCollection>,
+    name: Option<String>,
+    uri: Option<String>,
+) -> Result<()> {
+    validate_registry_params(
+        name.as_deref().unwrap_or_default(),
+        uri.as_deref().unwrap_or_default(),
+    )?;
+
+    let collection_key = ctx.accounts.collection.key();
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        SEED_REGISTRY_CONFIG,
+        collection_key.as_ref(),
+        &[ctx.accounts.registry_config.bump],
+    ]];
+
+    let mut builder =
+        UpdateCollectionV1CpiBuilder::new(&ctx.accounts.mpl_core_program.to_account_info());
+    builder
         .collection(&ctx.accounts.collection.to_account_info())
         .payer(&ctx.accounts.authority.to_account_info())
-        .update_authority(Some(&registry.to_account_info()))
-        .system_program(&ctx.accounts.system_program.to_account_info())
-        .name("8004 Agent Registry".to_string())
-        .uri(String::new())
-        .invoke_signed(&[&[
+        .authority(Some(&ctx.accounts.registry_config.to_account_info()))
+        .system_program(&ctx.accounts.system_program.to_account_info());
+    if let Some(name) = name.clone() {
+        builder.new_name(name);
+    }
+    if let Some(uri) = uri.clone() {
+        builder.new_uri(uri);
+    }
+    builder.invoke_signed(signer_seeds)?;
+
+    emit!(CollectionUpdated {
+        collection: collection_key,
+        updated_by: ctx.accounts.authority.key(),
+        name,
+        uri,
+    });
+
+    Ok(())
+}
+
+/// Enable or disable the collection transfer gate
+///
+/// The first call creates the TransferGate oracle account and installs it on the
+/// collection as a Core Oracle plugin that can reject transfers. While enabled,
+/// assets only move through transfer_agent / accept_transfer, so the cached owner
+/// cannot go stale. Disabling sets the oracle result to Pass; the plugin stays installed,
+/// so every transfer still lists the gate account (Core SDKs add oracle accounts).
+pub fn set_transfer_gate(ctx: Context<SetTransferGate>, enabled: bool) -> Result<()> {
+    let collection_key = ctx.accounts.collection.key();
+    let gate_key = ctx.accounts.transfer_gate.key();
+    let is_new = ctx.accounts.transfer_gate.collection == Pubkey::default();
+
+    let gate = &mut ctx.accounts.transfer_gate;
+    gate.validation_version = TransferGate::VALIDATION_V1;
+    gate.create = TransferGate::RESULT_PASS;
+    gate.transfer = if enabled {
+        TransferGate::RESULT_REJECTED
+    } else {
+        TransferGate::RESULT_PASS
+    };
+    gate.burn = TransferGate::RESULT_PASS;
+    gate.update = TransferGate::RESULT_PASS;
+    gate.collection = collection_key;
+    gate.bump = ctx.bumps.transfer_gate;
+
+    if is_new {
+        let signer_seeds: &[&[&[u8]]] = &[&[
             SEED_REGISTRY_CONFIG,
             collection_key.as_ref(),
-            &[ctx.bumps.registry_config],
-        ]])?;
+            &[ctx.accounts.registry_config.bump],
+        ]];
+        AddCollectionExternalPluginAdapterV1CpiBuilder::new(
+            &ctx.accounts.mpl_core_program.to_account_info(),
+        )
+        .collection(&ctx.accounts.collection.to_account_info())
+        .payer(&ctx.accounts.authority.to_account_info())
+        .authority(Some(&ctx.accounts.registry_config.to_account_info()))
+        .system_program(&ctx.accounts.system_program.to_account_info())
+        .init_info(ExternalPluginAdapterInitInfo::Oracle(OracleInitInfo {
+            base_address: gate_key,
+            init_plugin_authority: Some(PluginAuthority::UpdateAuthority),
+            lifecycle_checks: vec![(
+                HookableLifecycleEvent::Transfer,
+                ExternalCheckResult { flags: ORACLE_CAN_REJECT },
+            )],
+            base_address_config: None,
+            results_offset: Some(ValidationResultsOffset::Anchor),
+        }))
+        .invoke_signed(signer_seeds)?;
+    }
 
-    emit!(RegistryInitialized {
+    emit!(TransferGateUpdated {
         collection: collection_key,
-        authority: ctx.accounts.authority.key(),
+        gate: gate_key,
+        enabled,
     });
 
-    msg!("Registry initialized with collection: {}", collection_key);
+    Ok(())
+}
+
+/// Set the allowed URI schemes (non-empty subset of ipfs://, ar://, https://)
+pub fn set_uri_schemes(ctx: Context<SetUriSchemes>, allowed_uri_schemes: u8) -> Result<()> {
+    require!(
+        allowed_uri_schemes != 0 && allowed_uri_schemes & !URI_SCHEMES_ALL == 0,
+        RegistryError::InvalidUriScheme
+    );
+
+    let registry = &mut ctx.accounts.registry_config;
+    registry.allowed_uri_schemes = allowed_uri_schemes;
+
+    emit!(UriSchemesUpdated {
+        collection: registry.collection,
+        allowed_uri_schemes,
+    });
 
     Ok(())
 }
 
-fn register_inner(
-    ctx: Context<Register>,
-    agent_uri: String,
-    atom_enabled: bool,
+/// Propose a new registry authority (None cancels a pending proposal)
+///
+/// The proposed key must call accept_registry_authority, which proves it can sign
+/// (wallet or PDA signing through CPI, e.g. a multisig vault) before control moves.
+pub fn set_registry_authority(
+    ctx: Context<SetRegistryAuthority>,
+    new_authority: Option<Pubkey>,
 ) -> Result<()> {
+    let registry = &mut ctx.accounts.registry_config;
+    registry.pending_authority = new_authority;
+
+    emit!(RegistryAuthorityProposed {
+        collection: registry.collection,
+        authority: registry.authority,
+        pending_authority: new_authority,
+    });
+
+    Ok(())
+}
+
+/// Accept a pending registry authority rotation
+pub fn accept_registry_authority(ctx: Context<AcceptRegistryAuthority>) -> Result<()> {
+    let registry = &mut ctx.accounts.registry_config;
+    let old_authority = registry.authority;
+    registry.authority = ctx.accounts.new_authority.key();
+    registry.pending_authority = None;
+
+    emit!(RegistryAuthorityTransferred {
+        collection: registry.collection,
+        old_authority,
+        new_authority: registry.authority,
+    });
+
+    msg!("Registry authority rotated: {} -> {}", old_authority, registry.authority);
+
+    Ok(())
+}
+
+/// Set the bounty paid to crankers that repair a stale cached owner
+pub fn set_sync_bounty(ctx: Context<SetSyncBounty>, sync_bounty: u64) -> Result<()> {
+    let registry = &mut ctx.accounts.registry_config;
+    registry.sync_bounty = sync_bounty;
+
+    emit!(SyncBountyUpdated {
+        collection: registry.collection,
+        sync_bounty,
+    });
+
+    Ok(())
+}
+
+/// Withdraw collected fees, keeping the treasury rent-exempt
+pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+    let treasury_info = ctx.accounts.treasury.to_account_info();
+    let min_balance = Rent::get()?.minimum_balance(treasury_info.data_len());
+    let remaining = treasury_info
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(RegistryError::InsufficientTreasuryBalance)?;
     require!(
-        agent_uri.len() <= AgentAccount::MAX_URI_LENGTH,
-        RegistryError::UriTooLong
+        remaining >= min_balance,
+        RegistryError::InsufficientTreasuryBalance
     );
 
-    let registry = &ctx.accounts.registry_config;
-    let asset = ctx.accounts.asset.key();
-    let collection_key = ctx.accounts.collection.key();
+    move_treasury_lamports(&treasury_info, &ctx.accounts.recipient.to_account_info(), amount)?;
 
-    // Create Core asset
-    create_core_asset_cpi(
-        &ctx.accounts.mpl_core_program.to_account_info(),
-        &ctx.accounts.asset.to_account_info(),
-        &ctx.accounts.collection.to_account_info(),
-        &ctx.accounts.owner.to_account_info(),
-        &ctx.accounts.owner.to_account_info(),
-        &registry.to_account_info(),
-        &ctx.accounts.system_program.to_account_info(),
-        "Agent".to_string(),
-        if agent_uri.is_empty() {
-            String::new()
-        } else {
-            agent_uri.clone()
-        },
-        &[&[
-            SEED_REGISTRY_CONFIG,
-            collection_key.as_ref(),
-            &[registry.bump],
-        ]],
-    )?;
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.total_withdrawn = treasury
+        .total_withdrawn
+        .checked_add(amount)
+        .ok_or(RegistryError::Overflow)?;
 
-    // Initialize agent account
-    let agent = &mut ctx.accounts.agent_account;
-    agent.collection = collection_key;
-    agent.creator = ctx.accounts.owner.key();
-    agent.owner = ctx.accounts.owner.key();
-    agent.asset = asset;
-    agent.bump = ctx.bumps.agent_account;
-    agent.atom_enabled = atom_enabled;
-    agent.agent_wallet = None;
-    agent.feedback_digest = [0u8; 32];
-    agent.feedback_count = 0;
-    agent.response_digest = [0u8; 32];
-    agent.response_count = 0;
-    agent.revoke_digest = [0u8; 32];
-    agent.revoke_count = 0;
-    agent.parent_asset = None;
-    agent.parent_locked = false;
-    agent.col_locked = false;
-    agent.agent_uri = agent_uri;
-    agent.nft_name = "Agent".to_string();
-    agent.col = String::new();
+    emit!(TreasuryWithdrawn {
+        collection: treasury.collection,
+        recipient: ctx.accounts.recipient.key(),
+        amount,
+    });
 
-    emit!(AgentRegistered {
-        asset,
-        collection: collection_key,
-        owner: ctx.accounts.owner.key(),
-        atom_enabled: agent.atom_enabled,
-        agent_uri: agent.agent_uri.clone(),
+    Ok(())
+}
+
+/// Enable or disable the Token-2022 registration backend
+pub fn set_token2022_backend(ctx: Context<SetToken2022Backend>, enabled: bool) -> Result<()> {
+    let registry = &mut ctx.accounts.registry_config;
+    registry.token2022_enabled = enabled;
+
+    emit!(Token2022BackendUpdated {
+        collection: registry.collection,
+        enabled,
     });
 
-    msg!("Agent registered: {} in collection {}", asset, collection_key);
+    Ok(())
+}
+
+/// Set the registration validity period for new registrations and renewals
+///
+/// A period of 0 disables expiry; existing valid_until values are left untouched.
+pub fn set_registration_period(
+    ctx: Context<SetRegistrationPeriod>,
+    registration_period: i64,
+) -> Result<()> {
+    require!(
+        registration_period >= 0,
+        RegistryError::InvalidRegistrationPeriod
+    );
+
+    let registry = &mut ctx.accounts.registry_config;
+    registry.registration_period = registration_period;
+
+    emit!(RegistrationPeriodUpdated {
+        collection: registry.collection,
+        registration_period,
+    });
 
     Ok(())
 }
 
-/// Register agent in the base collection
-pub fn register(ctx: Context<Register>, agent_uri: String) -> Result<()> {
-    register_inner(ctx, agent_uri, true)
+/// Renew an agent registration by one registration period
+///
+/// Renewal extends from the current expiry if still valid, otherwise from now.
+pub fn renew_agent(ctx: Context<RenewAgent>) -> Result<()> {
+    verify_core_owner(&ctx.accounts.asset, &ctx.accounts.owner.key())?;
+
+    let period = ctx.accounts.registry_config.registration_period;
+    require!(period > 0, RegistryError::RegistrationExpiryDisabled);
+
+    let agent = &mut ctx.accounts.agent_account;
+    agent.valid_until = registration_expiry(period, agent.valid_until)?;
+
+    emit!(AgentRenewed {
+        asset: agent.asset,
+        valid_until: agent.valid_until,
+        renewed_by: ctx.accounts.owner.key(),
+    });
+
+    msg!("Agent {} renewed until {}", agent.asset, agent.valid_until);
+
+    Ok(())
 }
 
-/// Register agent with explicit ATOM setting (default is true)
-pub fn register_with_options(
-    ctx: Context<Register>,
-    agent_uri: String,
-    atom_enabled: bool,
-) -> Result<()> {
-    register_inner(ctx, agent_uri, atom_enabled)
+/// Check whether an agent registration has expired (false when expiry is disabled)
+pub fn is_agent_expired(ctx: Context<IsAgentExpired>) -> Result<bool> {
+    let now = Clock::get()?.unix_timestamp;
+    Ok(ctx.accounts.agent_account.is_expired(now))
 }
 
 /// Enable ATOM for an agent (one-way)
@@ -1,6 +1,10 @@
+pub mod allowlist;
+pub mod card;
+pub mod council;
 pub mod contexts;
 pub mod events;
 pub mod instructions;
+pub mod schema;
 pub mod state;
 
 pub use contexts::*;
@@ -0,0 +1,142 @@
+//! Well-known metadata key schemas.
+//!
+//! Reserved keys have a fixed value format that `set_metadata_pda` enforces, so
+//! indexers can rely on their structure. Any other key remains free-form.
+//!
+//! | Key            | Value format                                   |
+//! |----------------|------------------------------------------------|
+//! | `a2a.endpoint` | UTF-8 `https://` URL                           |
+//! | `mcp.endpoint` | UTF-8 `https://` URL                           |
+//! | `x402.wallet`  | Raw 32-byte Solana pubkey                      |
+//! | `model.card`   | UTF-8 URI (`ipfs://`, `ar://` or `https://`)   |
+
+use anchor_lang::prelude::*;
+
+use crate::error::RegistryError;
+
+pub const KEY_A2A_ENDPOINT: &str = "a2a.endpoint";
+pub const KEY_MCP_ENDPOINT: &str = "mcp.endpoint";
+pub const KEY_X402_WALLET: &str = "x402.wallet";
+pub const KEY_MODEL_CARD: &str = "model.card";
+
+/// URI scheme bits for RegistryConfig.allowed_uri_schemes
+pub const URI_SCHEME_IPFS: u8 = 1 << 0;
+pub const URI_SCHEME_AR: u8 = 1 << 1;
+pub const URI_SCHEME_HTTPS: u8 = 1 << 2;
+pub const URI_SCHEMES_ALL: u8 = URI_SCHEME_IPFS | URI_SCHEME_AR | URI_SCHEME_HTTPS;
+
+/// Maximum category tag length in bytes
+pub const MAX_CATEGORY_TAG_LENGTH: usize = 32;
+
+const URI_SCHEME_PREFIXES: &[(u8, &str)] = &[
+    (URI_SCHEME_IPFS, "ipfs://"),
+    (URI_SCHEME_AR, "ar://"),
+    (URI_SCHEME_HTTPS, "https://"),
+];
+
+const ENDPOINT_SCHEMES: &[&str] = &["https://"];
+const CONTENT_URI_SCHEMES: &[&str] = &["ipfs://", "ar://", "https://"];
+
+/// Validate `value` against the schema of a well-known key.
+/// Keys without a schema are accepted unchanged.
+pub fn validate_well_known_value(key: &str, value: &[u8]) -> Result<()> {
+    let valid = match key {
+        KEY_A2A_ENDPOINT | KEY_MCP_ENDPOINT => is_uri_with_scheme(value, ENDPOINT_SCHEMES),
+        KEY_X402_WALLET => value.len() == 32,
+        KEY_MODEL_CARD => is_uri_with_scheme(value, CONTENT_URI_SCHEMES),
+        _ => true,
+    };
+    require!(valid, RegistryError::InvalidMetadataValue);
+    Ok(())
+}
+
+/// Validate an agent/feedback/response URI against a registry scheme allowlist.
+/// Empty URIs are allowed (no off-chain document).
+pub fn validate_uri_scheme(uri: &str, allowed_schemes: u8) -> Result<()> {
+    if uri.is_empty() {
+        return Ok(());
+    }
+    let valid = URI_SCHEME_PREFIXES.iter().any(|(bit, prefix)| {
+        allowed_schemes & bit != 0 && is_uri_with_scheme(uri.as_bytes(), &[prefix])
+    });
+    require!(valid, RegistryError::InvalidUriScheme);
+    Ok(())
+}
+
+/// Category tags: 1-32 chars of lowercase ASCII letters, digits or '-'
+/// (canonical form so equal categories hash identically).
+pub fn is_valid_category_tag(tag: &str) -> bool {
+    (1..=MAX_CATEGORY_TAG_LENGTH).contains(&tag.len())
+        && tag
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-')
+}
+
+/// UTF-8, printable ASCII only, one of `schemes` followed by a non-empty remainder.
+fn is_uri_with_scheme(value: &[u8], schemes: &[&str]) -> bool {
+    let Ok(uri) = core::str::from_utf8(value) else {
+        return false;
+    };
+    if !uri.bytes().all(|b| b.is_ascii_graphic()) {
+        return false;
+    }
+    schemes
+        .iter()
+        .any(|scheme| uri.len() > scheme.len() && uri.starts_with(scheme))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endpoint_keys_require_https() {
+        assert!(validate_well_known_value(KEY_A2A_ENDPOINT, b"https://agent.example/a2a").is_ok());
+        assert!(validate_well_known_value(KEY_MCP_ENDPOINT, b"https://agent.example/mcp").is_ok());
+        assert!(validate_well_known_value(KEY_A2A_ENDPOINT, b"http://agent.example").is_err());
+        assert!(validate_well_known_value(KEY_MCP_ENDPOINT, b"https://").is_err());
+        assert!(validate_well_known_value(KEY_MCP_ENDPOINT, b"https://a b").is_err());
+    }
+
+    #[test]
+    fn test_x402_wallet_requires_raw_pubkey() {
+        assert!(validate_well_known_value(KEY_X402_WALLET, &[7u8; 32]).is_ok());
+        assert!(validate_well_known_value(KEY_X402_WALLET, &[7u8; 31]).is_err());
+        assert!(validate_well_known_value(KEY_X402_WALLET, b"").is_err());
+    }
+
+    #[test]
+    fn test_model_card_schemes() {
+        assert!(validate_well_known_value(KEY_MODEL_CARD, b"ipfs://QmCard").is_ok());
+        assert!(validate_well_known_value(KEY_MODEL_CARD, b"ar://abc").is_ok());
+        assert!(validate_well_known_value(KEY_MODEL_CARD, b"javascript:alert(1)").is_err());
+        assert!(validate_well_known_value(KEY_MODEL_CARD, &[0xff, 0xfe]).is_err());
+    }
+
+    #[test]
+    fn test_uri_scheme_allowlist() {
+        assert!(validate_uri_scheme("", URI_SCHEME_IPFS).is_ok());
+        assert!(validate_uri_scheme("ipfs://QmAgent", URI_SCHEMES_ALL).is_ok());
+        assert!(validate_uri_scheme("https://agent.example/card.json", URI_SCHEMES_ALL).is_ok());
+        assert!(validate_uri_scheme("https://agent.example", URI_SCHEME_IPFS).is_err());
+        assert!(validate_uri_scheme("javascript:alert(1)", URI_SCHEMES_ALL).is_err());
+        assert!(validate_uri_scheme("data:text/html,hi", URI_SCHEMES_ALL).is_err());
+        assert!(validate_uri_scheme("ar://", URI_SCHEMES_ALL).is_err());
+        assert!(validate_uri_scheme("ipfs://Qm Agent", URI_SCHEMES_ALL).is_err());
+    }
+
+    #[test]
+    fn test_category_tags_are_canonical() {
+        assert!(is_valid_category_tag("trading"));
+        assert!(is_valid_category_tag("image-gen-2"));
+        assert!(!is_valid_category_tag(""));
+        assert!(!is_valid_category_tag("Trading"));
+        assert!(!is_valid_category_tag("image gen"));
+        assert!(!is_valid_category_tag(&"a".repeat(33)));
+    }
+
+    #[test]
+    fn test_unknown_keys_are_free_form() {
+        assert!(validate_well_known_value("custom", &[0u8, 1, 2]).is_ok());
+    }
+}
@@ -1,4 +1,10 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+
+use crate::error::RegistryError;
+use crate::identity::schema::URI_SCHEMES_ALL;
+use crate::reputation::sketch::{score_bucket, score_quantile, SKETCH_BUCKETS};
+use crate::reputation::state::ReputationConfig;
 
 // ============================================================================
 // Single Collection Architecture (v0.6.0)
@@ -18,6 +24,76 @@ pub struct RootConfig {
 
     /// PDA bump seed
     pub bump: u8,
+
+    /// Next registry id (each registry allocates agent ids in its own range)
+    pub next_registry_id: u64,
+}
+
+/// RootConfig layout of v0.6.0 deployments (no next_registry_id)
+/// Decoded by migrate_root_config only.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct RootConfigV0 {
+    pub base_collection: Pubkey,
+    pub authority: Pubkey,
+    pub bump: u8,
+}
+
+impl RootConfigV0 {
+    /// Account size of a v0 root config (discriminator included)
+    pub const SPACE: usize = RootConfig::DISCRIMINATOR.len() + Self::INIT_SPACE;
+
+    /// Upgrade to the current layout; the v0.6.0 base registry keeps registry id 0
+    pub fn into_current(self) -> RootConfig {
+        RootConfig {
+            base_collection: self.base_collection,
+            authority: self.authority,
+            bump: self.bump,
+            next_registry_id: 1,
+        }
+    }
+}
+
+/// Deployment capability flags (one per program deployment)
+/// Seeds: ["registry_features"]
+///
+/// Lets SDKs and other programs detect enabled subsystems at runtime instead of
+/// hardcoding per-cluster assumptions. Maintained by the root authority.
+#[account]
+#[derive(InitSpace)]
+pub struct RegistryFeatures {
+    /// Feature set version (bumped by the authority when the meaning of bits changes)
+    pub version: u16,
+
+    /// Enabled subsystems (FEATURE_* bits; upper 32 bits free for future use)
+    pub features: u64,
+
+    /// Last update (unix timestamp)
+    pub updated_at: i64,
+
+    pub bump: u8,
+}
+
+impl RegistryFeatures {
+    /// ATOM engine integration (stats init, feedback CPI, sync_reputation)
+    pub const FEATURE_ATOM: u64 = 1 << 0;
+    /// Registration fees and treasury
+    pub const FEATURE_FEES: u64 = 1 << 1;
+    /// Organization-operated user registries (sub-registries)
+    pub const FEATURE_USER_REGISTRIES: u64 = 1 << 2;
+    /// Soulbound (non-transferable) registration
+    pub const FEATURE_SOULBOUND: u64 = 1 << 3;
+    /// Token-2022 backed agents
+    pub const FEATURE_TOKEN2022: u64 = 1 << 4;
+    /// Compact (URI-hash-only) registration
+    pub const FEATURE_COMPACT: u64 = 1 << 5;
+    /// Registration bonds
+    pub const FEATURE_BONDS: u64 = 1 << 6;
+    /// Registration referrals
+    pub const FEATURE_REFERRALS: u64 = 1 << 7;
+    /// Owner councils (m-of-n)
+    pub const FEATURE_COUNCILS: u64 = 1 << 8;
+    /// Attestor verifications
+    pub const FEATURE_ATTESTORS: u64 = 1 << 9;
 }
 
 /// Registry configuration for the base collection
@@ -33,11 +109,176 @@ pub struct RegistryConfig {
 
     /// PDA bump seed
     pub bump: u8,
+
+    /// Registration validity period in seconds (0 = registrations never expire)
+    pub registration_period: i64,
+
+    /// Allow register_token2022 (Token-2022 NFT instead of Metaplex Core asset)
+    pub token2022_enabled: bool,
+
+    /// Registration fee in lamports collected into the treasury (0 = free)
+    pub registration_fee: u64,
+
+    /// Registry kind (REGISTRY_TYPE_BASE or REGISTRY_TYPE_USER)
+    pub registry_type: u8,
+
+    /// Maximum metadata chunks per key (caps chunked payloads at max * 1024 bytes)
+    pub max_metadata_chunks: u16,
+
+    /// Lamports paid from the treasury to crankers that repair a stale owner (0 = off)
+    pub sync_bounty: u64,
+
+    /// Allowed URI schemes for agent/feedback/response URIs (schema::URI_SCHEME_* bits)
+    pub allowed_uri_schemes: u8,
+
+    /// Proposed authority awaiting accept_registry_authority (two-step rotation)
+    pub pending_authority: Option<Pubkey>,
+
+    /// Refundable bond in lamports locked in each new AgentAccount (0 = off)
+    pub registration_bond: u64,
+
+    /// Seconds after registration before deregister may return the bond
+    pub bond_cooldown: i64,
+
+    /// Merkle root of allowlisted owner wallets (zero = permissionless registration)
+    pub allowlist_root: [u8; 32],
+
+    /// Share of the registration fee paid to the referrer, in basis points (0 = none)
+    pub referral_share_bps: u16,
+
+    /// Secondary-sale royalty enforced by the registry, in basis points (0 = owner-managed)
+    pub royalty_bps: u16,
+
+    /// Royalty recipient (ROYALTY_ROUTE_*)
+    pub royalty_route: u8,
+
+    /// Seconds after an agent's last feedback during which it cannot deregister (0 = off)
+    pub dispute_window: i64,
+
+    /// How give_feedback assigns feedback_index (FEEDBACK_INDEX_*)
+    pub feedback_index_mode: u8,
+
+    /// SPL / Token-2022 mint accepted by give_feedback_paid (default = paid feedback off)
+    pub feedback_payment_mint: Pubkey,
+
+    /// Minimum payment (base units of feedback_payment_mint) for paid feedback
+    pub min_feedback_payment: u64,
+
+    /// Test-environment escape hatch: skip self-feedback checks (owner, wallet, operators)
+    pub allow_self_feedback: bool,
+
+    /// Minimum slots between two feedbacks of the same client on the same agent (0 = off)
+    pub feedback_cooldown_slots: u64,
+
+    /// Lamports a client bonds per feedback (0 = off)
+    pub feedback_bond: u64,
+
+    /// Seconds during which the agent owner may dispute a bonded feedback
+    pub feedback_bond_window: i64,
+
+    /// Reject feedback tags that are not registered CanonicalTags
+    pub strict_feedback_tags: bool,
+
+    /// Seconds before a committed feedback can be revealed without the owner's consent
+    pub feedback_reveal_delay: i64,
+
+    /// Registry number allocated by RootConfig; agent ids are `registry_id << 32 | n`
+    pub registry_id: u64,
+
+    /// Next agent id allocated in this registry
+    pub next_agent_id: u64,
+
+    /// Live agents in this registry (registered or migrated, minus deregistered)
+    pub total_agents: u64,
+}
+
+impl RegistryConfig {
+    /// Protocol-operated registry (authority = root authority)
+    pub const REGISTRY_TYPE_BASE: u8 = 0;
+
+    /// Organization-operated registry (authority = creator)
+    pub const REGISTRY_TYPE_USER: u8 = 1;
+
+    /// Royalties paid to the registry treasury (collection-level Core plugin)
+    pub const ROYALTY_ROUTE_TREASURY: u8 = 0;
+    /// Royalties paid to each agent's original creator (asset-level plugin, applied by
+    /// apply_creator_royalty; the treasury receives them until then)
+    pub const ROYALTY_ROUTE_CREATOR: u8 = 1;
+
+    /// feedback_index is the agent-wide sequence (agent_account.feedback_count)
+    pub const FEEDBACK_INDEX_GLOBAL: u8 = 0;
+    /// feedback_index is the client's own sequence (ClientIndex PDA), as in ERC-8004
+    pub const FEEDBACK_INDEX_PER_CLIENT: u8 = 1;
+
+    /// Maximum registry (collection) name length in bytes
+    pub const MAX_NAME_LENGTH: usize = 32;
+
+    /// Default chunk cap for new registries (16 KiB per key)
+    pub const DEFAULT_MAX_METADATA_CHUNKS: u16 = 16;
+
+    /// Low bits of an agent id holding the per-registry sequence
+    pub const AGENT_ID_SEQUENCE_BITS: u32 = 32;
+
+    /// First agent id of a registry's range
+    pub fn first_agent_id(registry_id: u64) -> u64 {
+        registry_id << Self::AGENT_ID_SEQUENCE_BITS
+    }
+}
+
+/// RegistryConfig layout of v0.6.0 deployments (collection, authority, bump)
+/// Decoded by migrate_registry_config only.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct RegistryConfigV0 {
+    pub collection: Pubkey,
+    pub authority: Pubkey,
+    pub bump: u8,
+}
+
+impl RegistryConfigV0 {
+    /// Account size of a v0 registry config (discriminator included)
+    pub const SPACE: usize = RegistryConfig::DISCRIMINATOR.len() + Self::INIT_SPACE;
+
+    /// Upgrade to the current layout; v0.6.0 only had the base registry, and fields added
+    /// since take the defaults of a newly created registry
+    pub fn into_current(self) -> RegistryConfig {
+        RegistryConfig {
+            collection: self.collection,
+            authority: self.authority,
+            bump: self.bump,
+            registration_period: 0,
+            token2022_enabled: false,
+            registration_fee: 0,
+            registry_type: RegistryConfig::REGISTRY_TYPE_BASE,
+            max_metadata_chunks: RegistryConfig::DEFAULT_MAX_METADATA_CHUNKS,
+            sync_bounty: 0,
+            allowed_uri_schemes: URI_SCHEMES_ALL,
+            pending_authority: None,
+            registration_bond: 0,
+            bond_cooldown: 0,
+            allowlist_root: [0u8; 32],
+            referral_share_bps: 0,
+            royalty_bps: 0,
+            royalty_route: RegistryConfig::ROYALTY_ROUTE_TREASURY,
+            dispute_window: 0,
+            feedback_index_mode: RegistryConfig::FEEDBACK_INDEX_GLOBAL,
+            feedback_payment_mint: Pubkey::default(),
+            min_feedback_payment: 0,
+            allow_self_feedback: false,
+            feedback_cooldown_slots: 0,
+            feedback_bond: 0,
+            feedback_bond_window: 0,
+            strict_feedback_tags: false,
+            feedback_reveal_delay: 0,
+            registry_id: 0,
+            next_agent_id: 0,
+            total_agents: 0,
+        }
+    }
 }
 
 /// Agent account (represents an AI agent identity)
 /// Seeds: [b"agent", asset.key()]
-/// EVM conformity: asset = unique identifier (no sequential agent_id)
+/// EVM conformity: asset = unique identifier, agent_id = numeric id (see AgentIdIndex)
 /// Keeps nft_name to avoid extra Metaplex RPC calls
 #[account]
 #[derive(InitSpace)]
@@ -82,6 +323,102 @@ pub struct AgentAccount {
     /// Collection pointer lock (once true, collection pointer cannot be modified)
     pub col_locked: bool,
 
+    /// Frozen by registry authority (blocks URI/metadata updates and new feedback)
+    pub frozen: bool,
+
+    /// Registration expiry timestamp (0 = never expires)
+    pub valid_until: i64,
+
+    /// Identity backed by a Token-2022 NFT mint instead of a Metaplex Core asset
+    /// (`asset` is then the mint address)
+    pub token_backed: bool,
+
+    /// keccak256 of the committed off-chain agent card (zero = no commitment)
+    /// Cleared when agent_uri changes
+    pub card_hash: [u8; 32],
+
+    /// Merkle root over card chunks for chunked verification (zero = full card only)
+    pub card_chunk_root: [u8; 32],
+
+    /// Capability bitmask (CAP_* bits; upper 32 bits free for ecosystem use)
+    /// Fixed offset for memcmp filtering without fetching metadata PDAs
+    pub capabilities: u64,
+
+    /// Non-transferable agent (Core PermanentFreezeDelegate installed at registration)
+    pub soulbound: bool,
+
+    /// Numeric agent id, sequential within its registry (resolvable via AgentIdIndex)
+    pub agent_id: u64,
+
+    /// Slot at which the cached owner was last written or confirmed
+    pub owner_synced_slot: u64,
+
+    /// Refundable anti-spam bond held in this account's lamports (0 = none)
+    pub bond_amount: u64,
+
+    /// Unix timestamp after which deregister may return the bond
+    pub bond_unlocks_at: i64,
+
+    /// Account layout version (AgentAccount::SCHEMA_VERSION; legacy accounts predate
+    /// the field and are upgraded with migrate_agent)
+    pub schema_version: u8,
+
+    /// Lifecycle status (STATUS_*): set by the owner, SUSPENDED only by the authority
+    pub status: u8,
+
+    /// ATOM trust tier mirrored by sync_reputation (0 = unrated / never synced)
+    pub trust_tier: u8,
+
+    /// ATOM feedback count mirrored by sync_reputation
+    pub atom_feedback_count: u64,
+
+    /// Slot of the last sync_reputation (0 = never)
+    pub reputation_synced_slot: u64,
+
+    /// keccak256 of the current agent URI (zero when unset)
+    pub uri_hash: [u8; 32],
+
+    /// Rent-optimized agent: agent_uri is not stored (events + uri_hash only) and the
+    /// account is allocated with COMPACT_SPACE
+    pub compact: bool,
+
+    /// Unix timestamp of the most recent feedback (0 = none); starts the dispute window
+    pub last_feedback_at: i64,
+
+    /// Ethereum address of the bridged owner (zero = native Solana agent)
+    /// Set by register_for_evm_owner after a secp256k1 proof.
+    pub evm_owner: [u8; 20],
+
+    /// Content-type hint for agent_uri (URI_CONTENT_*; 0 = unknown)
+    pub uri_content_type: u8,
+
+    /// Size hint for the agent_uri document in bytes (0 = unknown)
+    pub uri_content_length: u32,
+
+    /// New feedback must carry an ERC-8004 FeedbackAuth (give_feedback_with_auth)
+    pub feedback_auth_required: bool,
+
+    /// Sum of scores of counted scored feedback (naive average numerator)
+    pub score_sum: u64,
+
+    /// Scored feedback currently counted in score_sum
+    pub scored_count: u64,
+
+    /// Time-decayed score sum (scores x DECAY_WEIGHT_SCALE, halved per decay half-life)
+    pub decayed_score_sum: u64,
+
+    /// Time-decayed weight matching decayed_score_sum (DECAY_WEIGHT_SCALE per feedback)
+    pub decayed_weight: u64,
+
+    /// Start of the current decay period (see ReputationConfig.decay_half_life)
+    pub decayed_at: i64,
+
+    /// Halvings applied to the decayed pair so far (FeedbackScore keeps the value at scoring)
+    pub decay_halvings: u64,
+
+    /// Counted scores per sketch bucket (median / p90, see reputation::sketch)
+    pub score_histogram: [u32; SKETCH_BUCKETS],
+
     // === Dynamic-size fields last ===
 
     /// Agent URI (IPFS/Arweave/HTTP link, max 250 bytes)
@@ -96,6 +433,10 @@ pub struct AgentAccount {
     /// Canonical collection pointer: c1:<cid_norm>
     #[max_len(128)]
     pub col: String,
+
+    /// Display symbol (registry-side only: Core assets have no symbol field)
+    #[max_len(10)]
+    pub nft_symbol: String,
 }
 
 impl AgentAccount {
@@ -105,6 +446,272 @@ impl AgentAccount {
 
     /// Maximum collection pointer length in bytes (c1:<cid_norm>)
     pub const MAX_COL_LENGTH: usize = 128;
+
+    /// Maximum agent (NFT) name length in bytes
+    pub const MAX_NAME_LENGTH: usize = 32;
+
+    /// Maximum agent symbol length in bytes
+    pub const MAX_SYMBOL_LENGTH: usize = 10;
+
+    /// Account size of a compact agent (no room reserved for agent_uri content)
+    pub const COMPACT_SPACE: usize =
+        Self::DISCRIMINATOR.len() + Self::INIT_SPACE - Self::MAX_URI_LENGTH;
+
+    /// Fixed-point weight of one feedback in the decayed average (keeps precision
+    /// across halvings)
+    pub const DECAY_WEIGHT_SCALE: u64 = 1 << 16;
+
+    /// Current account layout version
+    /// Bump when appending fields: freeze the released layout as AgentAccountV<n> and add
+    /// a LegacyAgentAccount variant for it.
+    pub const SCHEMA_VERSION: u8 = 1;
+
+    /// Status: operating normally
+    pub const STATUS_ACTIVE: u8 = 0;
+    /// Status: temporarily not operating (still accepts feedback)
+    pub const STATUS_PAUSED: u8 = 1;
+    /// Status: permanently retired by the owner (terminal, no new feedback)
+    pub const STATUS_RETIRED: u8 = 2;
+    /// Status: suspended by the registry authority (no new feedback)
+    pub const STATUS_SUSPENDED: u8 = 3;
+
+    /// URI content type: not declared
+    pub const URI_CONTENT_UNKNOWN: u8 = 0;
+    /// URI content type: application/json
+    pub const URI_CONTENT_JSON: u8 = 1;
+    /// URI content type: application/ld+json
+    pub const URI_CONTENT_JSON_LD: u8 = 2;
+    /// URI content type: anything else (clients should not parse as an agent card)
+    pub const URI_CONTENT_OTHER: u8 = 3;
+
+    /// Capability: speaks A2A (Agent2Agent protocol)
+    pub const CAP_A2A: u64 = 1 << 0;
+    /// Capability: exposes an MCP server
+    pub const CAP_MCP: u64 = 1 << 1;
+    /// Capability: accepts x402 payments
+    pub const CAP_X402: u64 = 1 << 2;
+    /// Capability: acts as a validator for other agents
+    pub const CAP_VALIDATOR: u64 = 1 << 3;
+    /// Capability: supports OASF skill/domain descriptors
+    pub const CAP_OASF: u64 = 1 << 4;
+
+    /// Standardized bits currently defined
+    pub const CAP_KNOWN: u64 =
+        Self::CAP_A2A | Self::CAP_MCP | Self::CAP_X402 | Self::CAP_VALIDATOR | Self::CAP_OASF;
+    /// Lower half reserved for standardized bits
+    pub const CAP_STANDARD_MASK: u64 = 0xFFFF_FFFF;
+
+    /// Whether new feedback may be given in the current status
+    pub fn accepts_feedback(&self) -> bool {
+        self.status == Self::STATUS_ACTIVE || self.status == Self::STATUS_PAUSED
+    }
+
+    /// keccak256 of an agent URI, zero for an empty URI (matches the unset convention)
+    pub fn compute_uri_hash(uri: &str) -> [u8; 32] {
+        if uri.is_empty() {
+            [0u8; 32]
+        } else {
+            keccak::hash(uri.as_bytes()).0
+        }
+    }
+
+    /// Count a feedback score in the naive and time-decayed averages and the quantile sketch
+    pub fn record_score(&mut self, score: u8, now: i64) {
+        self.score_sum = self.score_sum.saturating_add(score as u64);
+        self.scored_count = self.scored_count.saturating_add(1);
+        if self.decayed_weight == 0 {
+            self.decayed_at = now;
+        }
+        self.decayed_score_sum = self
+            .decayed_score_sum
+            .saturating_add(score as u64 * Self::DECAY_WEIGHT_SCALE);
+        self.decayed_weight = self.decayed_weight.saturating_add(Self::DECAY_WEIGHT_SCALE);
+        let bucket = &mut self.score_histogram[score_bucket(score)];
+        *bucket = bucket.saturating_add(1);
+    }
+
+    /// Remove a revoked feedback score from both averages and the quantile sketch
+    ///
+    /// `recorded_halvings` is decay_halvings when the score was recorded; the decayed pair
+    /// loses the weight the score has left after the halvings applied since then.
+    pub fn remove_score(&mut self, score: u8, recorded_halvings: u64) {
+        self.score_sum = self.score_sum.saturating_sub(score as u64);
+        self.scored_count = self.scored_count.saturating_sub(1);
+        let weight = u32::try_from(self.decay_halvings.saturating_sub(recorded_halvings))
+            .ok()
+            .and_then(|shift| Self::DECAY_WEIGHT_SCALE.checked_shr(shift))
+            .unwrap_or(0);
+        self.decayed_score_sum = self.decayed_score_sum.saturating_sub(score as u64 * weight);
+        self.decayed_weight = self.decayed_weight.saturating_sub(weight);
+        let bucket = &mut self.score_histogram[score_bucket(score)];
+        *bucket = bucket.saturating_sub(1);
+    }
+
+    /// Halve the decayed pair `halvings` times (decay_agent_reputation)
+    pub fn decay_scores(&mut self, halvings: u64) {
+        let shift = halvings.min(ReputationConfig::MAX_HALVINGS as u64) as u32;
+        self.decayed_score_sum >>= shift;
+        self.decayed_weight >>= shift;
+        self.decay_halvings = self.decay_halvings.saturating_add(halvings);
+    }
+
+    /// score_sum / scored_count (0 when no scored feedback)
+    pub fn average_score(&self) -> u8 {
+        self.score_sum.checked_div(self.scored_count).unwrap_or(0) as u8
+    }
+
+    /// Approximate `pct`-th percentile of counted scores (0 when no scored feedback)
+    pub fn score_quantile(&self, pct: u8) -> u8 {
+        score_quantile(&self.score_histogram, pct)
+    }
+
+    /// decayed_score_sum / decayed_weight (0 when fully decayed or no scored feedback)
+    pub fn decayed_average_score(&self) -> u8 {
+        self.decayed_score_sum
+            .checked_div(self.decayed_weight)
+            .unwrap_or(0)
+            .min(100) as u8
+    }
+
+    /// Whether the registration has lapsed at `now` (never true when valid_until = 0)
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.valid_until != 0 && now > self.valid_until
+    }
+}
+
+/// AgentAccount layout of v0.6.0 deployments (schema version 0, no version field)
+/// Decoded by migrate_agent only.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct AgentAccountV0 {
+    pub collection: Pubkey,
+    pub creator: Pubkey,
+    pub owner: Pubkey,
+    pub asset: Pubkey,
+    pub bump: u8,
+    pub atom_enabled: bool,
+    pub agent_wallet: Option<Pubkey>,
+    pub feedback_digest: [u8; 32],
+    pub feedback_count: u64,
+    pub response_digest: [u8; 32],
+    pub response_count: u64,
+    pub revoke_digest: [u8; 32],
+    pub revoke_count: u64,
+    pub parent_asset: Option<Pubkey>,
+    pub parent_locked: bool,
+    pub col_locked: bool,
+    #[max_len(250)]
+    pub agent_uri: String,
+    #[max_len(32)]
+    pub nft_name: String,
+    #[max_len(128)]
+    pub col: String,
+}
+
+impl AgentAccountV0 {
+    /// Account size of a v0 agent (discriminator included)
+    pub const SPACE: usize = AgentAccount::DISCRIMINATOR.len() + Self::INIT_SPACE;
+
+    /// Upgrade to the current layout; fields added since v0 take their defaults
+    pub fn into_current(self, agent_id: u64) -> AgentAccount {
+        AgentAccount {
+            collection: self.collection,
+            creator: self.creator,
+            owner: self.owner,
+            asset: self.asset,
+            bump: self.bump,
+            atom_enabled: self.atom_enabled,
+            agent_wallet: self.agent_wallet,
+            feedback_digest: self.feedback_digest,
+            feedback_count: self.feedback_count,
+            response_digest: self.response_digest,
+            response_count: self.response_count,
+            revoke_digest: self.revoke_digest,
+            revoke_count: self.revoke_count,
+            parent_asset: self.parent_asset,
+            parent_locked: self.parent_locked,
+            col_locked: self.col_locked,
+            frozen: false,
+            valid_until: 0,
+            token_backed: false,
+            card_hash: [0u8; 32],
+            card_chunk_root: [0u8; 32],
+            capabilities: 0,
+            soulbound: false,
+            agent_id,
+            // Unknown sync point: the cached owner is treated as unconfirmed
+            owner_synced_slot: 0,
+            bond_amount: 0,
+            bond_unlocks_at: 0,
+            schema_version: AgentAccount::SCHEMA_VERSION,
+            status: AgentAccount::STATUS_ACTIVE,
+            trust_tier: 0,
+            atom_feedback_count: 0,
+            reputation_synced_slot: 0,
+            uri_hash: AgentAccount::compute_uri_hash(&self.agent_uri),
+            compact: false,
+            last_feedback_at: 0,
+            evm_owner: [0u8; 20],
+            uri_content_type: AgentAccount::URI_CONTENT_UNKNOWN,
+            uri_content_length: 0,
+            feedback_auth_required: false,
+            score_sum: 0,
+            scored_count: 0,
+            decayed_score_sum: 0,
+            decayed_weight: 0,
+            decayed_at: 0,
+            decay_halvings: 0,
+            score_histogram: [0; SKETCH_BUCKETS],
+            agent_uri: self.agent_uri,
+            nft_name: self.nft_name,
+            col: self.col,
+            nft_symbol: String::new(),
+        }
+    }
+}
+
+/// AgentAccount in a released layout older than SCHEMA_VERSION (one variant per layout)
+pub enum LegacyAgentAccount {
+    V0(AgentAccountV0),
+}
+
+impl LegacyAgentAccount {
+    /// Decode legacy account data (discriminator included); the layout is identified by size
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        require!(
+            data.len() >= 8 && data[..8] == *AgentAccount::DISCRIMINATOR,
+            RegistryError::UnsupportedAgentLayout
+        );
+        match data.len() {
+            AgentAccountV0::SPACE => Ok(Self::V0(AgentAccountV0::deserialize(&mut &data[8..])?)),
+            _ => err!(RegistryError::UnsupportedAgentLayout),
+        }
+    }
+
+    /// Schema version of the decoded layout
+    pub fn version(&self) -> u8 {
+        match self {
+            Self::V0(_) => 0,
+        }
+    }
+
+    pub fn asset(&self) -> Pubkey {
+        match self {
+            Self::V0(agent) => agent.asset,
+        }
+    }
+
+    pub fn collection(&self) -> Pubkey {
+        match self {
+            Self::V0(agent) => agent.collection,
+        }
+    }
+
+    /// Upgrade to the current layout through each later version's defaults
+    pub fn into_current(self, agent_id: u64) -> AgentAccount {
+        match self {
+            Self::V0(agent) => agent.into_current(agent_id),
+        }
+    }
 }
 
 /// Individual metadata entry stored as separate PDA
@@ -134,13 +741,801 @@ pub struct MetadataEntryPda {
     /// Metadata value (max 250 bytes, arbitrary binary data)
     #[max_len(250)]
     pub metadata_value: Vec<u8>,
+
+    /// Unix timestamp after which an immutable entry unlocks (0 = never)
+    /// Appended after dynamic fields to keep fixed offsets of existing entries; v0.6.0
+    /// entries are upgraded with migrate_metadata_entry before they can be changed
+    pub expires_at: i64,
+
+    /// Key that answered the endpoint challenge for the current value
+    /// (default = unverified, reset whenever the value is rewritten)
+    pub verified_key: Pubkey,
 }
 
 impl MetadataEntryPda {
+    /// Account size of a current-layout entry (discriminator included)
+    pub const SPACE: usize = Self::DISCRIMINATOR.len() + Self::INIT_SPACE;
+
     /// Maximum key length in bytes (used for validation)
     pub const MAX_KEY_LENGTH: usize = 32;
 
     /// Maximum value length in bytes (used for validation)
     pub const MAX_VALUE_LENGTH: usize = 250;
+
+    /// True while the entry cannot be replaced or deleted
+    pub fn is_locked(&self, now: i64) -> bool {
+        self.immutable && (self.expires_at == 0 || now < self.expires_at)
+    }
+}
+
+/// MetadataEntryPda layout of v0.6.0 deployments (no expires_at / verified_key)
+/// Decoded by migrate_metadata_entry only.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct MetadataEntryPdaV0 {
+    pub asset: Pubkey,
+    pub immutable: bool,
+    pub bump: u8,
+    #[max_len(32)]
+    pub metadata_key: String,
+    #[max_len(250)]
+    pub metadata_value: Vec<u8>,
+}
+
+impl MetadataEntryPdaV0 {
+    /// Account size of a v0 entry (discriminator included)
+    pub const SPACE: usize = MetadataEntryPda::DISCRIMINATOR.len() + Self::INIT_SPACE;
+
+    /// Upgrade to the current layout: no expiry (immutable stays permanent), unverified
+    pub fn into_current(self) -> MetadataEntryPda {
+        MetadataEntryPda {
+            asset: self.asset,
+            immutable: self.immutable,
+            bump: self.bump,
+            metadata_key: self.metadata_key,
+            metadata_value: self.metadata_value,
+            expires_at: 0,
+            verified_key: Pubkey::default(),
+        }
+    }
+}
+
+/// Pending endpoint ownership challenge for a metadata entry
+/// Seeds: [b"endpoint_challenge", asset.key(), key_hash[0..16]]
+///
+/// The endpoint publishes `endpoint_key`; verify_endpoint checks an Ed25519 signature
+/// by that key over the nonce, binding it to the current metadata value.
+#[account]
+#[derive(InitSpace)]
+pub struct EndpointChallenge {
+    pub asset: Pubkey,
+    pub key_hash: [u8; 16],
+    /// Key the endpoint is expected to publish
+    pub endpoint_key: Pubkey,
+    /// Fresh nonce to be signed by endpoint_key
+    pub nonce: [u8; 32],
+    /// Challenge deadline (unix timestamp)
+    pub expires_at: i64,
+    /// Receives rent back when the challenge is consumed
+    pub requester: Pubkey,
+    pub bump: u8,
+}
+
+impl EndpointChallenge {
+    /// Challenge lifetime in seconds
+    pub const TTL: i64 = 600;
+}
+
+/// Continuation chunk for a metadata value larger than 250 bytes
+/// Seeds: [b"agent_meta_chunk", asset.key(), key_hash[0..16], chunk_index (u16 LE)]
+///
+/// Chunks extend the MetadataEntryPda with the same key_hash. Readers concatenate
+/// `metadata_value` followed by chunks 0..n in index order.
+#[account]
+#[derive(InitSpace)]
+pub struct MetadataChunk {
+    /// Asset this chunk belongs to
+    pub asset: Pubkey,
+
+    /// Parent metadata key hash (SHA256(key)[0..16])
+    pub key_hash: [u8; 16],
+
+    /// Position of this chunk after the base value
+    pub chunk_index: u16,
+
+    /// Parent entry's immutable flag when written (informational: chunk writes and deletes
+    /// follow the parent's current lock state)
+    pub immutable: bool,
+
+    /// PDA bump seed
+    pub bump: u8,
+
+    /// Chunk payload (max 1024 bytes, may be filled across several append calls)
+    #[max_len(1024)]
+    pub data: Vec<u8>,
+}
+
+impl MetadataChunk {
+    /// Maximum chunk payload in bytes
+    pub const MAX_CHUNK_SIZE: usize = 1024;
+}
+
+
+/// Operator delegation granted by the agent owner
+/// Seeds: [b"agent_operator", asset.key(), operator.key()]
+///
+/// Operators can manage agent metadata and URI without the owner key being hot.
+/// A grant is only honored while `granted_by` is still the live Core owner,
+/// so every delegation is implicitly voided when the asset changes hands.
+#[account]
+#[derive(InitSpace)]
+pub struct AgentOperator {
+    /// Asset this delegation applies to
+    pub asset: Pubkey,
+
+    /// Delegated operator key
+    pub operator: Pubkey,
+
+    /// Owner that granted the delegation
+    pub granted_by: Pubkey,
+
+    /// PDA bump seed
+    pub bump: u8,
+
+    /// Actions the operator may perform (SCOPE_* bits; SCOPE_ALL for full operators)
+    pub scope: u64,
+
+    /// Session expiry (unix timestamp, 0 = never expires)
+    pub expires_at: i64,
+}
+
+impl AgentOperator {
+    /// Scope: set_agent_uri and card commitments
+    pub const SCOPE_URI: u64 = 1 << 0;
+    /// Scope: metadata entries, chunks and endpoint challenges
+    pub const SCOPE_METADATA: u64 = 1 << 1;
+    /// Scope: name, symbol, capabilities, status and tags
+    pub const SCOPE_PROFILE: u64 = 1 << 2;
+    /// Scope: responses to feedback
+    pub const SCOPE_RESPONSES: u64 = 1 << 3;
+    /// Full operator (grant_operator)
+    pub const SCOPE_ALL: u64 = u64::MAX;
+
+    /// Whether the delegation covers every bit of `scope` at `now`
+    pub fn allows(&self, scope: u64, now: i64) -> bool {
+        self.scope & scope == scope && (self.expires_at == 0 || now <= self.expires_at)
+    }
+}
+
+/// Co-owner council for DAO-operated agents
+/// Seeds: [b"owner_council", asset.key()]
+///
+/// Configured by the Core owner. Like operator grants, a council is only honored
+/// while `owner` is still the live Core owner. While enabled, the PDA is the Core
+/// TransferDelegate of the asset so approved transfers need no owner signature.
+#[account]
+#[derive(InitSpace)]
+pub struct OwnerCouncil {
+    pub asset: Pubkey,
+
+    /// Core owner that configured the council
+    pub owner: Pubkey,
+
+    /// Co-owner keys (bit i of a proposal bitmap is members[i])
+    #[max_len(5)]
+    pub members: Vec<Pubkey>,
+
+    /// Approvals required to execute (0 = disabled)
+    pub threshold: u8,
+
+    /// Incremented on every reconfiguration; older proposals become stale
+    pub epoch: u32,
+
+    /// Next proposal id
+    pub proposal_count: u64,
+
+    pub bump: u8,
+}
+
+impl OwnerCouncil {
+    pub const MAX_MEMBERS: usize = 5;
+
+    pub fn member_index(&self, key: &Pubkey) -> Option<usize> {
+        self.members.iter().position(|m| m == key)
+    }
+}
+
+/// Council proposal committing to a single management action
+/// Seeds: [b"council_proposal", asset.key(), proposal_id.to_le_bytes()]
+#[account]
+#[derive(InitSpace)]
+pub struct CouncilProposal {
+    pub asset: Pubkey,
+    pub proposal_id: u64,
+
+    /// Council epoch the approvals refer to
+    pub epoch: u32,
+
+    /// Action kind (see identity::council)
+    pub action: u8,
+
+    /// keccak256 commitment to the action arguments (see identity::council)
+    pub action_hash: [u8; 32],
+
+    /// Approval bitmap indexed by member position
+    pub approvals: u8,
+
+    pub proposer: Pubkey,
+    pub executed: bool,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+/// Pending two-step ownership transfer
+/// Seeds: [b"pending_transfer", asset.key()]
+///
+/// While pending, this PDA is the Core TransferDelegate of the asset so the
+/// recipient can complete the transfer without the proposer signing again.
+#[account]
+#[derive(InitSpace)]
+pub struct PendingTransfer {
+    /// Asset being transferred
+    pub asset: Pubkey,
+
+    /// Owner who proposed the transfer (receives rent back)
+    pub from: Pubkey,
+
+    /// Recipient who must accept
+    pub to: Pubkey,
+
+    /// Proposal timestamp
+    pub created_at: i64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+/// Registry treasury collecting registration fees
+/// Seeds: [b"treasury", collection.key()]
+/// Lamports above the rent-exempt minimum are withdrawable by the registry authority
+#[account]
+#[derive(InitSpace)]
+pub struct Treasury {
+    /// Collection (registry) this treasury belongs to
+    pub collection: Pubkey,
+
+    /// Lifetime fees collected (lamports)
+    pub total_collected: u64,
+
+    /// Lifetime withdrawals (lamports)
+    pub total_withdrawn: u64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+/// Attestor approved by the registry authority to issue agent verifications
+/// Seeds: [b"attestor", collection.key(), attestor]
+#[account]
+#[derive(InitSpace)]
+pub struct Attestor {
+    pub collection: Pubkey,
+    pub attestor: Pubkey,
+    pub added_at: i64,
+    pub bump: u8,
+}
+
+/// Verification issued by a registry attestor ("verified publisher", KYC, audit)
+/// Seeds: [b"agent_verification", asset.key()]
+///
+/// Owners cannot write this account; only an Attestor of the agent's registry can.
+#[account]
+#[derive(InitSpace)]
+pub struct AgentVerification {
+    pub asset: Pubkey,
+
+    /// Verification level (0 = revoked; meaning of higher levels is registry-defined)
+    pub level: u8,
+
+    /// Hash of the off-chain attestation document
+    pub attestation_hash: [u8; 32],
+
+    /// Attestor that last wrote the verification
+    pub attestor: Pubkey,
+
+    pub verified_at: i64,
+    pub bump: u8,
+}
+
+/// Registration referral attribution
+/// Seeds: [b"referral", asset.key()]
+#[account]
+#[derive(InitSpace)]
+pub struct Referral {
+    pub asset: Pubkey,
+
+    /// Referrer credited for the registration
+    pub referrer: Pubkey,
+
+    /// Collection (registry) the agent registered in
+    pub collection: Pubkey,
+
+    /// Registration fee share paid to the referrer (lamports)
+    pub fee_share: u64,
+
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+/// DID bound to an agent
+/// Seeds: [b"agent_did", asset.key()]
+///
+/// The DID's verification key signs the binding (Ed25519), so the DID controller
+/// has consented. For did:sol the method-specific id must equal `verification_key`.
+#[account]
+#[derive(InitSpace)]
+pub struct AgentDid {
+    /// Asset the DID is bound to
+    pub asset: Pubkey,
+
+    /// Ed25519 key from the DID document that signed the binding
+    pub verification_key: Pubkey,
+
+    /// Owner who created the binding
+    pub bound_by: Pubkey,
+
+    /// PDA bump seed
+    pub bump: u8,
+
+    /// DID string (did:sol:... or did:web:...)
+    #[max_len(128)]
+    pub did: String,
+}
+
+impl AgentDid {
+    /// Maximum DID length in bytes
+    pub const MAX_DID_LENGTH: usize = 128;
+}
+
+/// Metadata entry created by register_with_metadata
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct MetadataInput {
+    pub key: String,
+    pub value: Vec<u8>,
+    pub immutable: bool,
 }
 
+impl MetadataInput {
+    /// Maximum metadata entries per register_with_metadata call
+    pub const MAX_ENTRIES: usize = 8;
+}
+
+/// Royalty recipient share for set_agent_royalties
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RoyaltyShare {
+    pub address: Pubkey,
+    /// Share of royalties in percent (all shares sum to 100)
+    pub percentage: u8,
+}
+
+impl RoyaltyShare {
+    /// Maximum royalty recipients per asset
+    pub const MAX_CREATORS: usize = 5;
+}
+
+/// Reverse index from numeric agent id to asset
+/// Seeds: [b"agent_id", agent_id (u64 LE)]
+#[account]
+#[derive(InitSpace)]
+pub struct AgentIdIndex {
+    /// Sequential agent id
+    pub agent_id: u64,
+
+    /// Asset registered under this id
+    pub asset: Pubkey,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+/// Agent record returned by get_agent (Borsh return data, fits the 1024-byte limit)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct AgentView {
+    pub asset: Pubkey,
+    pub collection: Pubkey,
+    /// Cached owner (may be stale after external transfer)
+    pub owner: Pubkey,
+    pub agent_id: u64,
+    pub agent_wallet: Option<Pubkey>,
+    pub atom_enabled: bool,
+    pub frozen: bool,
+    pub status: u8,
+    pub trust_tier: u8,
+    pub soulbound: bool,
+    pub token_backed: bool,
+    pub valid_until: i64,
+    pub capabilities: u64,
+    pub feedback_count: u64,
+    pub response_count: u64,
+    pub revoke_count: u64,
+    pub uri_hash: [u8; 32],
+    pub uri_content_type: u8,
+    pub uri_content_length: u32,
+    /// Empty for compact agents (see uri_hash)
+    pub agent_uri: String,
+}
+
+impl From<&AgentAccount> for AgentView {
+    fn from(agent: &AgentAccount) -> Self {
+        Self {
+            asset: agent.asset,
+            collection: agent.collection,
+            owner: agent.owner,
+            agent_id: agent.agent_id,
+            agent_wallet: agent.agent_wallet,
+            atom_enabled: agent.atom_enabled,
+            frozen: agent.frozen,
+            status: agent.status,
+            trust_tier: agent.trust_tier,
+            soulbound: agent.soulbound,
+            token_backed: agent.token_backed,
+            valid_until: agent.valid_until,
+            capabilities: agent.capabilities,
+            feedback_count: agent.feedback_count,
+            response_count: agent.response_count,
+            revoke_count: agent.revoke_count,
+            uri_hash: agent.uri_hash,
+            uri_content_type: agent.uri_content_type,
+            uri_content_length: agent.uri_content_length,
+            agent_uri: agent.agent_uri.clone(),
+        }
+    }
+}
+
+/// Metadata entry returned by get_metadata (Borsh return data)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct MetadataView {
+    pub asset: Pubkey,
+    pub immutable: bool,
+    pub expires_at: i64,
+    pub key: String,
+    pub value: Vec<u8>,
+}
+
+/// Registry-owned Core Oracle account gating transfers of a collection's assets
+/// Seeds: [b"transfer_gate", collection.key()]
+///
+/// The first five fields mirror mpl-core's `OracleValidation::V1` Borsh layout at the
+/// Anchor offset (8), so Core reads them directly. Transfers are rejected outside the
+/// registry; transfer_agent/accept_transfer open the gate only for their own CPI.
+#[account]
+#[derive(InitSpace)]
+pub struct TransferGate {
+    /// OracleValidation variant (1 = V1)
+    pub validation_version: u8,
+    pub create: u8,
+    pub transfer: u8,
+    pub burn: u8,
+    pub update: u8,
+
+    /// Collection gated by this oracle
+    pub collection: Pubkey,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl TransferGate {
+    /// OracleValidation::V1 variant tag
+    pub const VALIDATION_V1: u8 = 1;
+
+    /// ExternalValidationResult tags
+    pub const RESULT_APPROVED: u8 = 0;
+    pub const RESULT_REJECTED: u8 = 1;
+    pub const RESULT_PASS: u8 = 2;
+
+    /// Byte offset of `transfer` in account data (discriminator + version + create)
+    pub const TRANSFER_RESULT_OFFSET: usize = 8 + 2;
+}
+
+/// Category tags of an agent for discovery
+/// Seeds: [b"agent_tags", asset.key()]
+///
+/// Fixed layout: tag slot i is at offset 8 + 32 + 32 * i, so clients filter with
+/// getProgramAccounts memcmp on keccak256(tag). Unused slots are zero.
+#[account]
+#[derive(InitSpace)]
+pub struct AgentTags {
+    /// Asset these tags belong to
+    pub asset: Pubkey,
+
+    /// keccak256 of each tag (zero = empty slot)
+    pub tags: [[u8; 32]; 4],
+
+    /// Number of used slots
+    pub count: u8,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl AgentTags {
+    /// Maximum tags per agent
+    pub const MAX_TAGS: usize = 4;
+}
+
+/// Number of agents carrying a category tag
+/// Seeds: [b"tag_counter", keccak256(tag)]
+#[account]
+#[derive(InitSpace)]
+pub struct TagCounter {
+    pub tag_hash: [u8; 32],
+    pub agent_count: u64,
+    pub bump: u8,
+}
+
+/// Page of the wallet -> agents reverse index
+/// Seeds: [b"owner_index", owner, page.to_le_bytes()]
+///
+/// Kept current by register, transfer, sync and deregister when the relevant pages
+/// are passed; reindex_owner repairs pages that were omitted. Pages are created with
+/// init_owner_index; once a page is full the next page must be created.
+#[account]
+#[derive(InitSpace)]
+pub struct OwnerIndex {
+    /// Wallet whose agents are listed
+    pub owner: Pubkey,
+
+    /// Page number (0-based)
+    pub page: u16,
+
+    /// PDA bump seed
+    pub bump: u8,
+
+    /// Agent assets owned by `owner` (unordered)
+    #[max_len(32)]
+    pub assets: Vec<Pubkey>,
+}
+
+impl OwnerIndex {
+    /// Maximum assets per page
+    pub const MAX_ASSETS: usize = 32;
+
+    pub fn is_full(&self) -> bool {
+        self.assets.len() >= Self::MAX_ASSETS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn baseline() -> AgentAccountV0 {
+        AgentAccountV0 {
+            collection: Pubkey::new_unique(),
+            creator: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+            asset: Pubkey::new_unique(),
+            bump: 255,
+            atom_enabled: false,
+            agent_wallet: None,
+            feedback_digest: [0u8; 32],
+            feedback_count: 0,
+            response_digest: [0u8; 32],
+            response_count: 0,
+            revoke_digest: [0u8; 32],
+            revoke_count: 0,
+            parent_asset: None,
+            parent_locked: false,
+            col_locked: false,
+            agent_uri: String::new(),
+            nft_name: String::new(),
+            col: String::new(),
+        }
+    }
+
+    fn agent() -> AgentAccount {
+        baseline().into_current(1)
+    }
+
+    #[test]
+    fn test_migrate_baseline_agent_round_trip() {
+        let legacy = AgentAccountV0 {
+            agent_wallet: Some(Pubkey::new_unique()),
+            feedback_count: 7,
+            revoke_count: 2,
+            feedback_digest: [7u8; 32],
+            parent_locked: true,
+            agent_uri: "ipfs://agent".to_string(),
+            nft_name: "Agent #7".to_string(),
+            col: "c1:ipfs://collection".to_string(),
+            ..baseline()
+        };
+        // Baseline accounts were allocated at full size; unused space stays zeroed
+        let mut data = vec![0u8; AgentAccountV0::SPACE];
+        data[..8].copy_from_slice(AgentAccount::DISCRIMINATOR);
+        legacy.serialize(&mut &mut data[8..]).unwrap();
+
+        let decoded = LegacyAgentAccount::decode(&data).unwrap();
+        assert_eq!(decoded.version(), 0);
+        assert_eq!(decoded.asset(), legacy.asset);
+        let agent = decoded.into_current(42);
+
+        let mut current = vec![0u8; AgentAccount::DISCRIMINATOR.len() + AgentAccount::INIT_SPACE];
+        agent.try_serialize(&mut &mut current[..]).unwrap();
+        let migrated = AgentAccount::try_deserialize(&mut &current[..]).unwrap();
+        assert_eq!(migrated.schema_version, AgentAccount::SCHEMA_VERSION);
+        assert_eq!(migrated.agent_id, 42);
+        assert_eq!(migrated.owner, legacy.owner);
+        assert_eq!(migrated.asset, legacy.asset);
+        assert_eq!(migrated.agent_wallet, legacy.agent_wallet);
+        assert_eq!(migrated.feedback_digest, legacy.feedback_digest);
+        assert_eq!(migrated.feedback_count, 7);
+        assert_eq!(migrated.revoke_count, 2);
+        assert!(migrated.parent_locked);
+        assert_eq!(migrated.agent_uri, legacy.agent_uri);
+        assert_eq!(migrated.nft_name, legacy.nft_name);
+        assert_eq!(migrated.col, legacy.col);
+        assert_eq!(migrated.uri_hash, AgentAccount::compute_uri_hash("ipfs://agent"));
+    }
+
+    #[test]
+    fn test_migrate_baseline_metadata_entry() {
+        let legacy = MetadataEntryPdaV0 {
+            asset: Pubkey::new_unique(),
+            immutable: true,
+            bump: 252,
+            metadata_key: "endpoint".to_string(),
+            metadata_value: b"https://agent.example".to_vec(),
+        };
+        // A shorter rewrite leaves stale bytes where expires_at would be read
+        let mut data = vec![0xffu8; MetadataEntryPdaV0::SPACE];
+        data[..8].copy_from_slice(MetadataEntryPda::DISCRIMINATOR);
+        legacy.serialize(&mut &mut data[8..]).unwrap();
+        let misread = MetadataEntryPda::try_deserialize(&mut &data[..]).unwrap();
+        assert_ne!(misread.expires_at, 0);
+
+        let entry = MetadataEntryPdaV0::deserialize(&mut &data[8..]).unwrap().into_current();
+        let mut current = vec![0u8; MetadataEntryPda::SPACE];
+        entry.try_serialize(&mut &mut current[..]).unwrap();
+        let migrated = MetadataEntryPda::try_deserialize(&mut &current[..]).unwrap();
+        assert_eq!(migrated.asset, legacy.asset);
+        assert_eq!(migrated.metadata_key, legacy.metadata_key);
+        assert_eq!(migrated.metadata_value, legacy.metadata_value);
+        assert_eq!(migrated.expires_at, 0);
+        assert_eq!(migrated.verified_key, Pubkey::default());
+        assert!(migrated.is_locked(i64::MAX));
+    }
+
+    #[test]
+    fn test_migrate_baseline_root_config() {
+        let legacy = RootConfigV0 {
+            base_collection: Pubkey::new_unique(),
+            authority: Pubkey::new_unique(),
+            bump: 253,
+        };
+        let mut data = vec![0u8; RootConfigV0::SPACE];
+        data[..8].copy_from_slice(RootConfig::DISCRIMINATOR);
+        legacy.serialize(&mut &mut data[8..]).unwrap();
+
+        let root = RootConfigV0::deserialize(&mut &data[8..]).unwrap().into_current();
+        let mut current = vec![0u8; RootConfig::DISCRIMINATOR.len() + RootConfig::INIT_SPACE];
+        root.try_serialize(&mut &mut current[..]).unwrap();
+        let migrated = RootConfig::try_deserialize(&mut &current[..]).unwrap();
+        assert_eq!(migrated.base_collection, legacy.base_collection);
+        assert_eq!(migrated.authority, legacy.authority);
+        assert_eq!(migrated.bump, 253);
+        assert_eq!(migrated.next_registry_id, 1);
+    }
+
+    #[test]
+    fn test_migrate_baseline_registry_config() {
+        let legacy = RegistryConfigV0 {
+            collection: Pubkey::new_unique(),
+            authority: Pubkey::new_unique(),
+            bump: 254,
+        };
+        let mut data = vec![0u8; RegistryConfigV0::SPACE];
+        data[..8].copy_from_slice(RegistryConfig::DISCRIMINATOR);
+        legacy.serialize(&mut &mut data[8..]).unwrap();
+
+        let registry = RegistryConfigV0::deserialize(&mut &data[8..]).unwrap().into_current();
+        let space = RegistryConfig::DISCRIMINATOR.len() + RegistryConfig::INIT_SPACE;
+        let mut current = vec![0u8; space];
+        registry.try_serialize(&mut &mut current[..]).unwrap();
+        let migrated = RegistryConfig::try_deserialize(&mut &current[..]).unwrap();
+        assert_eq!(migrated.collection, legacy.collection);
+        assert_eq!(migrated.authority, legacy.authority);
+        assert_eq!(migrated.bump, 254);
+        assert_eq!(migrated.registry_type, RegistryConfig::REGISTRY_TYPE_BASE);
+        assert_eq!(migrated.registry_id, 0);
+        assert_eq!(migrated.next_agent_id, 0);
+        assert_eq!(migrated.total_agents, 0);
+        assert_eq!(migrated.max_metadata_chunks, RegistryConfig::DEFAULT_MAX_METADATA_CHUNKS);
+        assert_eq!(migrated.allowed_uri_schemes, URI_SCHEMES_ALL);
+    }
+
+    #[test]
+    fn test_decode_rejects_unknown_layout() {
+        let mut data = vec![0u8; AgentAccountV0::SPACE + 1];
+        data[..8].copy_from_slice(AgentAccount::DISCRIMINATOR);
+        assert!(LegacyAgentAccount::decode(&data).is_err());
+        assert!(LegacyAgentAccount::decode(&data[..AgentAccountV0::SPACE]).is_ok());
+        data[0] ^= 1;
+        assert!(LegacyAgentAccount::decode(&data[..AgentAccountV0::SPACE]).is_err());
+    }
+
+    fn averages(agent: &AgentAccount) -> (u64, u64, u64, u64, u8, u8) {
+        (
+            agent.score_sum,
+            agent.scored_count,
+            agent.decayed_score_sum,
+            agent.decayed_weight,
+            agent.average_score(),
+            agent.decayed_average_score(),
+        )
+    }
+
+    #[test]
+    fn test_revoke_restores_averages() {
+        let mut agent = agent();
+        agent.record_score(70, 0);
+        let before = averages(&agent);
+
+        let recorded = agent.decay_halvings;
+        agent.record_score(20, 0);
+        assert_ne!(averages(&agent), before);
+        agent.remove_score(20, recorded);
+        assert_eq!(averages(&agent), before);
+    }
+
+    #[test]
+    fn test_revoke_after_decay_restores_averages() {
+        let mut with = agent();
+        let mut without = agent();
+        for account in [&mut with, &mut without] {
+            account.record_score(80, 0);
+            account.decay_scores(1);
+        }
+
+        let recorded = with.decay_halvings;
+        with.record_score(40, 0);
+        with.decay_scores(2);
+        without.decay_scores(2);
+        with.remove_score(40, recorded);
+        assert_eq!(averages(&with), averages(&without));
+        assert_eq!(with.decayed_average_score(), 80);
+    }
+
+    #[test]
+    fn test_revoke_restores_histogram_and_quantiles() {
+        let mut agent = agent();
+        for score in [40, 60, 75, 80, 95] {
+            agent.record_score(score, 0);
+        }
+        let histogram = agent.score_histogram;
+        let quantiles = (agent.score_quantile(50), agent.score_quantile(90));
+
+        let recorded = agent.decay_halvings;
+        for _ in 0..5 {
+            agent.record_score(0, 0);
+        }
+        assert_ne!((agent.score_quantile(50), agent.score_quantile(90)), quantiles);
+        for _ in 0..5 {
+            agent.remove_score(0, recorded);
+        }
+        assert_eq!(agent.score_histogram, histogram);
+        assert_eq!((agent.score_quantile(50), agent.score_quantile(90)), quantiles);
+    }
+
+    #[test]
+    fn test_revoke_of_fully_decayed_score_keeps_newer_weight() {
+        let mut agent = agent();
+        agent.record_score(10, 0);
+        agent.decay_scores(ReputationConfig::MAX_HALVINGS as u64 + 1);
+        agent.record_score(90, 0);
+
+        agent.remove_score(10, 0);
+        assert_eq!(agent.decayed_weight, AgentAccount::DECAY_WEIGHT_SCALE);
+        assert_eq!(agent.decayed_average_score(), 90);
+    }
+}
@@ -32,7 +32,39 @@ pub mod agent_registry_8004 {
         identity::instructions::initialize(ctx)
     }
 
-    /// Register agent in the base collection
+    /// Publish deployment feature flags for runtime capability detection (root authority)
+    pub fn set_registry_features(
+        ctx: Context<SetRegistryFeatures>,
+        version: u16,
+        features: u64,
+    ) -> Result<()> {
+        identity::instructions::set_registry_features(ctx, version, features)
+    }
+
+    /// Create an additional protocol-operated base registry (root authority only)
+    pub fn create_base_registry(
+        ctx: Context<CreateBaseRegistry>,
+        name: String,
+        uri: String,
+    ) -> Result<()> {
+        identity::instructions::create_base_registry(ctx, name, uri)
+    }
+
+    /// Create an organization-operated user registry with its own collection
+    pub fn create_user_registry(
+        ctx: Context<CreateUserRegistry>,
+        name: String,
+        uri: String,
+    ) -> Result<()> {
+        identity::instructions::create_user_registry(ctx, name, uri)
+    }
+
+    /// Delegate an existing Core collection to a new user registry (collection authority)
+    pub fn approve_collection(ctx: Context<ApproveCollection>) -> Result<()> {
+        identity::instructions::approve_collection(ctx)
+    }
+
+    /// Register agent in a registry collection (base or user registry)
     pub fn register(ctx: Context<Register>, agent_uri: String) -> Result<()> {
         identity::instructions::register(ctx, agent_uri)
     }
@@ -46,6 +78,390 @@ pub mod agent_registry_8004 {
         identity::instructions::register_with_options(ctx, agent_uri, atom_enabled)
     }
 
+    /// Register agent with metadata PDAs (remaining_accounts) and optional ATOM init
+    pub fn register_with_metadata<'info>(
+        ctx: Context<'_, '_, 'info, 'info, Register<'info>>,
+        agent_uri: String,
+        atom_enabled: bool,
+        metadata: Vec<MetadataInput>,
+    ) -> Result<()> {
+        identity::instructions::register_with_metadata(ctx, agent_uri, atom_enabled, metadata)
+    }
+
+    /// Register a soulbound (non-transferable) agent
+    pub fn register_soulbound(
+        ctx: Context<Register>,
+        agent_uri: String,
+        atom_enabled: bool,
+    ) -> Result<()> {
+        identity::instructions::register_soulbound(ctx, agent_uri, atom_enabled)
+    }
+
+    /// Register agent in a gated registry (Merkle proof of the owner wallet)
+    pub fn register_allowlisted(
+        ctx: Context<Register>,
+        agent_uri: String,
+        atom_enabled: bool,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        identity::instructions::register_allowlisted(ctx, agent_uri, atom_enabled, proof)
+    }
+
+    /// Register agent crediting a referrer (Referral PDA + registration fee share)
+    pub fn register_with_referral(
+        ctx: Context<Register>,
+        agent_uri: String,
+        atom_enabled: bool,
+        referrer: Pubkey,
+    ) -> Result<()> {
+        identity::instructions::register_with_referral(ctx, agent_uri, atom_enabled, referrer)
+    }
+
+    /// Register a rent-optimized agent storing only the URI hash (URI in events)
+    pub fn register_compact(
+        ctx: Context<Register>,
+        agent_uri: String,
+        atom_enabled: bool,
+    ) -> Result<()> {
+        identity::instructions::register_compact(ctx, agent_uri, atom_enabled)
+    }
+
+    /// Register an agent for an Ethereum owner (secp256k1 eth_sign proof)
+    pub fn register_for_evm_owner(
+        ctx: Context<Register>,
+        agent_uri: String,
+        atom_enabled: bool,
+        evm_address: [u8; 20],
+        signature: [u8; 64],
+        recovery_id: u8,
+        deadline: i64,
+    ) -> Result<()> {
+        identity::instructions::register_for_evm_owner(
+            ctx,
+            agent_uri,
+            atom_enabled,
+            evm_address,
+            signature,
+            recovery_id,
+            deadline,
+        )
+    }
+
+    /// Upgrade a legacy AgentAccount to the current layout (permissionless)
+    pub fn migrate_agent(ctx: Context<MigrateAgent>) -> Result<()> {
+        identity::instructions::migrate_agent(ctx)
+    }
+
+    /// Upgrade the v0.6.0 RootConfig to the current layout (permissionless)
+    pub fn migrate_root_config(ctx: Context<MigrateRootConfig>) -> Result<()> {
+        identity::instructions::migrate_root_config(ctx)
+    }
+
+    /// Upgrade a v0.6.0 MetadataEntryPda to the current layout (permissionless)
+    pub fn migrate_metadata_entry(
+        ctx: Context<MigrateMetadataEntry>,
+        key_hash: [u8; 16],
+    ) -> Result<()> {
+        identity::instructions::migrate_metadata_entry(ctx, key_hash)
+    }
+
+    /// Upgrade a v0.6.0 RegistryConfig to the current layout (permissionless)
+    pub fn migrate_registry_config(ctx: Context<MigrateRegistryConfig>) -> Result<()> {
+        identity::instructions::migrate_registry_config(ctx)
+    }
+
+    /// Rebind an agent and its agent_id to a new Core asset (owner + authority)
+    pub fn replace_asset(ctx: Context<ReplaceAsset>) -> Result<()> {
+        identity::instructions::replace_asset(ctx)
+    }
+
+    /// Configure, update or disable the m-of-n owner council (Core owner)
+    pub fn set_owner_council(
+        ctx: Context<SetOwnerCouncil>,
+        members: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        identity::instructions::set_owner_council(ctx, members, threshold)
+    }
+
+    /// Open a council proposal for a URI, metadata or transfer action (council member)
+    pub fn propose_council_action(
+        ctx: Context<ProposeCouncilAction>,
+        action: u8,
+        action_hash: [u8; 32],
+    ) -> Result<()> {
+        identity::instructions::propose_council_action(ctx, action, action_hash)
+    }
+
+    /// Approve a council proposal (council member)
+    pub fn approve_council_action(ctx: Context<ApproveCouncilAction>) -> Result<()> {
+        identity::instructions::approve_council_action(ctx)
+    }
+
+    /// Execute a council-approved transfer (council member)
+    pub fn council_transfer_agent(ctx: Context<CouncilTransferAgent>) -> Result<()> {
+        identity::instructions::council_transfer_agent(ctx)
+    }
+
+    /// Deregister agent: burn Core asset and close AgentAccount
+    pub fn deregister(ctx: Context<Deregister>) -> Result<()> {
+        identity::instructions::deregister(ctx)
+    }
+
+    /// Set registration validity period in seconds (0 = no expiry, authority only)
+    pub fn set_registration_period(
+        ctx: Context<SetRegistrationPeriod>,
+        registration_period: i64,
+    ) -> Result<()> {
+        identity::instructions::set_registration_period(ctx, registration_period)
+    }
+
+    /// Renew an agent registration by one registration period
+    pub fn renew_agent(ctx: Context<RenewAgent>) -> Result<()> {
+        identity::instructions::renew_agent(ctx)
+    }
+
+    /// Freeze an agent (registry authority only)
+    pub fn freeze_agent(ctx: Context<FreezeAgent>) -> Result<()> {
+        identity::instructions::freeze_agent(ctx)
+    }
+
+    /// Unfreeze an agent (registry authority only)
+    pub fn unfreeze_agent(ctx: Context<FreezeAgent>) -> Result<()> {
+        identity::instructions::unfreeze_agent(ctx)
+    }
+
+    /// Suspend an agent: no new feedback, owner cannot change status (authority only)
+    pub fn suspend_agent(ctx: Context<FreezeAgent>) -> Result<()> {
+        identity::instructions::suspend_agent(ctx)
+    }
+
+    /// Lift an agent suspension (registry authority only)
+    pub fn unsuspend_agent(ctx: Context<FreezeAgent>) -> Result<()> {
+        identity::instructions::unsuspend_agent(ctx)
+    }
+
+    /// Open an endpoint ownership challenge for a metadata entry (owner or operator)
+    pub fn request_endpoint_challenge(
+        ctx: Context<RequestEndpointChallenge>,
+        key_hash: [u8; 16],
+        endpoint_key: Pubkey,
+    ) -> Result<()> {
+        identity::instructions::request_endpoint_challenge(ctx, key_hash, endpoint_key)
+    }
+
+    /// Answer an endpoint challenge with an Ed25519 signature (permissionless)
+    pub fn verify_endpoint(ctx: Context<VerifyEndpoint>) -> Result<()> {
+        identity::instructions::verify_endpoint(ctx)
+    }
+
+    /// Rename the agent and its Core asset (owner or operator)
+    pub fn set_agent_name(ctx: Context<SetAgentUri>, new_name: String) -> Result<()> {
+        identity::instructions::set_agent_name(ctx, new_name)
+    }
+
+    /// Set the agent display symbol (owner or operator)
+    pub fn set_agent_symbol(ctx: Context<SetAgentSymbol>, new_symbol: String) -> Result<()> {
+        identity::instructions::set_agent_symbol(ctx, new_symbol)
+    }
+
+    /// Set agent lifecycle status: active / paused / retired (owner or operator)
+    pub fn set_agent_status(ctx: Context<SetAgentStatus>, status: u8) -> Result<()> {
+        identity::instructions::set_agent_status(ctx, status)
+    }
+
+    /// Register agent backed by a Token-2022 NFT (requires token2022_enabled)
+    pub fn register_token2022(
+        ctx: Context<RegisterToken2022>,
+        agent_uri: String,
+        atom_enabled: bool,
+    ) -> Result<()> {
+        identity::instructions::register_token2022(ctx, agent_uri, atom_enabled)
+    }
+
+    /// Enable or disable the Token-2022 registration backend (authority only)
+    pub fn set_token2022_backend(ctx: Context<SetToken2022Backend>, enabled: bool) -> Result<()> {
+        identity::instructions::set_token2022_backend(ctx, enabled)
+    }
+
+    /// Create the registry treasury (authority only)
+    pub fn initialize_treasury(ctx: Context<InitializeTreasury>) -> Result<()> {
+        identity::instructions::initialize_treasury(ctx)
+    }
+
+    /// Add an attestor allowed to set agent verifications (authority only)
+    pub fn add_attestor(ctx: Context<AddAttestor>) -> Result<()> {
+        identity::instructions::add_attestor(ctx)
+    }
+
+    /// Remove a registry attestor (authority only)
+    pub fn remove_attestor(ctx: Context<RemoveAttestor>) -> Result<()> {
+        identity::instructions::remove_attestor(ctx)
+    }
+
+    /// Set an agent's verification level + attestation hash (registry attestor only)
+    pub fn set_verification(
+        ctx: Context<SetVerification>,
+        level: u8,
+        attestation_hash: [u8; 32],
+    ) -> Result<()> {
+        identity::instructions::set_verification(ctx, level, attestation_hash)
+    }
+
+    /// Set registration fee in lamports (0 = free, authority only)
+    pub fn set_registration_fee(
+        ctx: Context<SetRegistrationFee>,
+        registration_fee: u64,
+    ) -> Result<()> {
+        identity::instructions::set_registration_fee(ctx, registration_fee)
+    }
+
+    /// Set the refundable registration bond and cooldown (authority only)
+    pub fn set_registration_bond(
+        ctx: Context<SetRegistrationBond>,
+        registration_bond: u64,
+        bond_cooldown: i64,
+    ) -> Result<()> {
+        identity::instructions::set_registration_bond(ctx, registration_bond, bond_cooldown)
+    }
+
+    /// Set the collection royalty policy: basis points routed to treasury or creator
+    pub fn set_collection_royalties(
+        ctx: Context<SetCollectionRoyalties>,
+        basis_points: u16,
+        route: u8,
+    ) -> Result<()> {
+        identity::instructions::set_collection_royalties(ctx, basis_points, route)
+    }
+
+    /// Apply the registry creator-royalty policy to an agent asset (permissionless)
+    pub fn apply_creator_royalty(ctx: Context<ApplyCreatorRoyalty>) -> Result<()> {
+        identity::instructions::apply_creator_royalty(ctx)
+    }
+
+    /// Set the post-feedback window during which agents cannot deregister (authority only)
+    pub fn set_dispute_window(ctx: Context<SetDisputeWindow>, dispute_window: i64) -> Result<()> {
+        identity::instructions::set_dispute_window(ctx, dispute_window)
+    }
+
+    /// Select agent-wide or per-client feedback indexing (authority only)
+    pub fn set_feedback_index_mode(ctx: Context<SetFeedbackIndexMode>, mode: u8) -> Result<()> {
+        identity::instructions::set_feedback_index_mode(ctx, mode)
+    }
+
+    /// Configure paid feedback mint and minimum amount (authority only)
+    pub fn set_feedback_payment(
+        ctx: Context<SetFeedbackPayment>,
+        mint: Pubkey,
+        min_amount: u64,
+    ) -> Result<()> {
+        identity::instructions::set_feedback_payment(ctx, mint, min_amount)
+    }
+
+    /// Allow self-feedback in this registry (test environments, authority only)
+    pub fn set_allow_self_feedback(
+        ctx: Context<SetAllowSelfFeedback>,
+        allowed: bool,
+    ) -> Result<()> {
+        identity::instructions::set_allow_self_feedback(ctx, allowed)
+    }
+
+    /// Set the minimum slots between feedbacks of one client on one agent (authority only)
+    pub fn set_feedback_cooldown(
+        ctx: Context<SetFeedbackCooldown>,
+        cooldown_slots: u64,
+    ) -> Result<()> {
+        identity::instructions::set_feedback_cooldown(ctx, cooldown_slots)
+    }
+
+    /// Configure per-feedback client bonds and challenge window (authority only)
+    pub fn set_feedback_bond(
+        ctx: Context<SetFeedbackBond>,
+        amount: u64,
+        challenge_window: i64,
+    ) -> Result<()> {
+        identity::instructions::set_feedback_bond(ctx, amount, challenge_window)
+    }
+
+    /// Set the delay before committed feedback can be revealed unilaterally (authority only)
+    pub fn set_feedback_reveal_delay(
+        ctx: Context<SetFeedbackRevealDelay>,
+        delay: i64,
+    ) -> Result<()> {
+        identity::instructions::set_feedback_reveal_delay(ctx, delay)
+    }
+
+    /// Require registered canonical tags on feedback (authority only)
+    pub fn set_strict_feedback_tags(
+        ctx: Context<SetStrictFeedbackTags>,
+        strict: bool,
+    ) -> Result<()> {
+        identity::instructions::set_strict_feedback_tags(ctx, strict)
+    }
+
+    /// Set the registration fee share paid to referrers in basis points (authority only)
+    pub fn set_referral_share(
+        ctx: Context<SetReferralShare>,
+        referral_share_bps: u16,
+    ) -> Result<()> {
+        identity::instructions::set_referral_share(ctx, referral_share_bps)
+    }
+
+    /// Set the registration allowlist Merkle root (zero = open, authority only)
+    pub fn set_allowlist_root(
+        ctx: Context<SetAllowlistRoot>,
+        allowlist_root: [u8; 32],
+    ) -> Result<()> {
+        identity::instructions::set_allowlist_root(ctx, allowlist_root)
+    }
+
+    /// Slash an agent's registration bond into the treasury (authority only)
+    pub fn slash_registration_bond(ctx: Context<SlashRegistrationBond>) -> Result<()> {
+        identity::instructions::slash_registration_bond(ctx)
+    }
+
+    /// Update registry collection name/URI (authority only)
+    pub fn update_collection(
+        ctx: Context<UpdateCollection>,
+        name: Option<String>,
+        uri: Option<String>,
+    ) -> Result<()> {
+        identity::instructions::update_collection(ctx, name, uri)
+    }
+
+    /// Enable or disable the collection transfer gate (authority only)
+    pub fn set_transfer_gate(ctx: Context<SetTransferGate>, enabled: bool) -> Result<()> {
+        identity::instructions::set_transfer_gate(ctx, enabled)
+    }
+
+    /// Set the URI scheme allowlist (authority only)
+    pub fn set_uri_schemes(ctx: Context<SetUriSchemes>, allowed_uri_schemes: u8) -> Result<()> {
+        identity::instructions::set_uri_schemes(ctx, allowed_uri_schemes)
+    }
+
+    /// Propose a new registry authority, or cancel with None (authority only)
+    pub fn set_registry_authority(
+        ctx: Context<SetRegistryAuthority>,
+        new_authority: Option<Pubkey>,
+    ) -> Result<()> {
+        identity::instructions::set_registry_authority(ctx, new_authority)
+    }
+
+    /// Accept a pending registry authority rotation (proposed authority only)
+    pub fn accept_registry_authority(ctx: Context<AcceptRegistryAuthority>) -> Result<()> {
+        identity::instructions::accept_registry_authority(ctx)
+    }
+
+    /// Set the owner-sync bounty paid from the treasury (authority only)
+    pub fn set_sync_bounty(ctx: Context<SetSyncBounty>, sync_bounty: u64) -> Result<()> {
+        identity::instructions::set_sync_bounty(ctx, sync_bounty)
+    }
+
+    /// Withdraw collected fees from the treasury (authority only)
+    pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, amount: u64) -> Result<()> {
+        identity::instructions::withdraw_treasury(ctx, amount)
+    }
+
     /// Enable ATOM for an agent (one-way)
     pub fn enable_atom(ctx: Context<EnableAtom>) -> Result<()> {
         identity::instructions::enable_atom(ctx)
@@ -62,26 +478,189 @@ pub mod agent_registry_8004 {
         identity::instructions::set_metadata_pda(ctx, key_hash, key, value, immutable)
     }
 
+    /// Set agent metadata PDA that is immutable only until `expires_at`
+    pub fn set_metadata_pda_with_expiry(
+        ctx: Context<SetMetadataPda>,
+        key_hash: [u8; 16],
+        key: String,
+        value: Vec<u8>,
+        immutable: bool,
+        expires_at: i64,
+    ) -> Result<()> {
+        identity::instructions::set_metadata_pda_with_expiry(
+            ctx, key_hash, key, value, immutable, expires_at,
+        )
+    }
+
     /// Delete agent metadata PDA and recover rent (key_hash = SHA256(key)[0..16])
     pub fn delete_metadata_pda(ctx: Context<DeleteMetadataPda>, key_hash: [u8; 16]) -> Result<()> {
         identity::instructions::delete_metadata_pda(ctx, key_hash)
     }
 
+    /// Replace a metadata chunk (extends a metadata entry beyond 250 bytes)
+    pub fn set_metadata_chunk(
+        ctx: Context<SetMetadataChunk>,
+        key_hash: [u8; 16],
+        chunk_index: u16,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        identity::instructions::set_metadata_chunk(ctx, key_hash, chunk_index, data)
+    }
+
+    /// Append bytes to a metadata chunk (creates it if missing)
+    pub fn append_metadata_chunk(
+        ctx: Context<SetMetadataChunk>,
+        key_hash: [u8; 16],
+        chunk_index: u16,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        identity::instructions::append_metadata_chunk(ctx, key_hash, chunk_index, data)
+    }
+
+    /// Delete a metadata chunk and recover rent
+    pub fn delete_metadata_chunk(
+        ctx: Context<DeleteMetadataChunk>,
+        key_hash: [u8; 16],
+        chunk_index: u16,
+    ) -> Result<()> {
+        identity::instructions::delete_metadata_chunk(ctx, key_hash, chunk_index)
+    }
+
+    /// Set maximum metadata chunks per key (authority only)
+    pub fn set_max_metadata_chunks(
+        ctx: Context<SetMaxMetadataChunks>,
+        max_metadata_chunks: u16,
+    ) -> Result<()> {
+        identity::instructions::set_max_metadata_chunks(ctx, max_metadata_chunks)
+    }
+
     /// Set agent URI
     pub fn set_agent_uri(ctx: Context<SetAgentUri>, new_uri: String) -> Result<()> {
         identity::instructions::set_agent_uri(ctx, new_uri)
     }
 
+    /// Set agent URI with content-type and size hints
+    pub fn set_agent_uri_with_hints(
+        ctx: Context<SetAgentUri>,
+        new_uri: String,
+        content_type: u8,
+        content_length: u32,
+    ) -> Result<()> {
+        identity::instructions::set_agent_uri_with_hints(ctx, new_uri, content_type, content_length)
+    }
+
+    /// Commit keccak256 of the off-chain agent card (owner or operator)
+    pub fn commit_agent_card(
+        ctx: Context<CommitAgentCard>,
+        card_hash: [u8; 32],
+        card_chunk_root: [u8; 32],
+    ) -> Result<()> {
+        identity::instructions::commit_agent_card(ctx, card_hash, card_chunk_root)
+    }
+
+    /// Set agent capability flags (owner or operator)
+    pub fn set_capabilities(ctx: Context<SetCapabilities>, capabilities: u64) -> Result<()> {
+        identity::instructions::set_capabilities(ctx, capabilities)
+    }
+
+    /// Require an ERC-8004 FeedbackAuth for new feedback on this agent (owner or operator)
+    pub fn set_feedback_auth_required(
+        ctx: Context<SetCapabilities>,
+        required: bool,
+    ) -> Result<()> {
+        identity::instructions::set_feedback_auth_required(ctx, required)
+    }
+
+    /// Create an OwnerIndex page (wallet -> agents reverse lookup)
+    pub fn init_owner_index(ctx: Context<InitOwnerIndex>, owner: Pubkey, page: u16) -> Result<()> {
+        identity::instructions::init_owner_index(ctx, owner, page)
+    }
+
+    /// Repair an asset's entry on an OwnerIndex page from the cached owner (permissionless)
+    pub fn reindex_owner(ctx: Context<ReindexOwner>) -> Result<()> {
+        identity::instructions::reindex_owner(ctx)
+    }
+
+    /// Set agent category tags (max 4, keccak-hashed) with per-tag counters
+    pub fn set_agent_tags<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SetAgentTags<'info>>,
+        tags: Vec<String>,
+    ) -> Result<()> {
+        identity::instructions::set_agent_tags(ctx, tags)
+    }
+
+    /// Verify full agent card bytes against the commitment
+    pub fn verify_agent_card(ctx: Context<VerifyAgentCard>, card: Vec<u8>) -> Result<()> {
+        identity::instructions::verify_agent_card(ctx, card)
+    }
+
+    /// Verify one agent card chunk with a Merkle proof against the commitment
+    pub fn verify_agent_card_chunk(
+        ctx: Context<VerifyAgentCard>,
+        chunk_index: u32,
+        chunk: Vec<u8>,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        identity::instructions::verify_agent_card_chunk(ctx, chunk_index, chunk, proof)
+    }
+
     /// Sync agent owner from Core asset
     pub fn sync_owner(ctx: Context<SyncOwner>) -> Result<()> {
         identity::instructions::sync_owner(ctx)
     }
 
+    /// Sync owner of a Token-2022 backed agent from its holder token account
+    pub fn sync_token_owner(ctx: Context<SyncTokenOwner>) -> Result<()> {
+        identity::instructions::sync_token_owner(ctx)
+    }
+
     /// Get agent owner (cached - may be stale after external transfer)
     pub fn owner_of(ctx: Context<OwnerOf>) -> Result<Pubkey> {
         identity::instructions::owner_of(ctx)
     }
 
+    /// Check whether an agent registration has expired
+    pub fn is_agent_expired(ctx: Context<IsAgentExpired>) -> Result<bool> {
+        identity::instructions::is_agent_expired(ctx)
+    }
+
+    /// Create a per-tag feedback index page (permissionless)
+    pub fn init_tag_feedback_index(
+        ctx: Context<InitTagFeedbackIndex>,
+        asset: Pubkey,
+        tag_hash: [u8; 32],
+        page: u16,
+    ) -> Result<()> {
+        reputation::instructions::init_tag_feedback_index(ctx, asset, tag_hash, page)
+    }
+
+    /// Apply elapsed half-lives to an agent's time-decayed average (permissionless)
+    pub fn decay_agent_reputation(ctx: Context<DecayAgentReputation>) -> Result<()> {
+        reputation::instructions::decay_agent_reputation(ctx)
+    }
+
+    /// Write the current epoch's reputation checkpoint (permissionless)
+    pub fn checkpoint_reputation(ctx: Context<CheckpointReputation>) -> Result<()> {
+        reputation::instructions::checkpoint_reputation(ctx)
+    }
+
+    /// Get naive and ATOM reputation in one view (Borsh ReputationSummaryView via return data)
+    pub fn get_reputation_summary(
+        ctx: Context<GetReputationSummary>,
+    ) -> Result<ReputationSummaryView> {
+        reputation::instructions::get_reputation_summary(ctx)
+    }
+
+    /// Get agent record (Borsh AgentView via return data)
+    pub fn get_agent(ctx: Context<GetAgent>) -> Result<AgentView> {
+        identity::instructions::get_agent(ctx)
+    }
+
+    /// Get a single metadata entry (Borsh MetadataView via return data)
+    pub fn get_metadata(ctx: Context<GetMetadata>, key_hash: [u8; 16]) -> Result<MetadataView> {
+        identity::instructions::get_metadata(ctx, key_hash)
+    }
+
     /// Get authoritative Core owner (reads live from Metaplex Core)
     pub fn core_owner_of(ctx: Context<CoreOwnerOf>) -> Result<Pubkey> {
         identity::instructions::core_owner_of(ctx)
@@ -92,6 +671,21 @@ pub mod agent_registry_8004 {
         identity::instructions::transfer_agent(ctx)
     }
 
+    /// Propose a two-step transfer (recipient must accept)
+    pub fn propose_transfer(ctx: Context<ProposeTransfer>, new_owner: Pubkey) -> Result<()> {
+        identity::instructions::propose_transfer(ctx, new_owner)
+    }
+
+    /// Accept a pending transfer (recipient signs)
+    pub fn accept_transfer(ctx: Context<AcceptTransfer>) -> Result<()> {
+        identity::instructions::accept_transfer(ctx)
+    }
+
+    /// Cancel a pending transfer (proposer signs)
+    pub fn cancel_transfer(ctx: Context<CancelTransfer>) -> Result<()> {
+        identity::instructions::cancel_transfer(ctx)
+    }
+
     /// Set agent wallet with Ed25519 signature verification
     pub fn set_agent_wallet(
         ctx: Context<SetAgentWallet>,
@@ -101,6 +695,60 @@ pub mod agent_registry_8004 {
         identity::instructions::set_agent_wallet(ctx, new_wallet, deadline)
     }
 
+    /// Bind a DID (did:sol / did:web) to an agent, signed by the DID verification key
+    pub fn set_did(
+        ctx: Context<SetDid>,
+        did: String,
+        verification_key: Pubkey,
+        deadline: i64,
+    ) -> Result<()> {
+        identity::instructions::set_did(ctx, did, verification_key, deadline)
+    }
+
+    /// Remove an agent's DID binding (owner only)
+    pub fn clear_did(ctx: Context<ClearDid>) -> Result<()> {
+        identity::instructions::clear_did(ctx)
+    }
+
+    /// Add a Core FreezeDelegate plugin controlled by `delegate` (owner only)
+    pub fn add_freeze_delegate(ctx: Context<ManageAgentPlugin>, delegate: Pubkey) -> Result<()> {
+        identity::instructions::add_freeze_delegate(ctx, delegate)
+    }
+
+    /// Add or update the Core Royalties plugin on the agent asset (owner only)
+    pub fn set_agent_royalties(
+        ctx: Context<ManageAgentPlugin>,
+        basis_points: u16,
+        creators: Vec<RoyaltyShare>,
+    ) -> Result<()> {
+        identity::instructions::set_agent_royalties(ctx, basis_points, creators)
+    }
+
+    /// Add a Core Oracle plugin gating transfers of the agent asset (owner only)
+    pub fn add_agent_oracle(ctx: Context<ManageAgentPlugin>, oracle: Pubkey) -> Result<()> {
+        identity::instructions::add_agent_oracle(ctx, oracle)
+    }
+
+    /// Grant an operator key permission to manage agent URI and metadata
+    pub fn grant_operator(ctx: Context<GrantOperator>) -> Result<()> {
+        identity::instructions::grant_operator(ctx)
+    }
+
+    /// Grant an expiring session key limited to a scope bitmask (AgentOperator::SCOPE_*)
+    pub fn grant_session(ctx: Context<GrantOperator>, scope: u64, expires_at: i64) -> Result<()> {
+        identity::instructions::grant_session(ctx, scope, expires_at)
+    }
+
+    /// Revoke an operator delegation and recover rent
+    pub fn revoke_operator(ctx: Context<RevokeOperator>) -> Result<()> {
+        identity::instructions::revoke_operator(ctx)
+    }
+
+    /// Set agent wallet with the wallet co-signing (alternative to Ed25519 proof)
+    pub fn set_agent_wallet_cosigned(ctx: Context<SetAgentWalletCosigned>) -> Result<()> {
+        identity::instructions::set_agent_wallet_cosigned(ctx)
+    }
+
     /// Set canonical collection pointer once (first-write-wins)
     pub fn set_collection_pointer(ctx: Context<SetCollectionPointer>, col: String) -> Result<()> {
         identity::instructions::set_collection_pointer(ctx, col)
@@ -160,6 +808,255 @@ pub mod agent_registry_8004 {
         )
     }
 
+    /// Give feedback with an ERC-8004 FeedbackAuth (Ed25519 instruction must precede)
+    pub fn give_feedback_with_auth(
+        ctx: Context<GiveFeedbackWithAuth>,
+        value: i128,
+        value_decimals: u8,
+        score: Option<u8>,
+        feedback_file_hash: Option<[u8; 32]>,
+        tag1: String,
+        tag2: String,
+        endpoint: String,
+        feedback_uri: String,
+        feedback_auth: FeedbackAuth,
+    ) -> Result<()> {
+        reputation::instructions::give_feedback_with_auth(
+            ctx,
+            value,
+            value_decimals,
+            score,
+            feedback_file_hash,
+            tag1,
+            tag2,
+            endpoint,
+            feedback_uri,
+            feedback_auth,
+        )
+    }
+
+    /// Give feedback with a token payment to the agent wallet (registry payment mint)
+    pub fn give_feedback_paid(
+        ctx: Context<GiveFeedbackPaid>,
+        value: i128,
+        value_decimals: u8,
+        score: Option<u8>,
+        feedback_file_hash: Option<[u8; 32]>,
+        tag1: String,
+        tag2: String,
+        endpoint: String,
+        feedback_uri: String,
+        amount: u64,
+    ) -> Result<()> {
+        reputation::instructions::give_feedback_paid(
+            ctx,
+            value,
+            value_decimals,
+            score,
+            feedback_file_hash,
+            tag1,
+            tag2,
+            endpoint,
+            feedback_uri,
+            amount,
+        )
+    }
+
+    /// Give feedback with an accuracy/latency/cost/communication score vector
+    pub fn give_feedback_with_dimensions(
+        ctx: Context<GiveFeedbackWithDimensions>,
+        value: i128,
+        value_decimals: u8,
+        score: Option<u8>,
+        feedback_file_hash: Option<[u8; 32]>,
+        tag1: String,
+        tag2: String,
+        endpoint: String,
+        feedback_uri: String,
+        dimensions: [u8; 4],
+    ) -> Result<()> {
+        reputation::instructions::give_feedback_with_dimensions(
+            ctx,
+            value,
+            value_decimals,
+            score,
+            feedback_file_hash,
+            tag1,
+            tag2,
+            endpoint,
+            feedback_uri,
+            dimensions,
+        )
+    }
+
+    /// Commit hidden feedback for a delayed reveal
+    pub fn give_feedback_committed(
+        ctx: Context<CommitFeedback>,
+        commitment: [u8; 32],
+    ) -> Result<()> {
+        reputation::instructions::give_feedback_committed(ctx, commitment)
+    }
+
+    /// Consent to an early reveal of committed feedback (owner or operator)
+    pub fn consent_feedback_reveal(ctx: Context<ConsentFeedbackReveal>) -> Result<()> {
+        reputation::instructions::consent_feedback_reveal(ctx)
+    }
+
+    /// Reveal committed feedback (after the reveal delay or with owner consent)
+    pub fn reveal_feedback(
+        ctx: Context<RevealFeedback>,
+        value: i128,
+        value_decimals: u8,
+        score: Option<u8>,
+        feedback_file_hash: Option<[u8; 32]>,
+        tag1: String,
+        tag2: String,
+        endpoint: String,
+        feedback_uri: String,
+        salt: [u8; 32],
+    ) -> Result<()> {
+        reputation::instructions::reveal_feedback(
+            ctx,
+            value,
+            value_decimals,
+            score,
+            feedback_file_hash,
+            tag1,
+            tag2,
+            endpoint,
+            feedback_uri,
+            salt,
+        )
+    }
+
+    /// Amend earlier feedback with a new version (linked via FeedbackAmended)
+    pub fn amend_feedback(
+        ctx: Context<GiveFeedback>,
+        value: i128,
+        value_decimals: u8,
+        score: Option<u8>,
+        feedback_file_hash: Option<[u8; 32]>,
+        tag1: String,
+        tag2: String,
+        endpoint: String,
+        feedback_uri: String,
+        original_feedback_index: u64,
+        original_seal_hash: [u8; 32],
+    ) -> Result<()> {
+        reputation::instructions::amend_feedback(
+            ctx,
+            value,
+            value_decimals,
+            score,
+            feedback_file_hash,
+            tag1,
+            tag2,
+            endpoint,
+            feedback_uri,
+            original_feedback_index,
+            original_seal_hash,
+        )
+    }
+
+    /// Submit scored feedback for up to 10 agents (accounts via remaining_accounts)
+    pub fn give_feedback_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, GiveFeedbackBatch<'info>>,
+        entries: Vec<BatchFeedbackEntry>,
+    ) -> Result<()> {
+        reputation::instructions::give_feedback_batch(ctx, entries)
+    }
+
+    /// Register a canonical feedback tag with the next tag id (authority only)
+    pub fn register_canonical_tag(
+        ctx: Context<RegisterCanonicalTag>,
+        tag: String,
+        tag_hash: [u8; 32],
+    ) -> Result<()> {
+        reputation::instructions::register_canonical_tag(ctx, tag, tag_hash)
+    }
+
+    /// Submit client-signed feedback through a relayer that pays fees and rent
+    pub fn give_feedback_with_permit(
+        ctx: Context<GiveFeedbackWithPermit>,
+        value: i128,
+        value_decimals: u8,
+        score: Option<u8>,
+        feedback_file_hash: Option<[u8; 32]>,
+        tag1: String,
+        tag2: String,
+        endpoint: String,
+        feedback_uri: String,
+        permit: FeedbackPermit,
+    ) -> Result<()> {
+        reputation::instructions::give_feedback_with_permit(
+            ctx,
+            value,
+            value_decimals,
+            score,
+            feedback_file_hash,
+            tag1,
+            tag2,
+            endpoint,
+            feedback_uri,
+            permit,
+        )
+    }
+
+    /// Submit EIP-712 feedback signed by an EVM client through a relayer
+    pub fn give_feedback_with_evm_permit(
+        ctx: Context<GiveFeedbackWithEvmPermit>,
+        value: i128,
+        value_decimals: u8,
+        score: Option<u8>,
+        feedback_file_hash: Option<[u8; 32]>,
+        tag1: String,
+        tag2: String,
+        endpoint: String,
+        feedback_uri: String,
+        permit: EvmFeedbackPermit,
+    ) -> Result<()> {
+        reputation::instructions::give_feedback_with_evm_permit(
+            ctx,
+            value,
+            value_decimals,
+            score,
+            feedback_file_hash,
+            tag1,
+            tag2,
+            endpoint,
+            feedback_uri,
+            permit,
+        )
+    }
+
+    /// Approve a feedback aggregator (authority only)
+    pub fn add_feedback_aggregator(ctx: Context<AddFeedbackAggregator>) -> Result<()> {
+        reputation::instructions::add_feedback_aggregator(ctx)
+    }
+
+    /// Remove a feedback aggregator (authority only)
+    pub fn remove_feedback_aggregator(ctx: Context<RemoveFeedbackAggregator>) -> Result<()> {
+        reputation::instructions::remove_feedback_aggregator(ctx)
+    }
+
+    /// Commit a Merkle root of off-chain feedback (approved aggregator only)
+    pub fn commit_feedback_root(
+        ctx: Context<CommitFeedbackRoot>,
+        root: [u8; 32],
+        entry_count: u64,
+    ) -> Result<()> {
+        reputation::instructions::commit_feedback_root(ctx, root, entry_count)
+    }
+
+    /// Materialize one committed feedback record with a Merkle proof (permissionless)
+    pub fn claim_feedback(
+        ctx: Context<ClaimFeedback>,
+        entry: RootFeedbackEntry,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        reputation::instructions::claim_feedback(ctx, entry, proof)
+    }
+
     /// Revoke feedback
     /// SEAL v1: Client provides seal_hash (can be recomputed using computeSealHash)
     pub fn revoke_feedback(
@@ -170,6 +1067,16 @@ pub mod agent_registry_8004 {
         reputation::instructions::revoke_feedback(ctx, feedback_index, seal_hash)
     }
 
+    /// Revoke feedback and update the TagReputation for its tag1
+    pub fn revoke_feedback_with_tag(
+        ctx: Context<RevokeFeedback>,
+        feedback_index: u64,
+        seal_hash: [u8; 32],
+        tag1: String,
+    ) -> Result<()> {
+        reputation::instructions::revoke_feedback_with_tag(ctx, feedback_index, seal_hash, tag1)
+    }
+
     /// Append response to feedback
     /// SEAL v1: Client provides seal_hash from the original feedback
     pub fn append_response(
@@ -190,6 +1097,106 @@ pub mod agent_registry_8004 {
         )
     }
 
+    /// Append an official response as the agent owner or a responses operator
+    pub fn append_official_response(
+        ctx: Context<AppendResponse>,
+        client_address: Pubkey,
+        feedback_index: u64,
+        response_uri: String,
+        response_hash: [u8; 32],
+        seal_hash: [u8; 32],
+    ) -> Result<()> {
+        reputation::instructions::append_official_response(
+            ctx,
+            client_address,
+            feedback_index,
+            response_uri,
+            response_hash,
+            seal_hash,
+        )
+    }
+
+    /// Flag a feedback as contested (owner or responses operator)
+    pub fn flag_feedback(
+        ctx: Context<FlagFeedback>,
+        client_address: Pubkey,
+        feedback_index: u64,
+        seal_hash: [u8; 32],
+        reason: u8,
+        reason_hash: [u8; 32],
+    ) -> Result<()> {
+        reputation::instructions::flag_feedback(
+            ctx,
+            client_address,
+            feedback_index,
+            seal_hash,
+            reason,
+            reason_hash,
+        )
+    }
+
+    /// Counter-flag the agent's flag on your feedback (client)
+    pub fn counter_flag_feedback(
+        ctx: Context<CounterFlagFeedback>,
+        counter_hash: [u8; 32],
+    ) -> Result<()> {
+        reputation::instructions::counter_flag_feedback(ctx, counter_hash)
+    }
+
+    /// Reply to an earlier response (threaded responses)
+    pub fn append_reply(
+        ctx: Context<AppendResponse>,
+        client_address: Pubkey,
+        feedback_index: u64,
+        parent_response_index: u64,
+        response_uri: String,
+        response_hash: [u8; 32],
+        seal_hash: [u8; 32],
+    ) -> Result<()> {
+        reputation::instructions::append_reply(
+            ctx,
+            client_address,
+            feedback_index,
+            parent_response_index,
+            response_uri,
+            response_hash,
+            seal_hash,
+        )
+    }
+
+    /// Set the TagReputation decay half-life of a registry (authority only)
+    pub fn set_reputation_decay(
+        ctx: Context<SetReputationDecay>,
+        decay_half_life: i64,
+    ) -> Result<()> {
+        reputation::instructions::set_reputation_decay(ctx, decay_half_life)
+    }
+
+    /// Apply elapsed half-lives to a TagReputation aggregate (permissionless)
+    pub fn decay_tag_reputation(ctx: Context<DecayTagReputation>) -> Result<()> {
+        reputation::instructions::decay_tag_reputation(ctx)
+    }
+
+    /// Reclaim an undisputed feedback bond after its challenge window (client)
+    pub fn claim_feedback_bond(ctx: Context<ClaimFeedbackBond>) -> Result<()> {
+        reputation::instructions::claim_feedback_bond(ctx)
+    }
+
+    /// Dispute a bonded feedback as spam (agent owner or operator)
+    pub fn dispute_feedback_bond(ctx: Context<DisputeFeedbackBond>) -> Result<()> {
+        reputation::instructions::dispute_feedback_bond(ctx)
+    }
+
+    /// Slash or return a disputed feedback bond (registry authority)
+    pub fn resolve_feedback_bond(ctx: Context<ResolveFeedbackBond>, slash: bool) -> Result<()> {
+        reputation::instructions::resolve_feedback_bond(ctx, slash)
+    }
+
+    /// Mirror ATOM trust tier and feedback count into AgentAccount (permissionless)
+    pub fn sync_reputation(ctx: Context<SyncReputation>) -> Result<()> {
+        reputation::instructions::sync_reputation(ctx)
+    }
+
     // NOTE: Validation module removed in v0.5.0 - planned for future upgrade
     // Archived code available in src/_archive/validation/
 }
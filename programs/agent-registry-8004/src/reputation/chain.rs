@@ -6,6 +6,9 @@ pub const DOMAIN_RESPONSE: &[u8] = b"8004_RESPONSE_V1";
 pub const DOMAIN_REVOKE: &[u8] = b"8004_REVOKE_V1";
 pub const DOMAIN_RESPONSE_LEAF_V1: &[u8; 16] = b"8004_RSP_LEAF_V1";
 pub const DOMAIN_REVOKE_LEAF_V1: &[u8; 16] = b"8004_RVK_LEAF_V1";
+pub const DOMAIN_REPLY_LEAF_V1: &[u8; 16] = b"8004_RPL_LEAF_V1";
+pub const DOMAIN_OFFICIAL_LEAF_V1: &[u8; 16] = b"8004_OFR_LEAF_V1";
+pub const DOMAIN_ROOT_FEEDBACK_LEAF_V1: &[u8; 16] = b"8004_RFB_LEAF_V1";
 
 pub fn compute_feedback_leaf(
     asset: &Pubkey,
@@ -44,6 +47,37 @@ pub fn compute_response_leaf(
     keccak::hash(&data).0
 }
 
+/// Leaf for a threaded response: binds the response leaf to its parent response index
+pub fn compute_reply_leaf(response_leaf: &[u8; 32], parent_response_index: u64) -> [u8; 32] {
+    let mut data = Vec::with_capacity(16 + 32 + 8);
+    data.extend_from_slice(DOMAIN_REPLY_LEAF_V1);
+    data.extend_from_slice(response_leaf);
+    data.extend_from_slice(&parent_response_index.to_le_bytes());
+    keccak::hash(&data).0
+}
+
+/// Leaf for an official response: marks a response leaf as sent by the owner or an operator
+pub fn compute_official_leaf(response_leaf: &[u8; 32]) -> [u8; 32] {
+    let mut data = Vec::with_capacity(16 + 32);
+    data.extend_from_slice(DOMAIN_OFFICIAL_LEAF_V1);
+    data.extend_from_slice(response_leaf);
+    keccak::hash(&data).0
+}
+
+/// Leaf of an aggregator feedback root: binds an off-chain record to its agent and client
+pub fn compute_root_feedback_leaf(
+    asset: &Pubkey,
+    client: &Pubkey,
+    seal_hash: &[u8; 32],
+) -> [u8; 32] {
+    let mut data = Vec::with_capacity(16 + 32 + 32 + 32);
+    data.extend_from_slice(DOMAIN_ROOT_FEEDBACK_LEAF_V1);
+    data.extend_from_slice(asset.as_ref());
+    data.extend_from_slice(client.as_ref());
+    data.extend_from_slice(seal_hash);
+    keccak::hash(&data).0
+}
+
 pub fn compute_revoke_leaf(
     asset: &Pubkey,
     client: &Pubkey,
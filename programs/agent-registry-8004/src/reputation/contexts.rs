@@ -1,10 +1,19 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
+use super::state::{
+    CanonicalTag, EvmFeedbackPermit, FeedbackAggregator, FeedbackBond, FeedbackCommitment,
+    FeedbackFlag, FeedbackPermit, FeedbackRoot, FeedbackScore, PermitNonce, ReputationConfig,
+    TagFeedbackIndex, TagRegistry, TagReputation,
+};
 use crate::error::RegistryError;
-use crate::identity::state::AgentAccount;
+use crate::identity::state::{AgentAccount, AgentOperator, RegistryConfig, Treasury};
 
 pub const ATOM_CPI_AUTHORITY_SEED: &[u8] = b"atom_cpi_authority";
 
+/// Scored feedback from the client (give_feedback, amend_feedback); the other scored entry
+/// points wrap it with their own accounts
 #[derive(Accounts)]
 #[instruction(_value: i128, _value_decimals: u8, _score: Option<u8>, _feedback_file_hash: Option<[u8; 32]>, _tag1: String, _tag2: String, _endpoint: String, _feedback_uri: String)]
 pub struct GiveFeedback<'info> {
@@ -15,9 +24,18 @@ pub struct GiveFeedback<'info> {
         mut,
         seeds = [b"agent", asset.key().as_ref()],
         bump = agent_account.bump,
+        constraint = !agent_account.frozen @ RegistryError::AgentFrozen,
+        constraint = agent_account.accepts_feedback() @ RegistryError::AgentInactive,
     )]
     pub agent_account: Account<'info, AgentAccount>,
 
+    /// Registry config (URI scheme allowlist)
+    #[account(
+        seeds = [b"registry_config", agent_account.collection.as_ref()],
+        bump = registry_config.bump
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
     /// CHECK: Validated via agent_account.asset constraint
     #[account(
         constraint = asset.key() == agent_account.asset @ RegistryError::InvalidAsset
@@ -54,12 +72,226 @@ pub struct GiveFeedback<'info> {
         bump,
     )]
     pub registry_authority: Option<UncheckedAccount<'info>>,
+
+    /// ClientIndex PDA (required in per-client indexing mode or with a feedback cooldown,
+    /// created on first feedback)
+    /// CHECK: PDA verified in instruction
+    #[account(mut)]
+    pub client_index: Option<UncheckedAccount<'info>>,
+
+    /// TagReputation PDA for tag1; when passed with feedback_score, a scored feedback with a
+    /// tag1 is also counted under that tag
+    /// CHECK: PDA verified in instruction, created on first use
+    #[account(mut)]
+    pub tag_reputation: Option<UncheckedAccount<'info>>,
+
+    /// AgentOperator PDA for (asset, client); when passed, feedback from an active operator
+    /// is rejected (usually uninitialized)
+    /// CHECK: PDA verified in instruction
+    pub client_operator: Option<UncheckedAccount<'info>>,
+
+    /// FeedbackBond PDA for this feedback (required when the registry sets feedback_bond)
+    /// CHECK: PDA verified in instruction, created with the bond
+    #[account(mut)]
+    pub feedback_bond: Option<UncheckedAccount<'info>>,
+
+    /// CanonicalTag for tag1 (required in strict mode; otherwise tag1 counts as non-canonical)
+    pub canonical_tag1: Option<Account<'info, CanonicalTag>>,
+
+    /// CanonicalTag for tag2 (same rules as canonical_tag1)
+    pub canonical_tag2: Option<Account<'info, CanonicalTag>>,
+
+    /// ReputationCheckpoint PDA for the current epoch (written when passed)
+    /// CHECK: PDA verified in instruction, created on first write in the epoch
+    #[account(mut)]
+    pub reputation_checkpoint: Option<UncheckedAccount<'info>>,
+
+    /// Current TagFeedbackIndex page for tag1 (appended when passed)
+    #[account(mut)]
+    pub tag_feedback_index: Option<Account<'info, TagFeedbackIndex>>,
+
+    /// FeedbackScore PDA for this feedback; a score counts in the AgentAccount aggregates
+    /// only when this record is created with it
+    /// CHECK: PDA verified in instruction, created with the feedback
+    #[account(mut)]
+    pub feedback_score: Option<UncheckedAccount<'info>>,
+}
+
+/// Feedback under an ERC-8004 FeedbackAuth (Ed25519 instruction must precede)
+#[derive(Accounts)]
+pub struct GiveFeedbackWithAuth<'info> {
+    pub feedback: GiveFeedback<'info>,
+
+    /// CHECK: Verified by address constraint
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+}
+
+/// Feedback backed by a token payment to the agent wallet
+#[derive(Accounts)]
+pub struct GiveFeedbackPaid<'info> {
+    pub feedback: GiveFeedback<'info>,
+
+    /// Registry payment mint
+    pub payment_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Client token account funding the payment
+    #[account(mut)]
+    pub client_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Token account owned by the agent's registered wallet
+    #[account(mut)]
+    pub agent_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Feedback opened from a FeedbackCommitment
+#[derive(Accounts)]
+pub struct RevealFeedback<'info> {
+    pub feedback: GiveFeedback<'info>,
+
+    /// FeedbackCommitment being revealed (closed to the client)
+    #[account(mut)]
+    pub feedback_commitment: Account<'info, FeedbackCommitment>,
+}
+
+/// Feedback with a per-dimension score vector
+#[derive(Accounts)]
+pub struct GiveFeedbackWithDimensions<'info> {
+    pub feedback: GiveFeedback<'info>,
+
+    /// DimensionReputation PDA
+    /// CHECK: PDA verified in instruction, created on first use
+    #[account(mut)]
+    pub dimension_reputation: UncheckedAccount<'info>,
+}
+
+/// Relayed feedback signed off-chain by the client (relayer pays fees and rent)
+#[derive(Accounts)]
+#[instruction(
+    value: i128,
+    value_decimals: u8,
+    score: Option<u8>,
+    feedback_file_hash: Option<[u8; 32]>,
+    tag1: String,
+    tag2: String,
+    endpoint: String,
+    feedback_uri: String,
+    permit: FeedbackPermit
+)]
+pub struct GiveFeedbackWithPermit<'info> {
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"agent", asset.key().as_ref()],
+        bump = agent_account.bump,
+        constraint = !agent_account.frozen @ RegistryError::AgentFrozen,
+        constraint = agent_account.accepts_feedback() @ RegistryError::AgentInactive,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// CHECK: Verified via agent_account constraint
+    #[account(
+        constraint = asset.key() == agent_account.asset @ RegistryError::InvalidAsset
+    )]
+    pub asset: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"registry_config", agent_account.collection.as_ref()],
+        bump = registry_config.bump
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = PermitNonce::DISCRIMINATOR.len() + PermitNonce::INIT_SPACE,
+        seeds = [b"permit_nonce", permit.client.as_ref()],
+        bump
+    )]
+    pub permit_nonce: Account<'info, PermitNonce>,
+
+    /// CHECK: Verified by address constraint
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// AgentOperator PDA for (asset, permit client), as in give_feedback
+    /// CHECK: PDA verified in instruction
+    pub client_operator: Option<UncheckedAccount<'info>>,
+}
+
+/// Relayed feedback signed off-chain by an EVM client (EIP-712 typed data)
+#[derive(Accounts)]
+#[instruction(
+    value: i128,
+    value_decimals: u8,
+    score: Option<u8>,
+    feedback_file_hash: Option<[u8; 32]>,
+    tag1: String,
+    tag2: String,
+    endpoint: String,
+    feedback_uri: String,
+    permit: EvmFeedbackPermit
+)]
+pub struct GiveFeedbackWithEvmPermit<'info> {
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"agent", asset.key().as_ref()],
+        bump = agent_account.bump,
+        constraint = !agent_account.frozen @ RegistryError::AgentFrozen,
+        constraint = agent_account.accepts_feedback() @ RegistryError::AgentInactive,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// CHECK: Verified via agent_account constraint
+    #[account(
+        constraint = asset.key() == agent_account.asset @ RegistryError::InvalidAsset
+    )]
+    pub asset: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"registry_config", agent_account.collection.as_ref()],
+        bump = registry_config.bump
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = PermitNonce::DISCRIMINATOR.len() + PermitNonce::INIT_SPACE,
+        seeds = [b"evm_permit_nonce", permit.evm_address.as_ref()],
+        bump
+    )]
+    pub permit_nonce: Account<'info, PermitNonce>,
+
+    pub system_program: Program<'info, System>,
+
+    /// AgentOperator PDA for (asset, permit client), as in give_feedback
+    /// CHECK: PDA verified in instruction
+    pub client_operator: Option<UncheckedAccount<'info>>,
+}
+
+/// Batch feedback from one client across agents (accounts per entry in remaining_accounts)
+#[derive(Accounts)]
+pub struct GiveFeedbackBatch<'info> {
+    #[account(mut)]
+    pub client: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
 }
 
 /// RevokeFeedback calls CPI to atom-engine to revoke stats (optional)
 /// SEAL v1: Uses seal_hash instead of feedback_hash
 #[derive(Accounts)]
-#[instruction(_feedback_index: u64, _seal_hash: [u8; 32])]
+#[instruction(feedback_index: u64, _seal_hash: [u8; 32])]
 pub struct RevokeFeedback<'info> {
     #[account(mut)]
     pub client: Signer<'info>,
@@ -79,6 +311,21 @@ pub struct RevokeFeedback<'info> {
 
     pub system_program: Program<'info, System>,
 
+    /// FeedbackScore PDA of the revoked feedback (uninitialized when its score was not
+    /// aggregated); always required so a counted score cannot outlive its revocation
+    /// CHECK: Seeds verified here, contents in instruction
+    #[account(
+        mut,
+        seeds = [
+            b"feedback_score",
+            asset.key().as_ref(),
+            client.key().as_ref(),
+            feedback_index.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub feedback_score: UncheckedAccount<'info>,
+
     // === OPTIONAL: CPI to atom-engine ===
     // If atom_enabled is false, these accounts may be omitted
 
@@ -101,6 +348,123 @@ pub struct RevokeFeedback<'info> {
         bump,
     )]
     pub registry_authority: Option<UncheckedAccount<'info>>,
+
+    /// TagReputation PDA the revoked score was counted under (required when its FeedbackScore
+    /// has a tag_hash)
+    /// CHECK: PDA verified in instruction
+    #[account(mut)]
+    pub tag_reputation: Option<UncheckedAccount<'info>>,
+}
+
+/// Mirror the ATOM summary into AgentAccount (permissionless crank)
+#[derive(Accounts)]
+pub struct SyncReputation<'info> {
+    #[account(
+        mut,
+        seeds = [b"agent", asset.key().as_ref()],
+        bump = agent_account.bump,
+        constraint = agent_account.atom_enabled @ RegistryError::AtomStatsNotInitialized,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// CHECK: Validated via agent_account.asset constraint
+    #[account(
+        constraint = asset.key() == agent_account.asset @ RegistryError::InvalidAsset
+    )]
+    pub asset: UncheckedAccount<'info>,
+
+    /// AtomStats PDA (seeds checked by atom-engine)
+    /// CHECK: Validated by atom-engine program
+    pub atom_stats: UncheckedAccount<'info>,
+
+    /// CHECK: ATOM Engine program ID (verified by address constraint)
+    #[account(address = atom_engine::ID @ RegistryError::InvalidProgram)]
+    pub atom_engine_program: UncheckedAccount<'info>,
+}
+
+/// Create a TagFeedbackIndex page (permissionless)
+#[derive(Accounts)]
+#[instruction(asset: Pubkey, tag_hash: [u8; 32], page: u16)]
+pub struct InitTagFeedbackIndex<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = TagFeedbackIndex::DISCRIMINATOR.len() + TagFeedbackIndex::INIT_SPACE,
+        seeds = [
+            b"tag_fb_index",
+            asset.as_ref(),
+            tag_hash.as_ref(),
+            page.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub tag_feedback_index: Account<'info, TagFeedbackIndex>,
+
+    /// Previous page (required when page > 0)
+    #[account(
+        seeds = [
+            b"tag_fb_index",
+            asset.as_ref(),
+            tag_hash.as_ref(),
+            page.saturating_sub(1).to_le_bytes().as_ref()
+        ],
+        bump = previous_page.bump
+    )]
+    pub previous_page: Option<Account<'info, TagFeedbackIndex>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Write the current epoch's reputation checkpoint (permissionless crank)
+#[derive(Accounts)]
+pub struct CheckpointReputation<'info> {
+    #[account(
+        seeds = [b"agent", asset.key().as_ref()],
+        bump = agent_account.bump,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// CHECK: Validated via agent_account.asset constraint
+    #[account(
+        constraint = asset.key() == agent_account.asset @ RegistryError::InvalidAsset
+    )]
+    pub asset: UncheckedAccount<'info>,
+
+    /// CHECK: PDA verified in instruction, created on first write in the epoch
+    #[account(mut)]
+    pub reputation_checkpoint: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Read the combined registry + ATOM reputation summary (returned as ReputationSummaryView)
+#[derive(Accounts)]
+pub struct GetReputationSummary<'info> {
+    #[account(
+        seeds = [b"agent", asset.key().as_ref()],
+        bump = agent_account.bump,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// CHECK: Validated via agent_account.asset constraint
+    #[account(
+        constraint = asset.key() == agent_account.asset @ RegistryError::InvalidAsset
+    )]
+    pub asset: UncheckedAccount<'info>,
+
+    /// AtomStats PDA (seeds checked by atom-engine); include with atom_engine_program
+    /// CHECK: Validated by atom-engine program
+    pub atom_stats: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: ATOM Engine program ID (verified by address constraint)
+    #[account(address = atom_engine::ID @ RegistryError::InvalidProgram)]
+    pub atom_engine_program: Option<UncheckedAccount<'info>>,
 }
 
 /// SEAL v1: Uses seal_hash instead of feedback_hash
@@ -123,4 +487,468 @@ pub struct AppendResponse<'info> {
         constraint = asset.key() == agent_account.asset @ RegistryError::InvalidAsset
     )]
     pub asset: UncheckedAccount<'info>,
+
+    /// Registry config (URI scheme allowlist)
+    #[account(
+        seeds = [b"registry_config", agent_account.collection.as_ref()],
+        bump = registry_config.bump
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
+    /// Optional operator delegation (official responses from a non-owner signer)
+    pub agent_operator: Option<Account<'info, AgentOperator>>,
+}
+
+/// Set the aggregate decay half-life of a registry (registry authority only)
+#[derive(Accounts)]
+pub struct SetReputationDecay<'info> {
+    #[account(
+        seeds = [b"registry_config", registry_config.collection.as_ref()],
+        bump = registry_config.bump,
+        has_one = authority @ RegistryError::Unauthorized
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = ReputationConfig::DISCRIMINATOR.len() + ReputationConfig::INIT_SPACE,
+        seeds = [b"reputation_config", registry_config.collection.as_ref()],
+        bump
+    )]
+    pub reputation_config: Account<'info, ReputationConfig>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Apply elapsed half-lives to a TagReputation (permissionless crank)
+#[derive(Accounts)]
+pub struct DecayTagReputation<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"tag_rep",
+            tag_reputation.agent_id.to_le_bytes().as_ref(),
+            tag_reputation.tag_hash.as_ref()
+        ],
+        bump = tag_reputation.bump,
+    )]
+    pub tag_reputation: Account<'info, TagReputation>,
+
+    #[account(
+        seeds = [b"agent", tag_reputation.asset.as_ref()],
+        bump = agent_account.bump,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        seeds = [b"reputation_config", agent_account.collection.as_ref()],
+        bump = reputation_config.bump,
+    )]
+    pub reputation_config: Account<'info, ReputationConfig>,
+}
+
+/// Decay an agent's time-weighted average (permissionless crank)
+#[derive(Accounts)]
+pub struct DecayAgentReputation<'info> {
+    #[account(
+        mut,
+        seeds = [b"agent", agent_account.asset.as_ref()],
+        bump = agent_account.bump,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    #[account(
+        seeds = [b"reputation_config", agent_account.collection.as_ref()],
+        bump = reputation_config.bump,
+    )]
+    pub reputation_config: Account<'info, ReputationConfig>,
+}
+
+/// Flag a feedback as contested (agent owner or operator)
+#[derive(Accounts)]
+#[instruction(client_address: Pubkey, feedback_index: u64)]
+pub struct FlagFeedback<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = FeedbackFlag::DISCRIMINATOR.len() + FeedbackFlag::INIT_SPACE,
+        seeds = [
+            b"feedback_flag",
+            asset.key().as_ref(),
+            client_address.as_ref(),
+            feedback_index.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub feedback_flag: Account<'info, FeedbackFlag>,
+
+    #[account(
+        seeds = [b"agent", asset.key().as_ref()],
+        bump = agent_account.bump,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// CHECK: Verified via agent_account constraint
+    #[account(
+        constraint = asset.key() == agent_account.asset @ RegistryError::InvalidAsset
+    )]
+    pub asset: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Optional operator delegation (required when signer is not the Core owner)
+    pub agent_operator: Option<Account<'info, AgentOperator>>,
+}
+
+/// Answer an agent's flag on the client's feedback (client)
+#[derive(Accounts)]
+pub struct CounterFlagFeedback<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"feedback_flag",
+            feedback_flag.asset.as_ref(),
+            client.key().as_ref(),
+            feedback_flag.feedback_index.to_le_bytes().as_ref()
+        ],
+        bump = feedback_flag.bump,
+    )]
+    pub feedback_flag: Account<'info, FeedbackFlag>,
+
+    pub client: Signer<'info>,
+}
+
+/// Reclaim a feedback bond after its challenge window (client)
+#[derive(Accounts)]
+pub struct ClaimFeedbackBond<'info> {
+    #[account(
+        mut,
+        close = client,
+        seeds = [
+            b"feedback_bond",
+            feedback_bond.asset.as_ref(),
+            client.key().as_ref(),
+            feedback_bond.feedback_index.to_le_bytes().as_ref()
+        ],
+        bump = feedback_bond.bump,
+    )]
+    pub feedback_bond: Account<'info, FeedbackBond>,
+
+    #[account(mut)]
+    pub client: Signer<'info>,
+}
+
+/// Dispute a bonded feedback within its challenge window (agent owner or operator)
+#[derive(Accounts)]
+pub struct DisputeFeedbackBond<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"feedback_bond",
+            feedback_bond.asset.as_ref(),
+            feedback_bond.client.as_ref(),
+            feedback_bond.feedback_index.to_le_bytes().as_ref()
+        ],
+        bump = feedback_bond.bump,
+    )]
+    pub feedback_bond: Account<'info, FeedbackBond>,
+
+    /// CHECK: Ownership verified in instruction
+    #[account(
+        constraint = asset.key() == feedback_bond.asset @ RegistryError::InvalidAsset
+    )]
+    pub asset: UncheckedAccount<'info>,
+
+    pub owner: Signer<'info>,
+
+    /// Optional operator delegation (required when signer is not the Core owner)
+    pub agent_operator: Option<Account<'info, AgentOperator>>,
+}
+
+/// Settle a disputed feedback bond (registry authority only)
+#[derive(Accounts)]
+pub struct ResolveFeedbackBond<'info> {
+    #[account(
+        seeds = [b"registry_config", registry_config.collection.as_ref()],
+        bump = registry_config.bump,
+        has_one = authority @ RegistryError::Unauthorized
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
+    #[account(
+        mut,
+        close = client,
+        seeds = [
+            b"feedback_bond",
+            feedback_bond.asset.as_ref(),
+            client.key().as_ref(),
+            feedback_bond.feedback_index.to_le_bytes().as_ref()
+        ],
+        bump = feedback_bond.bump,
+        constraint = feedback_bond.collection == registry_config.collection
+            @ RegistryError::InvalidCollection
+    )]
+    pub feedback_bond: Account<'info, FeedbackBond>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury", registry_config.collection.as_ref()],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    /// CHECK: Bonding client (verified via feedback_bond seeds), receives the rent back
+    #[account(mut)]
+    pub client: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+}
+
+/// Approve a feedback aggregator (registry authority only)
+#[derive(Accounts)]
+pub struct AddFeedbackAggregator<'info> {
+    #[account(
+        seeds = [b"registry_config", registry_config.collection.as_ref()],
+        bump = registry_config.bump,
+        has_one = authority @ RegistryError::Unauthorized
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = FeedbackAggregator::DISCRIMINATOR.len() + FeedbackAggregator::INIT_SPACE,
+        seeds = [
+            b"feedback_aggregator",
+            registry_config.collection.as_ref(),
+            aggregator.key().as_ref()
+        ],
+        bump
+    )]
+    pub aggregator_record: Account<'info, FeedbackAggregator>,
+
+    /// Aggregator key
+    /// CHECK: Can be any account
+    pub aggregator: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Remove a feedback aggregator (registry authority only)
+#[derive(Accounts)]
+pub struct RemoveFeedbackAggregator<'info> {
+    #[account(
+        seeds = [b"registry_config", registry_config.collection.as_ref()],
+        bump = registry_config.bump,
+        has_one = authority @ RegistryError::Unauthorized
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [
+            b"feedback_aggregator",
+            registry_config.collection.as_ref(),
+            aggregator_record.aggregator.as_ref()
+        ],
+        bump = aggregator_record.bump
+    )]
+    pub aggregator_record: Account<'info, FeedbackAggregator>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+/// Post a Merkle root of off-chain feedback (approved aggregator only)
+#[derive(Accounts)]
+#[instruction(root: [u8; 32])]
+pub struct CommitFeedbackRoot<'info> {
+    #[account(
+        seeds = [
+            b"feedback_aggregator",
+            aggregator_record.collection.as_ref(),
+            aggregator.key().as_ref()
+        ],
+        bump = aggregator_record.bump
+    )]
+    pub aggregator_record: Account<'info, FeedbackAggregator>,
+
+    #[account(
+        init,
+        payer = aggregator,
+        space = FeedbackRoot::DISCRIMINATOR.len() + FeedbackRoot::INIT_SPACE,
+        seeds = [b"feedback_root", aggregator_record.collection.as_ref(), root.as_ref()],
+        bump
+    )]
+    pub feedback_root: Account<'info, FeedbackRoot>,
+
+    #[account(mut)]
+    pub aggregator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Materialize one committed feedback record on-chain with a Merkle proof (permissionless)
+#[derive(Accounts)]
+pub struct ClaimFeedback<'info> {
+    #[account(
+        mut,
+        seeds = [b"feedback_root", feedback_root.collection.as_ref(), feedback_root.root.as_ref()],
+        bump = feedback_root.bump
+    )]
+    pub feedback_root: Account<'info, FeedbackRoot>,
+
+    /// FeedbackClaim marker for the entry's leaf
+    /// CHECK: PDA verified in instruction, created on claim
+    #[account(mut)]
+    pub feedback_claim: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"agent", asset.key().as_ref()],
+        bump = agent_account.bump,
+        constraint = agent_account.collection == feedback_root.collection
+            @ RegistryError::InvalidCollection,
+        constraint = !agent_account.frozen @ RegistryError::AgentFrozen,
+        constraint = agent_account.accepts_feedback() @ RegistryError::AgentInactive,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// CHECK: Verified via agent_account constraint
+    #[account(
+        constraint = asset.key() == agent_account.asset @ RegistryError::InvalidAsset
+    )]
+    pub asset: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"registry_config", agent_account.collection.as_ref()],
+        bump = registry_config.bump
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// AgentOperator PDA for (asset, entry client), as in give_feedback
+    /// CHECK: PDA verified in instruction
+    pub client_operator: Option<UncheckedAccount<'info>>,
+}
+
+/// Register a canonical feedback tag (registry authority only)
+#[derive(Accounts)]
+#[instruction(tag: String, tag_hash: [u8; 32])]
+pub struct RegisterCanonicalTag<'info> {
+    #[account(
+        seeds = [b"registry_config", registry_config.collection.as_ref()],
+        bump = registry_config.bump,
+        has_one = authority @ RegistryError::Unauthorized
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = TagRegistry::DISCRIMINATOR.len() + TagRegistry::INIT_SPACE,
+        seeds = [b"tag_registry", registry_config.collection.as_ref()],
+        bump
+    )]
+    pub tag_registry: Account<'info, TagRegistry>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = CanonicalTag::DISCRIMINATOR.len() + CanonicalTag::INIT_SPACE,
+        seeds = [b"canonical_tag", registry_config.collection.as_ref(), tag_hash.as_ref()],
+        bump
+    )]
+    pub canonical_tag: Account<'info, CanonicalTag>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Commit hidden feedback for later reveal (client)
+#[derive(Accounts)]
+#[instruction(commitment: [u8; 32])]
+pub struct CommitFeedback<'info> {
+    #[account(mut)]
+    pub client: Signer<'info>,
+
+    #[account(
+        seeds = [b"agent", asset.key().as_ref()],
+        bump = agent_account.bump,
+        constraint = !agent_account.frozen @ RegistryError::AgentFrozen,
+        constraint = agent_account.accepts_feedback() @ RegistryError::AgentInactive,
+    )]
+    pub agent_account: Account<'info, AgentAccount>,
+
+    /// CHECK: Verified via agent_account constraint
+    #[account(
+        constraint = asset.key() == agent_account.asset @ RegistryError::InvalidAsset
+    )]
+    pub asset: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"registry_config", agent_account.collection.as_ref()],
+        bump = registry_config.bump
+    )]
+    pub registry_config: Account<'info, RegistryConfig>,
+
+    #[account(
+        init,
+        payer = client,
+        space = FeedbackCommitment::DISCRIMINATOR.len() + FeedbackCommitment::INIT_SPACE,
+        seeds = [
+            b"feedback_commit",
+            asset.key().as_ref(),
+            client.key().as_ref(),
+            commitment.as_ref()
+        ],
+        bump
+    )]
+    pub feedback_commitment: Account<'info, FeedbackCommitment>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Allow an early reveal of committed feedback (agent owner or operator)
+#[derive(Accounts)]
+pub struct ConsentFeedbackReveal<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"feedback_commit",
+            feedback_commitment.asset.as_ref(),
+            feedback_commitment.client.as_ref(),
+            feedback_commitment.commitment.as_ref()
+        ],
+        bump = feedback_commitment.bump
+    )]
+    pub feedback_commitment: Account<'info, FeedbackCommitment>,
+
+    /// CHECK: Core asset, owner read in instruction
+    #[account(
+        constraint = asset.key() == feedback_commitment.asset @ RegistryError::InvalidAsset
+    )]
+    pub asset: UncheckedAccount<'info>,
+
+    pub owner: Signer<'info>,
+
+    /// Optional operator delegation (required when signer is not the Core owner)
+    pub agent_operator: Option<Account<'info, AgentOperator>>,
 }
@@ -35,6 +35,58 @@ pub struct NewFeedback {
     pub feedback_uri: String,
 }
 
+/// Event emitted alongside NewFeedback when the client paid the agent wallet
+#[event]
+pub struct FeedbackPaid {
+    pub asset: Pubkey,
+    pub client_address: Pubkey,
+    pub feedback_index: u64,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub agent_wallet: Pubkey,
+}
+
+/// Event emitted after NewFeedback when the new entry supersedes an earlier one
+#[event]
+pub struct FeedbackAmended {
+    pub asset: Pubkey,
+    pub client_address: Pubkey,
+    pub original_feedback_index: u64,
+    pub original_seal_hash: [u8; 32],
+    /// feedback_index of the new version
+    pub superseded_by: u64,
+    pub new_seal_hash: [u8; 32],
+}
+
+/// Event emitted when a client bonds lamports on a feedback
+#[event]
+pub struct FeedbackBondLocked {
+    pub asset: Pubkey,
+    pub client_address: Pubkey,
+    pub feedback_index: u64,
+    pub amount: u64,
+    pub unlocks_at: i64,
+}
+
+/// Event emitted when the agent owner disputes a bonded feedback
+#[event]
+pub struct FeedbackBondDisputed {
+    pub asset: Pubkey,
+    pub client_address: Pubkey,
+    pub feedback_index: u64,
+    pub disputed_by: Pubkey,
+}
+
+/// Event emitted when a feedback bond is returned to the client or slashed
+#[event]
+pub struct FeedbackBondSettled {
+    pub asset: Pubkey,
+    pub client_address: Pubkey,
+    pub feedback_index: u64,
+    pub amount: u64,
+    pub slashed: bool,
+}
+
 /// Event emitted when feedback is revoked
 /// SEAL v1: Uses seal_hash (on-chain computed) for identification.
 #[event]
@@ -57,6 +109,49 @@ pub struct FeedbackRevoked {
     pub new_revoke_count: u64,
 }
 
+/// Event emitted when a per-tag aggregate changes
+#[event]
+pub struct TagReputationUpdated {
+    pub asset: Pubkey,
+    pub tag_hash: [u8; 32],
+    pub feedback_count: u64,
+    pub score_sum: u64,
+    pub average_score: u8,
+}
+
+/// Event emitted when a registry's aggregate decay half-life changes
+#[event]
+pub struct ReputationDecayUpdated {
+    pub collection: Pubkey,
+    pub decay_half_life: i64,
+}
+
+/// Event emitted when the ATOM summary is mirrored into AgentAccount
+#[event]
+pub struct ReputationSynced {
+    pub asset: Pubkey,
+    pub trust_tier: u8,
+    pub feedback_count: u64,
+    pub slot: u64,
+}
+
+/// Event emitted after ResponseAppended when the response replies to an earlier response
+/// response_index is the agent-wide index of the new response (new_response_count - 1).
+#[event]
+pub struct ResponseThreaded {
+    pub asset: Pubkey,
+    pub response_index: u64,
+    pub parent_response_index: u64,
+}
+
+/// Emitted alongside ResponseAppended when the responder is the owner or an operator
+#[event]
+pub struct OfficialResponse {
+    pub asset: Pubkey,
+    pub response_index: u64,
+    pub responder: Pubkey,
+}
+
 /// Event emitted when response is appended to feedback
 /// SEAL v1: Uses seal_hash (on-chain computed) for feedback identification.
 #[event]
@@ -73,3 +168,138 @@ pub struct ResponseAppended {
     pub new_response_count: u64,
     pub response_uri: String,
 }
+
+/// Event emitted when a feedback aggregator is approved or removed
+#[event]
+pub struct FeedbackAggregatorUpdated {
+    pub collection: Pubkey,
+    pub aggregator: Pubkey,
+    pub active: bool,
+}
+
+/// Event emitted when an aggregator commits a feedback Merkle root
+#[event]
+pub struct FeedbackRootCommitted {
+    pub collection: Pubkey,
+    pub aggregator: Pubkey,
+    pub root: [u8; 32],
+    pub entry_count: u64,
+}
+
+/// Emitted alongside NewFeedback when the feedback was claimed from a committed root
+///
+/// The client of a claimed feedback is attested by `aggregator`, not by a client signature.
+#[event]
+pub struct FeedbackClaimed {
+    pub asset: Pubkey,
+    pub feedback_index: u64,
+    pub aggregator: Pubkey,
+    pub root: [u8; 32],
+    pub leaf: [u8; 32],
+}
+
+/// Event emitted when the registry authority registers a canonical tag
+#[event]
+pub struct CanonicalTagRegistered {
+    pub collection: Pubkey,
+    pub tag_id: u32,
+    pub tag_hash: [u8; 32],
+    pub tag: String,
+}
+
+/// Canonical ids of a feedback's tags (0 = empty or non-canonical), emitted after NewFeedback
+#[event]
+pub struct FeedbackTagsResolved {
+    pub asset: Pubkey,
+    pub feedback_index: u64,
+    pub tag1_id: u32,
+    pub tag2_id: u32,
+}
+
+/// Event emitted when a client commits hidden feedback
+#[event]
+pub struct FeedbackCommitted {
+    pub asset: Pubkey,
+    pub client_address: Pubkey,
+    pub commitment: [u8; 32],
+    pub reveal_after: i64,
+}
+
+/// Event emitted when the agent owner consents to an early reveal
+#[event]
+pub struct FeedbackRevealConsented {
+    pub asset: Pubkey,
+    pub client_address: Pubkey,
+    pub commitment: [u8; 32],
+}
+
+/// Emitted after NewFeedback when the feedback opened an earlier commitment
+#[event]
+pub struct FeedbackRevealed {
+    pub asset: Pubkey,
+    pub client_address: Pubkey,
+    pub commitment: [u8; 32],
+    pub feedback_index: u64,
+    pub committed_at: i64,
+}
+
+/// Event emitted when an agent's epoch checkpoint is written
+#[event]
+pub struct ReputationCheckpointed {
+    pub asset: Pubkey,
+    pub epoch: u64,
+    pub feedback_count: u64,
+    pub revoke_count: u64,
+    pub average_score: u8,
+}
+
+/// Emitted after NewFeedback with the feedback's per-dimension scores (255 = unrated)
+#[event]
+pub struct FeedbackDimensions {
+    pub asset: Pubkey,
+    pub feedback_index: u64,
+    pub dimensions: [u8; 4],
+    pub new_averages: [u8; 4],
+}
+
+/// Event emitted when decay_agent_reputation halves an agent's decayed average inputs
+#[event]
+pub struct AgentReputationDecayed {
+    pub asset: Pubkey,
+    pub decayed_weight: u64,
+    pub decayed_average_score: u8,
+}
+
+/// Event emitted when the agent flags a feedback (FLAG_REASON_*)
+#[event]
+pub struct FeedbackFlagged {
+    pub asset: Pubkey,
+    pub client_address: Pubkey,
+    pub feedback_index: u64,
+    /// SEAL v1: The seal_hash from the flagged feedback (for identification)
+    pub seal_hash: [u8; 32],
+    pub reason: u8,
+    pub reason_hash: [u8; 32],
+    pub flagged_by: Pubkey,
+}
+
+/// Event emitted when the client answers a flag on their feedback
+#[event]
+pub struct FeedbackCounterFlagged {
+    pub asset: Pubkey,
+    pub client_address: Pubkey,
+    pub feedback_index: u64,
+    pub counter_hash: [u8; 32],
+}
+
+/// Event emitted alongside NewFeedback for feedback signed by an EVM client
+#[event]
+pub struct EvmFeedbackRelayed {
+    pub asset: Pubkey,
+    /// Solana client key derived from evm_address (EvmFeedbackPermit::client)
+    pub client_address: Pubkey,
+    pub feedback_index: u64,
+    pub evm_address: [u8; 20],
+    pub chain_id: u64,
+    pub relayer: Pubkey,
+}
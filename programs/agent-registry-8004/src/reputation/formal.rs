@@ -7,7 +7,8 @@
 #![cfg(kani)]
 
 use super::chain::{
-    DOMAIN_FEEDBACK, DOMAIN_RESPONSE, DOMAIN_RESPONSE_LEAF_V1, DOMAIN_REVOKE, DOMAIN_REVOKE_LEAF_V1,
+    DOMAIN_FEEDBACK, DOMAIN_OFFICIAL_LEAF_V1, DOMAIN_REPLY_LEAF_V1, DOMAIN_RESPONSE,
+    DOMAIN_RESPONSE_LEAF_V1, DOMAIN_REVOKE, DOMAIN_REVOKE_LEAF_V1, DOMAIN_ROOT_FEEDBACK_LEAF_V1,
 };
 use super::seal::{DOMAIN_LEAF_V1, DOMAIN_SEAL_V1};
 
@@ -39,6 +40,13 @@ fn proof_domain_separators_are_distinct() {
     assert_ne!(DOMAIN_FEEDBACK, DOMAIN_REVOKE);
     assert_ne!(DOMAIN_RESPONSE, DOMAIN_REVOKE);
     assert_ne!(DOMAIN_RESPONSE_LEAF_V1, DOMAIN_REVOKE_LEAF_V1);
+    assert_ne!(DOMAIN_REPLY_LEAF_V1, DOMAIN_RESPONSE_LEAF_V1);
+    assert_ne!(DOMAIN_REPLY_LEAF_V1, DOMAIN_REVOKE_LEAF_V1);
+    assert_ne!(DOMAIN_OFFICIAL_LEAF_V1, DOMAIN_RESPONSE_LEAF_V1);
+    assert_ne!(DOMAIN_OFFICIAL_LEAF_V1, DOMAIN_REPLY_LEAF_V1);
+    assert_ne!(DOMAIN_OFFICIAL_LEAF_V1, DOMAIN_REVOKE_LEAF_V1);
+    assert_ne!(DOMAIN_ROOT_FEEDBACK_LEAF_V1, DOMAIN_LEAF_V1);
+    assert_ne!(DOMAIN_ROOT_FEEDBACK_LEAF_V1, DOMAIN_RESPONSE_LEAF_V1);
     assert_ne!(DOMAIN_LEAF_V1, DOMAIN_RESPONSE_LEAF_V1);
     assert_ne!(DOMAIN_LEAF_V1, DOMAIN_REVOKE_LEAF_V1);
     assert_ne!(DOMAIN_SEAL_V1, DOMAIN_LEAF_V1);
@@ -1,19 +1,1080 @@
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::keccak;
+use anchor_lang::system_program;
+use anchor_spl::token_interface::{transfer_checked, TransferChecked};
 
 use super::chain::{
-    chain_hash, compute_response_leaf, compute_revoke_leaf,
-    DOMAIN_FEEDBACK, DOMAIN_RESPONSE, DOMAIN_REVOKE,
+    chain_hash, compute_official_leaf, compute_reply_leaf, compute_response_leaf,
+    compute_revoke_leaf, compute_root_feedback_leaf, DOMAIN_FEEDBACK, DOMAIN_RESPONSE,
+    DOMAIN_REVOKE,
 };
 use super::seal::{compute_feedback_leaf_v1, compute_seal_hash};
 use super::contexts::{*, ATOM_CPI_AUTHORITY_SEED};
 use super::events::*;
 use super::state::*;
-use crate::core_asset::get_core_owner;
+use crate::constants::{
+    SEED_AGENT_OPERATOR, SEED_CLIENT_INDEX, SEED_DIMENSION_REPUTATION, SEED_FEEDBACK_BOND,
+    SEED_FEEDBACK_CLAIM, SEED_FEEDBACK_SCORE, SEED_REPUTATION_CHECKPOINT, SEED_TAG_REPUTATION,
+};
+use crate::core_asset::{get_core_owner, verify_owner_or_operator};
 use crate::error::RegistryError;
+use crate::identity::allowlist::verify_sorted_proof;
+use crate::identity::instructions::{
+    create_pda_account, verify_ed25519_signature, verify_evm_digest,
+};
+use crate::identity::schema::validate_uri_scheme;
+use crate::identity::state::{AgentAccount, AgentOperator, RegistryConfig};
+
+pub fn give_feedback(
+    ctx: Context<GiveFeedback>,
+    value: i128,
+    value_decimals: u8,
+    score: Option<u8>,
+    feedback_file_hash: Option<[u8; 32]>,
+    tag1: String,
+    tag2: String,
+    endpoint: String,
+    feedback_uri: String,
+) -> Result<()> {
+    require!(
+        !ctx.accounts.agent_account.feedback_auth_required,
+        RegistryError::FeedbackAuthRequired
+    );
+    give_feedback_inner(
+        ctx.accounts,
+        &ctx.bumps,
+        value,
+        value_decimals,
+        score,
+        feedback_file_hash,
+        tag1,
+        tag2,
+        endpoint,
+        feedback_uri,
+    )
+}
+
+/// Commit hidden feedback: only `FeedbackCommitment::compute(seal_hash, salt)` is stored
+pub fn give_feedback_committed(ctx: Context<CommitFeedback>, commitment: [u8; 32]) -> Result<()> {
+    require!(
+        !ctx.accounts.agent_account.feedback_auth_required,
+        RegistryError::FeedbackAuthRequired
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let reveal_after = now
+        .checked_add(ctx.accounts.registry_config.feedback_reveal_delay)
+        .ok_or(RegistryError::Overflow)?;
+
+    let record = &mut ctx.accounts.feedback_commitment;
+    record.asset = ctx.accounts.asset.key();
+    record.client = ctx.accounts.client.key();
+    record.commitment = commitment;
+    record.committed_at = now;
+    record.reveal_after = reveal_after;
+    record.owner_consent = false;
+    record.bump = ctx.bumps.feedback_commitment;
+
+    emit!(FeedbackCommitted {
+        asset: record.asset,
+        client_address: record.client,
+        commitment,
+        reveal_after,
+    });
+
+    Ok(())
+}
+
+/// Allow a committed feedback to be revealed before its delay (agent owner or operator)
+pub fn consent_feedback_reveal(ctx: Context<ConsentFeedbackReveal>) -> Result<()> {
+    verify_owner_or_operator(
+        &ctx.accounts.asset,
+        &ctx.accounts.owner.key(),
+        ctx.accounts.agent_operator.as_deref(),
+        AgentOperator::SCOPE_RESPONSES,
+    )?;
+
+    let record = &mut ctx.accounts.feedback_commitment;
+    record.owner_consent = true;
+
+    emit!(FeedbackRevealConsented {
+        asset: record.asset,
+        client_address: record.client,
+        commitment: record.commitment,
+    });
+
+    Ok(())
+}
+
+/// Open a committed feedback and record it like give_feedback (aggregates and ATOM)
+///
+/// Allowed once the registry's reveal delay has passed or the owner consented. The
+/// feedback gets its index at reveal time; the commitment account is closed to the client.
+pub fn reveal_feedback(
+    ctx: Context<RevealFeedback>,
+    value: i128,
+    value_decimals: u8,
+    score: Option<u8>,
+    feedback_file_hash: Option<[u8; 32]>,
+    tag1: String,
+    tag2: String,
+    endpoint: String,
+    feedback_uri: String,
+    salt: [u8; 32],
+) -> Result<()> {
+    let feedback = &ctx.accounts.feedback;
+    require!(
+        !feedback.agent_account.feedback_auth_required,
+        RegistryError::FeedbackAuthRequired
+    );
+
+    let seal_hash = compute_seal_hash(
+        value,
+        value_decimals,
+        score,
+        &tag1,
+        &tag2,
+        &endpoint,
+        &feedback_uri,
+        feedback_file_hash,
+    );
+    let record = &ctx.accounts.feedback_commitment;
+    require!(
+        record.asset == feedback.asset.key()
+            && record.client == feedback.client.key()
+            && record.commitment == FeedbackCommitment::compute(&seal_hash, &salt),
+        RegistryError::InvalidFeedbackCommitment
+    );
+    require!(
+        record.owner_consent || Clock::get()?.unix_timestamp >= record.reveal_after,
+        RegistryError::FeedbackRevealLocked
+    );
+    let commitment = record.commitment;
+    let committed_at = record.committed_at;
+    record.close(feedback.client.to_account_info())?;
+
+    let asset = feedback.asset.key();
+    let client = feedback.client.key();
+    let feedback_index = next_feedback_index(feedback)?;
+    give_feedback_inner(
+        &mut ctx.accounts.feedback,
+        &ctx.bumps.feedback,
+        value,
+        value_decimals,
+        score,
+        feedback_file_hash,
+        tag1,
+        tag2,
+        endpoint,
+        feedback_uri,
+    )?;
+
+    emit!(FeedbackRevealed {
+        asset,
+        client_address: client,
+        commitment,
+        feedback_index,
+        committed_at,
+    });
+
+    Ok(())
+}
+
+/// Give feedback with a per-dimension score vector alongside the overall score
+///
+/// Dimensions are accuracy, latency, cost and communication (0-100, 255 = unrated). They
+/// are not part of the SEAL v1 hash; FeedbackDimensions carries them next to NewFeedback
+/// and DimensionReputation keeps a running average per dimension.
+pub fn give_feedback_with_dimensions(
+    ctx: Context<GiveFeedbackWithDimensions>,
+    value: i128,
+    value_decimals: u8,
+    score: Option<u8>,
+    feedback_file_hash: Option<[u8; 32]>,
+    tag1: String,
+    tag2: String,
+    endpoint: String,
+    feedback_uri: String,
+    dimensions: [u8; SCORE_DIMENSIONS],
+) -> Result<()> {
+    let feedback = &ctx.accounts.feedback;
+    require!(
+        !feedback.agent_account.feedback_auth_required,
+        RegistryError::FeedbackAuthRequired
+    );
+    require!(
+        dimensions.iter().all(|&d| d <= 100 || d == DIMENSION_UNRATED),
+        RegistryError::InvalidDimensionScore
+    );
+
+    let dimension_info = ctx.accounts.dimension_reputation.to_account_info();
+    let payer = feedback.client.to_account_info();
+    let system_program_info = feedback.system_program.to_account_info();
+    let asset = feedback.asset.key();
+    let feedback_index = next_feedback_index(feedback)?;
+
+    give_feedback_inner(
+        &mut ctx.accounts.feedback,
+        &ctx.bumps.feedback,
+        value,
+        value_decimals,
+        score,
+        feedback_file_hash,
+        tag1,
+        tag2,
+        endpoint,
+        feedback_uri,
+    )?;
+
+    let (expected, bump) = Pubkey::find_program_address(
+        &[SEED_DIMENSION_REPUTATION, asset.as_ref()],
+        &crate::ID,
+    );
+    require!(
+        dimension_info.key() == expected,
+        RegistryError::InvalidDimensionReputation
+    );
+
+    let mut aggregate = if dimension_info.data_is_empty() {
+        create_pda_account(
+            &dimension_info,
+            &payer,
+            &system_program_info,
+            DimensionReputation::DISCRIMINATOR.len() + DimensionReputation::INIT_SPACE,
+            &[SEED_DIMENSION_REPUTATION, asset.as_ref(), &[bump]],
+        )?;
+        DimensionReputation {
+            asset,
+            counts: [0; SCORE_DIMENSIONS],
+            score_sums: [0; SCORE_DIMENSIONS],
+            averages: [0; SCORE_DIMENSIONS],
+            bump,
+        }
+    } else {
+        DimensionReputation::try_deserialize(&mut &dimension_info.try_borrow_data()?[..])?
+    };
+
+    for (i, &d) in dimensions.iter().enumerate() {
+        if d == DIMENSION_UNRATED {
+            continue;
+        }
+        aggregate.counts[i] = aggregate.counts[i]
+            .checked_add(1)
+            .ok_or(RegistryError::Overflow)?;
+        aggregate.score_sums[i] = aggregate.score_sums[i]
+            .checked_add(d as u64)
+            .ok_or(RegistryError::Overflow)?;
+        aggregate.averages[i] = (aggregate.score_sums[i] / aggregate.counts[i]) as u8;
+    }
+
+    let mut data = dimension_info.try_borrow_mut_data()?;
+    aggregate.try_serialize(&mut &mut data[..])?;
+
+    emit!(FeedbackDimensions {
+        asset,
+        feedback_index,
+        dimensions,
+        new_averages: aggregate.averages,
+    });
+
+    Ok(())
+}
+
+/// Amend one of the client's earlier feedback entries with a new version
+///
+/// The new version is appended like any feedback (new index, digest chain, ATOM update);
+/// FeedbackAmended records that it supersedes `original_feedback_index` so indexers can
+/// keep only the latest version.
+pub fn amend_feedback(
+    ctx: Context<GiveFeedback>,
+    value: i128,
+    value_decimals: u8,
+    score: Option<u8>,
+    feedback_file_hash: Option<[u8; 32]>,
+    tag1: String,
+    tag2: String,
+    endpoint: String,
+    feedback_uri: String,
+    original_feedback_index: u64,
+    original_seal_hash: [u8; 32],
+) -> Result<()> {
+    require!(
+        !ctx.accounts.agent_account.feedback_auth_required,
+        RegistryError::FeedbackAuthRequired
+    );
+    let new_feedback_index = next_feedback_index(ctx.accounts)?;
+    require!(
+        original_feedback_index < new_feedback_index,
+        RegistryError::InvalidFeedbackIndex
+    );
+
+    let asset = ctx.accounts.asset.key();
+    let client = ctx.accounts.client.key();
+    let new_seal_hash = compute_seal_hash(
+        value,
+        value_decimals,
+        score,
+        &tag1,
+        &tag2,
+        &endpoint,
+        &feedback_uri,
+        feedback_file_hash,
+    );
+
+    give_feedback_inner(
+        ctx.accounts,
+        &ctx.bumps,
+        value,
+        value_decimals,
+        score,
+        feedback_file_hash,
+        tag1,
+        tag2,
+        endpoint,
+        feedback_uri,
+    )?;
+
+    emit!(FeedbackAmended {
+        asset,
+        client_address: client,
+        original_feedback_index,
+        original_seal_hash,
+        superseded_by: new_feedback_index,
+        new_seal_hash,
+    });
+
+    Ok(())
+}
+
+/// Give feedback under an ERC-8004 FeedbackAuth signed by the agent owner or agent wallet
+///
+/// Checks client match, expiry and index limit, then introspects the preceding Ed25519
+/// instruction for a signature over `feedback_auth.message()`.
+pub fn give_feedback_with_auth(
+    ctx: Context<GiveFeedbackWithAuth>,
+    value: i128,
+    value_decimals: u8,
+    score: Option<u8>,
+    feedback_file_hash: Option<[u8; 32]>,
+    tag1: String,
+    tag2: String,
+    endpoint: String,
+    feedback_uri: String,
+    feedback_auth: FeedbackAuth,
+) -> Result<()> {
+    verify_feedback_auth(ctx.accounts, &feedback_auth)?;
+    give_feedback_inner(
+        &mut ctx.accounts.feedback,
+        &ctx.bumps.feedback,
+        value,
+        value_decimals,
+        score,
+        feedback_file_hash,
+        tag1,
+        tag2,
+        endpoint,
+        feedback_uri,
+    )
+}
+
+/// Give feedback backed by an SPL / Token-2022 payment from the client to the agent wallet
+///
+/// The transfer is performed here (transfer_checked) in the registry's payment mint and
+/// must meet min_feedback_payment; the amount is recorded in FeedbackPaid.
+pub fn give_feedback_paid(
+    ctx: Context<GiveFeedbackPaid>,
+    value: i128,
+    value_decimals: u8,
+    score: Option<u8>,
+    feedback_file_hash: Option<[u8; 32]>,
+    tag1: String,
+    tag2: String,
+    endpoint: String,
+    feedback_uri: String,
+    amount: u64,
+) -> Result<()> {
+    let feedback = &ctx.accounts.feedback;
+    require!(
+        !feedback.agent_account.feedback_auth_required,
+        RegistryError::FeedbackAuthRequired
+    );
+
+    let registry = &feedback.registry_config;
+    let payment_mint = registry.feedback_payment_mint;
+    require!(
+        payment_mint != Pubkey::default(),
+        RegistryError::InvalidFeedbackPayment
+    );
+    require!(
+        amount >= registry.min_feedback_payment,
+        RegistryError::FeedbackPaymentTooLow
+    );
+    let agent_wallet = feedback
+        .agent_account
+        .agent_wallet
+        .ok_or(RegistryError::InvalidFeedbackPayment)?;
+
+    let mint = &ctx.accounts.payment_mint;
+    let agent_token_account = &ctx.accounts.agent_token_account;
+
+    require!(
+        mint.key() == payment_mint
+            && agent_token_account.mint == payment_mint
+            && agent_token_account.owner == agent_wallet,
+        RegistryError::InvalidFeedbackPayment
+    );
+
+    transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.client_token_account.to_account_info(),
+                mint: mint.to_account_info(),
+                to: agent_token_account.to_account_info(),
+                authority: feedback.client.to_account_info(),
+            },
+        ),
+        amount,
+        mint.decimals,
+    )?;
+
+    let asset = feedback.asset.key();
+    let client = feedback.client.key();
+    let feedback_index = next_feedback_index(feedback)?;
+
+    give_feedback_inner(
+        &mut ctx.accounts.feedback,
+        &ctx.bumps.feedback,
+        value,
+        value_decimals,
+        score,
+        feedback_file_hash,
+        tag1,
+        tag2,
+        endpoint,
+        feedback_uri,
+    )?;
+
+    emit!(FeedbackPaid {
+        asset,
+        client_address: client,
+        feedback_index,
+        mint: payment_mint,
+        amount,
+        agent_wallet,
+    });
+
+    Ok(())
+}
+
+fn verify_feedback_auth(accounts: &GiveFeedbackWithAuth, auth: &FeedbackAuth) -> Result<()> {
+    let feedback = &accounts.feedback;
+    let agent = &feedback.agent_account;
+    require!(
+        auth.asset == agent.asset && auth.client_address == feedback.client.key(),
+        RegistryError::InvalidFeedbackAuth
+    );
+
+    let core_owner = get_core_owner(&feedback.asset)?;
+    require!(
+        auth.signer_address == core_owner || agent.agent_wallet == Some(auth.signer_address),
+        RegistryError::InvalidFeedbackAuth
+    );
+
+    require!(
+        Clock::get()?.unix_timestamp < auth.expiry,
+        RegistryError::FeedbackAuthExpired
+    );
+    require!(
+        next_feedback_index(feedback)? < auth.index_limit,
+        RegistryError::FeedbackAuthIndexLimitExceeded
+    );
+
+    verify_ed25519_signature(&accounts.instructions_sysvar, auth.signer_address, &auth.message())
+}
+
+/// Reject feedback from the agent's owner (live or cached), agent wallet or an active operator
+///
+/// The operator check is opt-in: it runs only when the client's AgentOperator PDA is passed,
+/// so callers built against the original give_feedback accounts keep working.
+fn check_not_self_feedback(
+    client: Pubkey,
+    agent: &AgentAccount,
+    asset_info: &AccountInfo,
+    client_operator: Option<&AccountInfo>,
+) -> Result<()> {
+    let core_owner = get_core_owner(asset_info)?;
+    require!(
+        client != core_owner && client != agent.owner && agent.agent_wallet != Some(client),
+        RegistryError::SelfFeedbackNotAllowed
+    );
+
+    let Some(operator_info) = client_operator else {
+        return Ok(());
+    };
+    let (expected, _) = Pubkey::find_program_address(
+        &[SEED_AGENT_OPERATOR, agent.asset.as_ref(), client.as_ref()],
+        &crate::ID,
+    );
+    require!(
+        operator_info.key() == expected,
+        RegistryError::SelfFeedbackNotAllowed
+    );
+
+    if *operator_info.owner == crate::ID && !operator_info.data_is_empty() {
+        let operator =
+            AgentOperator::try_deserialize(&mut &operator_info.try_borrow_data()?[..])?;
+        require!(
+            operator.granted_by != core_owner
+                || !operator.allows(0, Clock::get()?.unix_timestamp),
+            RegistryError::SelfFeedbackNotAllowed
+        );
+    }
+
+    Ok(())
+}
+
+/// Reject feedback fields outside the give_feedback limits (decimals, score, tags, URI, endpoint)
+fn validate_feedback_fields(
+    registry: &RegistryConfig,
+    value_decimals: u8,
+    score: Option<u8>,
+    tag1: &str,
+    tag2: &str,
+    endpoint: &str,
+    feedback_uri: &str,
+) -> Result<()> {
+    require!(value_decimals <= MAX_VALUE_DECIMALS, RegistryError::InvalidDecimals);
+    if let Some(s) = score {
+        require!(s <= 100, RegistryError::InvalidScore);
+    }
+    require!(tag1.len() <= MAX_TAG_LENGTH, RegistryError::TagTooLong);
+    require!(tag2.len() <= MAX_TAG_LENGTH, RegistryError::TagTooLong);
+    require!(
+        feedback_uri.len() <= MAX_URI_LENGTH,
+        RegistryError::UriTooLong
+    );
+    validate_uri_scheme(feedback_uri, registry.allowed_uri_schemes)?;
+    require!(
+        endpoint.len() <= MAX_ENDPOINT_LENGTH,
+        RegistryError::EndpointTooLong
+    );
+    Ok(())
+}
+
+/// Whether feedback may skip give_feedback's per-client accounts (batch, permits, claims)
+///
+/// Those paths carry no feedback auth, ClientIndex, cooldown, bond or CanonicalTag accounts,
+/// so the agent and registry must not require any of them.
+fn accepts_relayed_feedback(agent: &AgentAccount, registry: &RegistryConfig) -> bool {
+    !agent.feedback_auth_required
+        && registry.feedback_index_mode == RegistryConfig::FEEDBACK_INDEX_GLOBAL
+        && registry.feedback_cooldown_slots == 0
+        && registry.feedback_bond == 0
+        && !registry.strict_feedback_tags
+}
+
+/// Canonical ClientIndex PDA for this (asset, client) pair and its bump
+fn client_index_account<'info>(
+    accounts: &GiveFeedback<'info>,
+) -> Result<(AccountInfo<'info>, u8)> {
+    let info = accounts
+        .client_index
+        .as_ref()
+        .ok_or(RegistryError::InvalidClientIndex)?;
+    let (expected, bump) = Pubkey::find_program_address(
+        &[
+            SEED_CLIENT_INDEX,
+            accounts.asset.key().as_ref(),
+            accounts.client.key().as_ref(),
+        ],
+        &crate::ID,
+    );
+    require!(info.key() == expected, RegistryError::InvalidClientIndex);
+    Ok((info.to_account_info(), bump))
+}
+
+fn is_per_client_mode(accounts: &GiveFeedback) -> bool {
+    accounts.registry_config.feedback_index_mode == RegistryConfig::FEEDBACK_INDEX_PER_CLIENT
+}
+
+/// Index the next feedback from this client will receive (read-only)
+fn next_feedback_index(accounts: &GiveFeedback) -> Result<u64> {
+    if !is_per_client_mode(accounts) {
+        return Ok(accounts.agent_account.feedback_count);
+    }
+    let (info, _) = client_index_account(accounts)?;
+    if info.data_is_empty() {
+        return Ok(0);
+    }
+    let client_index = ClientIndex::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+    Ok(client_index.next_index)
+}
+
+/// Consume the next feedback index, creating the ClientIndex PDA on a client's first feedback
+///
+/// The ClientIndex is also maintained in global mode while a feedback cooldown is set,
+/// rejecting feedback filed within feedback_cooldown_slots of the client's previous one.
+fn assign_feedback_index(accounts: &GiveFeedback, slot: u64) -> Result<u64> {
+    let cooldown = accounts.registry_config.feedback_cooldown_slots;
+    if !is_per_client_mode(accounts) && cooldown == 0 {
+        return Ok(accounts.agent_account.feedback_count);
+    }
+    let (info, bump) = client_index_account(accounts)?;
+    let asset = accounts.asset.key();
+    let client = accounts.client.key();
+
+    let mut client_index = if info.data_is_empty() {
+        create_pda_account(
+            &info,
+            &accounts.client.to_account_info(),
+            &accounts.system_program.to_account_info(),
+            ClientIndex::DISCRIMINATOR.len() + ClientIndex::INIT_SPACE,
+            &[SEED_CLIENT_INDEX, asset.as_ref(), client.as_ref(), &[bump]],
+        )?;
+        ClientIndex {
+            asset,
+            client,
+            next_index: 0,
+            bump,
+            last_feedback_slot: 0,
+        }
+    } else {
+        ClientIndex::try_deserialize(&mut &info.try_borrow_data()?[..])?
+    };
+
+    if cooldown > 0 && client_index.next_index > 0 {
+        require!(
+            slot >= client_index.last_feedback_slot.saturating_add(cooldown),
+            RegistryError::FeedbackCooldownActive
+        );
+    }
+
+    let client_feedback_index = client_index.next_index;
+    client_index.next_index = client_feedback_index
+        .checked_add(1)
+        .ok_or(RegistryError::Overflow)?;
+    client_index.last_feedback_slot = slot;
+    let mut data = info.try_borrow_mut_data()?;
+    client_index.try_serialize(&mut &mut data[..])?;
+
+    if is_per_client_mode(accounts) {
+        Ok(client_feedback_index)
+    } else {
+        Ok(accounts.agent_account.feedback_count)
+    }
+}
+
+/// Lock the registry's feedback bond from the client into a new FeedbackBond PDA
+fn lock_feedback_bond(accounts: &GiveFeedback, feedback_index: u64) -> Result<()> {
+    let registry = &accounts.registry_config;
+    let amount = registry.feedback_bond;
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let bond_info = accounts
+        .feedback_bond
+        .as_ref()
+        .ok_or(RegistryError::InvalidFeedbackBond)?
+        .to_account_info();
+    let asset = accounts.asset.key();
+    let client = accounts.client.key();
+    let index_bytes = feedback_index.to_le_bytes();
+    let (expected, bump) = Pubkey::find_program_address(
+        &[SEED_FEEDBACK_BOND, asset.as_ref(), client.as_ref(), index_bytes.as_ref()],
+        &crate::ID,
+    );
+    require!(bond_info.key() == expected, RegistryError::InvalidFeedbackBond);
+
+    let client_info = accounts.client.to_account_info();
+    let system_program_info = accounts.system_program.to_account_info();
+    create_pda_account(
+        &bond_info,
+        &client_info,
+        &system_program_info,
+        FeedbackBond::DISCRIMINATOR.len() + FeedbackBond::INIT_SPACE,
+        &[
+            SEED_FEEDBACK_BOND,
+            asset.as_ref(),
+            client.as_ref(),
+            index_bytes.as_ref(),
+            &[bump],
+        ],
+    )?;
+    system_program::transfer(
+        CpiContext::new(
+            system_program_info,
+            system_program::Transfer {
+                from: client_info,
+                to: bond_info.clone(),
+            },
+        ),
+        amount,
+    )?;
+
+    let unlocks_at = Clock::get()?
+        .unix_timestamp
+        .checked_add(registry.feedback_bond_window)
+        .ok_or(RegistryError::Overflow)?;
+    let bond = FeedbackBond {
+        asset,
+        client,
+        collection: registry.collection,
+        feedback_index,
+        amount,
+        unlocks_at,
+        disputed: false,
+        bump,
+    };
+    let mut data = bond_info.try_borrow_mut_data()?;
+    bond.try_serialize(&mut &mut data[..])?;
+
+    emit!(FeedbackBondLocked {
+        asset,
+        client_address: client,
+        feedback_index,
+        amount,
+        unlocks_at,
+    });
+
+    Ok(())
+}
+
+/// Create the FeedbackScore record of a scored feedback when its PDA is passed
+///
+/// Returns whether the record was written; only recorded scores enter the AgentAccount
+/// aggregates, so revoke_feedback can always take back exactly what was added.
+fn write_feedback_score(
+    accounts: &GiveFeedback,
+    feedback_index: u64,
+    seal_hash: &[u8; 32],
+    tag_hash: &[u8; 32],
+    score: u8,
+) -> Result<bool> {
+    let Some(record_info) = accounts.feedback_score.as_ref() else {
+        return Ok(false);
+    };
+    let record_info = record_info.to_account_info();
+    let asset = accounts.asset.key();
+    let client = accounts.client.key();
+    let index_bytes = feedback_index.to_le_bytes();
+    let (expected, bump) = Pubkey::find_program_address(
+        &[SEED_FEEDBACK_SCORE, asset.as_ref(), client.as_ref(), index_bytes.as_ref()],
+        &crate::ID,
+    );
+    require!(record_info.key() == expected, RegistryError::InvalidFeedbackScore);
+
+    create_pda_account(
+        &record_info,
+        &accounts.client.to_account_info(),
+        &accounts.system_program.to_account_info(),
+        FeedbackScore::DISCRIMINATOR.len() + FeedbackScore::INIT_SPACE,
+        &[
+            SEED_FEEDBACK_SCORE,
+            asset.as_ref(),
+            client.as_ref(),
+            index_bytes.as_ref(),
+            &[bump],
+        ],
+    )?;
+
+    let record = FeedbackScore {
+        asset,
+        client,
+        feedback_index,
+        seal_hash: *seal_hash,
+        tag_hash: *tag_hash,
+        score,
+        decay_halvings: accounts.agent_account.decay_halvings,
+        bump,
+    };
+    let mut data = record_info.try_borrow_mut_data()?;
+    record.try_serialize(&mut &mut data[..])?;
+
+    Ok(true)
+}
+
+/// Read and close the FeedbackScore record of a revoked feedback (None when never created)
+///
+/// The record must belong to the cited seal hash; its rent goes back to the client.
+fn take_feedback_score<'info>(
+    record_info: &AccountInfo<'info>,
+    client_info: &AccountInfo<'info>,
+    seal_hash: &[u8; 32],
+) -> Result<Option<FeedbackScore>> {
+    if record_info.data_is_empty() {
+        return Ok(None);
+    }
+    require!(
+        *record_info.owner == crate::ID,
+        RegistryError::InvalidFeedbackScore
+    );
+    let record = FeedbackScore::try_deserialize(&mut &record_info.try_borrow_data()?[..])?;
+    require!(
+        record.seal_hash == *seal_hash,
+        RegistryError::InvalidFeedbackScore
+    );
+
+    let client_balance = client_info
+        .lamports()
+        .checked_add(record_info.lamports())
+        .ok_or(RegistryError::Overflow)?;
+    **client_info.try_borrow_mut_lamports()? = client_balance;
+    **record_info.try_borrow_mut_lamports()? = 0;
+    record_info.assign(&system_program::ID);
+    record_info.realloc(0, false)?;
+
+    Ok(Some(record))
+}
+
+/// Snapshot the agent's counters into its ReputationCheckpoint for the current epoch
+///
+/// The PDA is created on the first write of the epoch and overwritten by later writes,
+/// so it holds the state as of the agent's last checkpointed change in that epoch.
+fn write_reputation_checkpoint<'info>(
+    checkpoint_info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program_info: &AccountInfo<'info>,
+    agent: &AgentAccount,
+    clock: &Clock,
+) -> Result<()> {
+    let epoch = clock.epoch.to_le_bytes();
+    let (expected, bump) = Pubkey::find_program_address(
+        &[SEED_REPUTATION_CHECKPOINT, agent.asset.as_ref(), epoch.as_ref()],
+        &crate::ID,
+    );
+    require!(
+        checkpoint_info.key() == expected,
+        RegistryError::InvalidReputationCheckpoint
+    );
+
+    if checkpoint_info.data_is_empty() {
+        create_pda_account(
+            checkpoint_info,
+            payer,
+            system_program_info,
+            ReputationCheckpoint::DISCRIMINATOR.len() + ReputationCheckpoint::INIT_SPACE,
+            &[SEED_REPUTATION_CHECKPOINT, agent.asset.as_ref(), epoch.as_ref(), &[bump]],
+        )?;
+    }
+
+    let checkpoint = ReputationCheckpoint {
+        asset: agent.asset,
+        epoch: clock.epoch,
+        feedback_count: agent.feedback_count,
+        revoke_count: agent.revoke_count,
+        scored_count: agent.scored_count,
+        average_score: agent.average_score(),
+        slot: clock.slot,
+        bump,
+    };
+    let mut data = checkpoint_info.try_borrow_mut_data()?;
+    checkpoint.try_serialize(&mut &mut data[..])?;
+
+    emit!(ReputationCheckpointed {
+        asset: checkpoint.asset,
+        epoch: checkpoint.epoch,
+        feedback_count: checkpoint.feedback_count,
+        revoke_count: checkpoint.revoke_count,
+        average_score: checkpoint.average_score,
+    });
+
+    Ok(())
+}
+
+/// Create a TagFeedbackIndex page for (asset, tag_hash)
+///
+/// Permissionless: pages only ever hold indices appended by give_feedback, so anyone may
+/// fund one. Page N > 0 requires page N - 1 to be full.
+pub fn init_tag_feedback_index(
+    ctx: Context<InitTagFeedbackIndex>,
+    asset: Pubkey,
+    tag_hash: [u8; 32],
+    page: u16,
+) -> Result<()> {
+    if page > 0 {
+        let previous = ctx
+            .accounts
+            .previous_page
+            .as_ref()
+            .ok_or(RegistryError::TagFeedbackIndexMismatch)?;
+        require!(previous.is_full(), RegistryError::TagFeedbackIndexMismatch);
+    }
+
+    let index = &mut ctx.accounts.tag_feedback_index;
+    index.asset = asset;
+    index.tag_hash = tag_hash;
+    index.page = page;
+    index.bump = ctx.bumps.tag_feedback_index;
+    index.entries = Vec::new();
+
+    msg!("Tag feedback index page {} created for {}", page, asset);
+
+    Ok(())
+}
+
+/// Write the current epoch's checkpoint without giving feedback (e.g. after revocations)
+pub fn checkpoint_reputation(ctx: Context<CheckpointReputation>) -> Result<()> {
+    write_reputation_checkpoint(
+        &ctx.accounts.reputation_checkpoint.to_account_info(),
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.agent_account,
+        &Clock::get()?,
+    )
+}
+
+/// Canonical id of a feedback tag (0 when empty or, outside strict mode, non-canonical)
+fn resolve_canonical_tag(
+    registry: &RegistryConfig,
+    tag: &str,
+    canonical_tag: Option<&CanonicalTag>,
+) -> Result<u32> {
+    if tag.is_empty() {
+        return Ok(0);
+    }
+    match canonical_tag {
+        Some(canonical) => {
+            require!(
+                canonical.collection == registry.collection
+                    && canonical.tag_hash == keccak::hash(tag.as_bytes()).0,
+                RegistryError::InvalidCanonicalTag
+            );
+            Ok(canonical.tag_id)
+        }
+        None => {
+            require!(!registry.strict_feedback_tags, RegistryError::NonCanonicalTag);
+            Ok(0)
+        }
+    }
+}
+
+/// Register a canonical feedback tag with the next registry tag id
+pub fn register_canonical_tag(
+    ctx: Context<RegisterCanonicalTag>,
+    tag: String,
+    tag_hash: [u8; 32],
+) -> Result<()> {
+    require!(
+        !tag.is_empty() && tag.len() <= MAX_TAG_LENGTH,
+        RegistryError::TagTooLong
+    );
+    require!(
+        tag_hash == keccak::hash(tag.as_bytes()).0,
+        RegistryError::InvalidCanonicalTag
+    );
+
+    let collection = ctx.accounts.registry_config.collection;
+    let tag_registry = &mut ctx.accounts.tag_registry;
+    tag_registry.collection = collection;
+    tag_registry.bump = ctx.bumps.tag_registry;
+    tag_registry.tag_count = tag_registry
+        .tag_count
+        .checked_add(1)
+        .ok_or(RegistryError::Overflow)?;
+    let tag_id = tag_registry.tag_count;
+
+    let canonical = &mut ctx.accounts.canonical_tag;
+    canonical.collection = collection;
+    canonical.tag_id = tag_id;
+    canonical.tag_hash = tag_hash;
+    canonical.tag = tag.clone();
+    canonical.bump = ctx.bumps.canonical_tag;
+
+    emit!(CanonicalTagRegistered {
+        collection,
+        tag_id,
+        tag_hash,
+        tag,
+    });
+
+    Ok(())
+}
+
+/// Add (or, on revocation, remove) a score in the agent's TagReputation for `tag_hash`
+///
+/// The PDA is created on the first scored feedback under the tag; revocations against a
+/// missing aggregate are ignored.
+fn update_tag_reputation<'info>(
+    tag_reputation_info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program_info: &AccountInfo<'info>,
+    agent: &AgentAccount,
+    tag_hash: [u8; 32],
+    score: u8,
+    revoked: bool,
+) -> Result<()> {
+    let agent_id = agent.agent_id.to_le_bytes();
+    let (expected, bump) = Pubkey::find_program_address(
+        &[SEED_TAG_REPUTATION, agent_id.as_ref(), tag_hash.as_ref()],
+        &crate::ID,
+    );
+    require!(
+        tag_reputation_info.key() == expected,
+        RegistryError::InvalidTagReputation
+    );
+
+    let mut tag_reputation = if tag_reputation_info.data_is_empty() {
+        if revoked {
+            return Ok(());
+        }
+        create_pda_account(
+            tag_reputation_info,
+            payer,
+            system_program_info,
+            TagReputation::DISCRIMINATOR.len() + TagReputation::INIT_SPACE,
+            &[SEED_TAG_REPUTATION, agent_id.as_ref(), tag_hash.as_ref(), &[bump]],
+        )?;
+        TagReputation {
+            asset: agent.asset,
+            agent_id: agent.agent_id,
+            tag_hash,
+            feedback_count: 0,
+            score_sum: 0,
+            average_score: 0,
+            last_decay_at: Clock::get()?.unix_timestamp,
+            bump,
+        }
+    } else {
+        TagReputation::try_deserialize(&mut &tag_reputation_info.try_borrow_data()?[..])?
+    };
 
-pub fn give_feedback(
-    ctx: Context<GiveFeedback>,
+    if revoked {
+        tag_reputation.feedback_count = tag_reputation.feedback_count.saturating_sub(1);
+        tag_reputation.score_sum = tag_reputation.score_sum.saturating_sub(score as u64);
+    } else {
+        tag_reputation.feedback_count = tag_reputation
+            .feedback_count
+            .checked_add(1)
+            .ok_or(RegistryError::Overflow)?;
+        tag_reputation.score_sum = tag_reputation
+            .score_sum
+            .checked_add(score as u64)
+            .ok_or(RegistryError::Overflow)?;
+    }
+    tag_reputation.average_score = tag_reputation
+        .score_sum
+        .checked_div(tag_reputation.feedback_count)
+        .unwrap_or(0) as u8;
+
+    let mut data = tag_reputation_info.try_borrow_mut_data()?;
+    tag_reputation.try_serialize(&mut &mut data[..])?;
+
+    emit!(TagReputationUpdated {
+        asset: agent.asset,
+        tag_hash,
+        feedback_count: tag_reputation.feedback_count,
+        score_sum: tag_reputation.score_sum,
+        average_score: tag_reputation.average_score,
+    });
+
+    Ok(())
+}
+
+fn give_feedback_inner<'info>(
+    accounts: &mut GiveFeedback<'info>,
+    bumps: &GiveFeedbackBumps,
     value: i128,
     value_decimals: u8,
     score: Option<u8>,
@@ -23,37 +1084,46 @@ pub fn give_feedback(
     endpoint: String,
     feedback_uri: String,
 ) -> Result<()> {
-    let core_owner = get_core_owner(&ctx.accounts.asset)?;
-    require!(
-        core_owner != ctx.accounts.client.key(),
-        RegistryError::SelfFeedbackNotAllowed
-    );
-
-    require!(value_decimals <= MAX_VALUE_DECIMALS, RegistryError::InvalidDecimals);
-    if let Some(s) = score {
-        require!(s <= 100, RegistryError::InvalidScore);
+    if !accounts.registry_config.allow_self_feedback {
+        check_not_self_feedback(
+            accounts.client.key(),
+            &accounts.agent_account,
+            &accounts.asset,
+            accounts.client_operator.as_deref(),
+        )?;
     }
-    require!(tag1.len() <= MAX_TAG_LENGTH, RegistryError::TagTooLong);
-    require!(tag2.len() <= MAX_TAG_LENGTH, RegistryError::TagTooLong);
-    require!(
-        feedback_uri.len() <= MAX_URI_LENGTH,
-        RegistryError::UriTooLong
-    );
-    require!(
-        endpoint.len() <= MAX_ENDPOINT_LENGTH,
-        RegistryError::EndpointTooLong
-    );
 
-    let asset = ctx.accounts.asset.key();
+    validate_feedback_fields(
+        &accounts.registry_config,
+        value_decimals,
+        score,
+        &tag1,
+        &tag2,
+        &endpoint,
+        &feedback_uri,
+    )?;
+    let tag1_id = resolve_canonical_tag(
+        &accounts.registry_config,
+        &tag1,
+        accounts.canonical_tag1.as_deref(),
+    )?;
+    let tag2_id = resolve_canonical_tag(
+        &accounts.registry_config,
+        &tag2,
+        accounts.canonical_tag2.as_deref(),
+    )?;
+    let has_tags = !tag1.is_empty() || !tag2.is_empty();
 
-    let atom_enabled = ctx.accounts.agent_account.atom_enabled;
+    let asset = accounts.asset.key();
+
+    let atom_enabled = accounts.agent_account.atom_enabled;
     let mut is_atom_initialized = false;
 
     // Check if ATOM stats are initialized (when atom_enabled)
     // NOTE: If atom_enabled but stats not initialized, feedback still works but without ATOM scoring
     // This prevents sellers from blocking all feedback by enabling ATOM but never initializing stats
     if atom_enabled {
-        if let Some(atom_stats) = ctx.accounts.atom_stats.as_ref() {
+        if let Some(atom_stats) = accounts.atom_stats.as_ref() {
             // SECURITY: Validate that atom_stats is the correct PDA for this asset
             let (expected_atom_stats, _bump) = Pubkey::find_program_address(
                 &[b"atom_stats", asset.as_ref()],
@@ -72,23 +1142,19 @@ pub fn give_feedback(
     }
 
     let update_result = if let Some(s) = score.filter(|_| is_atom_initialized) {
-        let atom_config = ctx
-            .accounts
+        let atom_config = accounts
             .atom_config
             .as_ref()
             .ok_or(RegistryError::InvalidProgram)?;
-        let atom_engine_program = ctx
-            .accounts
+        let atom_engine_program = accounts
             .atom_engine_program
             .as_ref()
             .ok_or(RegistryError::InvalidProgram)?;
-        let registry_authority = ctx
-            .accounts
+        let registry_authority = accounts
             .registry_authority
             .as_ref()
             .ok_or(RegistryError::InvalidProgram)?;
-        let atom_stats_info = ctx
-            .accounts
+        let atom_stats_info = accounts
             .atom_stats
             .as_ref()
             .ok_or(RegistryError::AtomStatsNotInitialized)?
@@ -99,20 +1165,19 @@ pub fn give_feedback(
             RegistryError::InvalidProgram
         );
 
-        let client_hash = keccak::hash(ctx.accounts.client.key().as_ref());
+        let client_hash = keccak::hash(accounts.client.key().as_ref());
 
         let cpi_accounts = atom_engine::cpi::accounts::UpdateStats {
-            payer: ctx.accounts.client.to_account_info(),
-            asset: ctx.accounts.asset.to_account_info(),
-            collection: ctx.accounts.collection.to_account_info(),
+            payer: accounts.client.to_account_info(),
+            asset: accounts.asset.to_account_info(),
+            collection: accounts.collection.to_account_info(),
             config: atom_config.to_account_info(),
             stats: atom_stats_info,
             registry_authority: registry_authority.to_account_info(),
-            system_program: ctx.accounts.system_program.to_account_info(),
+            system_program: accounts.system_program.to_account_info(),
         };
 
-        let bump = ctx
-            .bumps
+        let bump = bumps
             .registry_authority
             .ok_or(RegistryError::InvalidProgram)?;
         let signer_seeds: &[&[&[u8]]] = &[&[ATOM_CPI_AUTHORITY_SEED, &[bump]]];
@@ -136,10 +1201,11 @@ pub fn give_feedback(
         }
     };
 
-    let slot = Clock::get()?.slot;
-    let client = ctx.accounts.client.key();
-    let agent = &mut ctx.accounts.agent_account;
-    let feedback_index = agent.feedback_count;
+    let clock = Clock::get()?;
+    let slot = clock.slot;
+    let client = accounts.client.key();
+    let feedback_index = assign_feedback_index(accounts, slot)?;
+    lock_feedback_bond(accounts, feedback_index)?;
 
     // SEAL v1: Compute content hash on-chain (trustless)
     let seal_hash = compute_seal_hash(
@@ -153,37 +1219,475 @@ pub fn give_feedback(
         feedback_file_hash,
     );
 
+    if let Some(s) = score {
+        let tag_reputation = accounts
+            .tag_reputation
+            .as_ref()
+            .filter(|_| !tag1.is_empty())
+            .map(|info| info.to_account_info());
+        let tag_hash = match tag_reputation {
+            Some(_) => keccak::hash(tag1.as_bytes()).0,
+            None => [0u8; 32],
+        };
+        if write_feedback_score(accounts, feedback_index, &seal_hash, &tag_hash, s)? {
+            accounts.agent_account.record_score(s, clock.unix_timestamp);
+            if let Some(tag_reputation) = tag_reputation {
+                update_tag_reputation(
+                    &tag_reputation,
+                    &accounts.client.to_account_info(),
+                    &accounts.system_program.to_account_info(),
+                    &accounts.agent_account,
+                    tag_hash,
+                    s,
+                    false,
+                )?;
+            }
+        } else {
+            // A tag aggregate without a FeedbackScore could never be revoked
+            require!(tag_reputation.is_none(), RegistryError::InvalidFeedbackScore);
+        }
+    }
+
     // SEAL v1: Compute leaf with domain separator
     let asset_bytes = asset.to_bytes();
     let client_bytes = client.to_bytes();
     let leaf = compute_feedback_leaf_v1(
-        &asset_bytes,
-        &client_bytes,
+        &asset_bytes,
+        &client_bytes,
+        feedback_index,
+        &seal_hash,
+        slot,
+    );
+
+    let agent = &mut accounts.agent_account;
+    agent.feedback_digest = chain_hash(&agent.feedback_digest, DOMAIN_FEEDBACK, &leaf);
+    agent.feedback_count = agent.feedback_count.checked_add(1).ok_or(RegistryError::Overflow)?;
+    agent.last_feedback_at = clock.unix_timestamp;
+
+    if accounts.tag_feedback_index.is_some() {
+        require!(
+            feedback_index < next_feedback_index(accounts)?,
+            RegistryError::InvalidFeedbackIndex
+        );
+    }
+    if let Some(index) = accounts.tag_feedback_index.as_deref_mut() {
+        require!(
+            !tag1.is_empty()
+                && index.asset == asset
+                && index.tag_hash == keccak::hash(tag1.as_bytes()).0,
+            RegistryError::TagFeedbackIndexMismatch
+        );
+        require!(!index.is_full(), RegistryError::TagFeedbackIndexFull);
+        index.entries.push(TagFeedbackEntry {
+            client,
+            feedback_index,
+        });
+    }
+
+    emit!(NewFeedback {
+        asset,
+        client_address: client,
+        feedback_index,
+        slot,
+        value,
+        value_decimals,
+        score,
+        feedback_file_hash,
+        seal_hash,
+        atom_enabled: is_atom_initialized && score.is_some(),
+        new_trust_tier: update_result.trust_tier,
+        new_quality_score: update_result.quality_score,
+        new_confidence: update_result.confidence,
+        new_risk_score: update_result.risk_score,
+        new_diversity_ratio: update_result.diversity_ratio,
+        is_unique_client: update_result.hll_changed,
+        new_feedback_digest: agent.feedback_digest,
+        new_feedback_count: agent.feedback_count,
+        tag1,
+        tag2,
+        endpoint,
+        feedback_uri,
+    });
+
+    if has_tags {
+        emit!(FeedbackTagsResolved {
+            asset,
+            feedback_index,
+            tag1_id,
+            tag2_id,
+        });
+    }
+
+    if let Some(checkpoint) = accounts.reputation_checkpoint.as_ref() {
+        write_reputation_checkpoint(
+            &checkpoint.to_account_info(),
+            &accounts.client.to_account_info(),
+            &accounts.system_program.to_account_info(),
+            &accounts.agent_account,
+            &clock,
+        )?;
+    }
+
+    msg!(
+        "Feedback #{} created: asset={}, client={}, score={:?}, atom_enabled={}, tier={}",
+        feedback_index,
+        asset,
+        client,
+        score,
+        is_atom_initialized && score.is_some(),
+        update_result.trust_tier
+    );
+
+    Ok(())
+}
+
+/// Submit scored feedback for several agents in one instruction (aggregators)
+///
+/// remaining_accounts: BATCH_ACCOUNTS_PER_ENTRY accounts per entry, in entry order. Entries
+/// skip ATOM scoring and are limited to registries using global indexes without cooldown
+/// or bonds. Each advances its agent's digest chain only: without a FeedbackScore record
+/// the scores stay out of the AgentAccount and TagReputation aggregates.
+pub fn give_feedback_batch<'info>(
+    ctx: Context<'_, '_, 'info, 'info, GiveFeedbackBatch<'info>>,
+    entries: Vec<BatchFeedbackEntry>,
+) -> Result<()> {
+    require!(
+        !entries.is_empty() && entries.len() <= MAX_BATCH_FEEDBACK,
+        RegistryError::InvalidBatch
+    );
+    require!(
+        ctx.remaining_accounts.len() == entries.len() * BATCH_ACCOUNTS_PER_ENTRY,
+        RegistryError::InvalidBatch
+    );
+
+    let client = ctx.accounts.client.key();
+    let clock = Clock::get()?;
+
+    let groups = ctx.remaining_accounts.chunks(BATCH_ACCOUNTS_PER_ENTRY);
+    for (entry, accounts) in entries.into_iter().zip(groups) {
+        let (agent_info, asset_info, registry_info) = (&accounts[0], &accounts[1], &accounts[2]);
+        let asset = asset_info.key();
+
+        let mut agent = Account::<AgentAccount>::try_from(agent_info)?;
+        let expected_agent =
+            Pubkey::create_program_address(&[b"agent", asset.as_ref(), &[agent.bump]], &crate::ID)
+                .map_err(|_| RegistryError::InvalidAsset)?;
+        require!(
+            agent_info.key() == expected_agent && agent.asset == asset,
+            RegistryError::InvalidAsset
+        );
+        require!(!agent.frozen, RegistryError::AgentFrozen);
+        require!(agent.accepts_feedback(), RegistryError::AgentInactive);
+
+        let registry = Account::<RegistryConfig>::try_from(registry_info)?;
+        let expected_registry = Pubkey::create_program_address(
+            &[b"registry_config", agent.collection.as_ref(), &[registry.bump]],
+            &crate::ID,
+        )
+        .map_err(|_| RegistryError::InvalidCollection)?;
+        require!(
+            registry_info.key() == expected_registry,
+            RegistryError::InvalidCollection
+        );
+        require!(
+            accepts_relayed_feedback(&agent, &registry),
+            RegistryError::BatchFeedbackUnsupported
+        );
+        if !registry.allow_self_feedback {
+            check_not_self_feedback(client, &agent, asset_info, Some(&accounts[3]))?;
+        }
+
+        validate_feedback_fields(
+            &registry,
+            0,
+            Some(entry.score),
+            &entry.tag1,
+            &entry.tag2,
+            "",
+            &entry.feedback_uri,
+        )?;
+
+        let value = entry.score as i128;
+        let score = Some(entry.score);
+        let seal_hash = compute_seal_hash(
+            value,
+            0,
+            score,
+            &entry.tag1,
+            &entry.tag2,
+            "",
+            &entry.feedback_uri,
+            entry.feedback_file_hash,
+        );
+        let feedback_index = agent.feedback_count;
+        let leaf = compute_feedback_leaf_v1(
+            &asset.to_bytes(),
+            &client.to_bytes(),
+            feedback_index,
+            &seal_hash,
+            clock.slot,
+        );
+        agent.feedback_digest = chain_hash(&agent.feedback_digest, DOMAIN_FEEDBACK, &leaf);
+        agent.feedback_count = agent
+            .feedback_count
+            .checked_add(1)
+            .ok_or(RegistryError::Overflow)?;
+        agent.last_feedback_at = clock.unix_timestamp;
+
+        emit!(NewFeedback {
+            asset,
+            client_address: client,
+            feedback_index,
+            slot: clock.slot,
+            value,
+            value_decimals: 0,
+            score,
+            feedback_file_hash: entry.feedback_file_hash,
+            seal_hash,
+            atom_enabled: false,
+            new_trust_tier: 0,
+            new_quality_score: 0,
+            new_confidence: 0,
+            new_risk_score: 0,
+            new_diversity_ratio: 0,
+            is_unique_client: false,
+            new_feedback_digest: agent.feedback_digest,
+            new_feedback_count: agent.feedback_count,
+            tag1: entry.tag1,
+            tag2: entry.tag2,
+            endpoint: String::new(),
+            feedback_uri: entry.feedback_uri,
+        });
+
+        agent.exit(&crate::ID)?;
+    }
+
+    Ok(())
+}
+
+/// Record feedback signed off-chain by the client and submitted by a relayer
+///
+/// The client signs the permit over the on-chain SEAL hash of the feedback, so every field
+/// is bound; nonces are sequential per client. Like give_feedback_batch, permits skip
+/// ATOM scoring and need a registry without per-client indexes, cooldown, bonds or strict tags.
+/// The score stays out of the AgentAccount and TagReputation aggregates (no FeedbackScore).
+pub fn give_feedback_with_permit(
+    ctx: Context<GiveFeedbackWithPermit>,
+    value: i128,
+    value_decimals: u8,
+    score: Option<u8>,
+    feedback_file_hash: Option<[u8; 32]>,
+    tag1: String,
+    tag2: String,
+    endpoint: String,
+    feedback_uri: String,
+    permit: FeedbackPermit,
+) -> Result<()> {
+    let registry = &ctx.accounts.registry_config;
+    require!(
+        accepts_relayed_feedback(&ctx.accounts.agent_account, registry),
+        RegistryError::RelayedFeedbackUnsupported
+    );
+    if !registry.allow_self_feedback {
+        check_not_self_feedback(
+            permit.client,
+            &ctx.accounts.agent_account,
+            &ctx.accounts.asset,
+            ctx.accounts.client_operator.as_deref(),
+        )?;
+    }
+
+    validate_feedback_fields(
+        registry,
+        value_decimals,
+        score,
+        &tag1,
+        &tag2,
+        &endpoint,
+        &feedback_uri,
+    )?;
+
+    let asset = ctx.accounts.asset.key();
+    let clock = Clock::get()?;
+    let seal_hash = compute_seal_hash(
+        value,
+        value_decimals,
+        score,
+        &tag1,
+        &tag2,
+        &endpoint,
+        &feedback_uri,
+        feedback_file_hash,
+    );
+
+    let nonce = &mut ctx.accounts.permit_nonce;
+    require!(
+        clock.unix_timestamp < permit.expiry && permit.nonce == nonce.next_nonce,
+        RegistryError::InvalidFeedbackPermit
+    );
+    verify_ed25519_signature(
+        &ctx.accounts.instructions_sysvar,
+        permit.client,
+        &permit.message(&asset, &seal_hash),
+    )?;
+    nonce.client = permit.client;
+    nonce.next_nonce = nonce.next_nonce.checked_add(1).ok_or(RegistryError::Overflow)?;
+    nonce.bump = ctx.bumps.permit_nonce;
+
+    let agent = &mut ctx.accounts.agent_account;
+    let feedback_index = agent.feedback_count;
+    let leaf = compute_feedback_leaf_v1(
+        &asset.to_bytes(),
+        &permit.client.to_bytes(),
+        feedback_index,
+        &seal_hash,
+        clock.slot,
+    );
+    agent.feedback_digest = chain_hash(&agent.feedback_digest, DOMAIN_FEEDBACK, &leaf);
+    agent.feedback_count = agent.feedback_count.checked_add(1).ok_or(RegistryError::Overflow)?;
+    agent.last_feedback_at = clock.unix_timestamp;
+
+    emit!(NewFeedback {
+        asset,
+        client_address: permit.client,
+        feedback_index,
+        slot: clock.slot,
+        value,
+        value_decimals,
+        score,
+        feedback_file_hash,
+        seal_hash,
+        atom_enabled: false,
+        new_trust_tier: 0,
+        new_quality_score: 0,
+        new_confidence: 0,
+        new_risk_score: 0,
+        new_diversity_ratio: 0,
+        is_unique_client: false,
+        new_feedback_digest: agent.feedback_digest,
+        new_feedback_count: agent.feedback_count,
+        tag1,
+        tag2,
+        endpoint,
+        feedback_uri,
+    });
+
+    Ok(())
+}
+
+/// Record feedback signed by an EVM client (EIP-712) and submitted by a relayer
+///
+/// Same rules as give_feedback_with_permit, with the signature recovered via secp256k1 and
+/// the feedback attributed to `permit.client()`. Self-feedback runs the give_feedback checks
+/// on that client and also rejects the agent's evm_owner. An EVM client cannot sign a
+/// revoke, so the score stays out of the AgentAccount and TagReputation aggregates.
+pub fn give_feedback_with_evm_permit(
+    ctx: Context<GiveFeedbackWithEvmPermit>,
+    value: i128,
+    value_decimals: u8,
+    score: Option<u8>,
+    feedback_file_hash: Option<[u8; 32]>,
+    tag1: String,
+    tag2: String,
+    endpoint: String,
+    feedback_uri: String,
+    permit: EvmFeedbackPermit,
+) -> Result<()> {
+    let registry = &ctx.accounts.registry_config;
+    require!(
+        accepts_relayed_feedback(&ctx.accounts.agent_account, registry),
+        RegistryError::RelayedFeedbackUnsupported
+    );
+    if !registry.allow_self_feedback {
+        require!(
+            permit.evm_address != ctx.accounts.agent_account.evm_owner,
+            RegistryError::SelfFeedbackNotAllowed
+        );
+        check_not_self_feedback(
+            permit.client(),
+            &ctx.accounts.agent_account,
+            &ctx.accounts.asset,
+            ctx.accounts.client_operator.as_deref(),
+        )?;
+    }
+
+    validate_feedback_fields(
+        registry,
+        value_decimals,
+        score,
+        &tag1,
+        &tag2,
+        &endpoint,
+        &feedback_uri,
+    )?;
+
+    let asset = ctx.accounts.asset.key();
+    let client = permit.client();
+    let clock = Clock::get()?;
+    let seal_hash = compute_seal_hash(
+        value,
+        value_decimals,
+        score,
+        &tag1,
+        &tag2,
+        &endpoint,
+        &feedback_uri,
+        feedback_file_hash,
+    );
+
+    let nonce = &mut ctx.accounts.permit_nonce;
+    require!(
+        clock.unix_timestamp < permit.expiry && permit.nonce == nonce.next_nonce,
+        RegistryError::InvalidFeedbackPermit
+    );
+    verify_evm_digest(
+        &permit.evm_address,
+        &permit.digest(&asset, score, &seal_hash),
+        &permit.signature,
+        permit.recovery_id,
+    )?;
+    nonce.client = client;
+    nonce.next_nonce = nonce.next_nonce.checked_add(1).ok_or(RegistryError::Overflow)?;
+    nonce.bump = ctx.bumps.permit_nonce;
+
+    let agent = &mut ctx.accounts.agent_account;
+    let feedback_index = agent.feedback_count;
+    let leaf = compute_feedback_leaf_v1(
+        &asset.to_bytes(),
+        &client.to_bytes(),
         feedback_index,
         &seal_hash,
-        slot,
+        clock.slot,
     );
-
     agent.feedback_digest = chain_hash(&agent.feedback_digest, DOMAIN_FEEDBACK, &leaf);
     agent.feedback_count = agent.feedback_count.checked_add(1).ok_or(RegistryError::Overflow)?;
+    agent.last_feedback_at = clock.unix_timestamp;
 
+    emit!(EvmFeedbackRelayed {
+        asset,
+        client_address: client,
+        feedback_index,
+        evm_address: permit.evm_address,
+        chain_id: permit.chain_id,
+        relayer: ctx.accounts.relayer.key(),
+    });
     emit!(NewFeedback {
         asset,
         client_address: client,
         feedback_index,
-        slot,
+        slot: clock.slot,
         value,
         value_decimals,
         score,
         feedback_file_hash,
         seal_hash,
-        atom_enabled: is_atom_initialized && score.is_some(),
-        new_trust_tier: update_result.trust_tier,
-        new_quality_score: update_result.quality_score,
-        new_confidence: update_result.confidence,
-        new_risk_score: update_result.risk_score,
-        new_diversity_ratio: update_result.diversity_ratio,
-        is_unique_client: update_result.hll_changed,
+        atom_enabled: false,
+        new_trust_tier: 0,
+        new_quality_score: 0,
+        new_confidence: 0,
+        new_risk_score: 0,
+        new_diversity_ratio: 0,
+        is_unique_client: false,
         new_feedback_digest: agent.feedback_digest,
         new_feedback_count: agent.feedback_count,
         tag1,
@@ -192,25 +1696,239 @@ pub fn give_feedback(
         feedback_uri,
     });
 
-    msg!(
-        "Feedback #{} created: asset={}, client={}, score={:?}, atom_enabled={}, tier={}",
+    Ok(())
+}
+
+/// Approve an aggregator to commit feedback roots for this registry
+pub fn add_feedback_aggregator(ctx: Context<AddFeedbackAggregator>) -> Result<()> {
+    let collection = ctx.accounts.registry_config.collection;
+    let aggregator = ctx.accounts.aggregator.key();
+
+    let record = &mut ctx.accounts.aggregator_record;
+    record.collection = collection;
+    record.aggregator = aggregator;
+    record.added_at = Clock::get()?.unix_timestamp;
+    record.bump = ctx.bumps.aggregator_record;
+
+    emit!(FeedbackAggregatorUpdated {
+        collection,
+        aggregator,
+        active: true,
+    });
+
+    Ok(())
+}
+
+/// Remove an aggregator (roots it already committed stay claimable)
+pub fn remove_feedback_aggregator(ctx: Context<RemoveFeedbackAggregator>) -> Result<()> {
+    emit!(FeedbackAggregatorUpdated {
+        collection: ctx.accounts.aggregator_record.collection,
+        aggregator: ctx.accounts.aggregator_record.aggregator,
+        active: false,
+    });
+
+    Ok(())
+}
+
+/// Commit a Merkle root covering `entry_count` off-chain feedback records
+pub fn commit_feedback_root(
+    ctx: Context<CommitFeedbackRoot>,
+    root: [u8; 32],
+    entry_count: u64,
+) -> Result<()> {
+    require!(
+        root != [0u8; 32] && entry_count > 0,
+        RegistryError::InvalidFeedbackRoot
+    );
+
+    let collection = ctx.accounts.aggregator_record.collection;
+    let aggregator = ctx.accounts.aggregator.key();
+
+    let feedback_root = &mut ctx.accounts.feedback_root;
+    feedback_root.collection = collection;
+    feedback_root.aggregator = aggregator;
+    feedback_root.root = root;
+    feedback_root.entry_count = entry_count;
+    feedback_root.claimed_count = 0;
+    feedback_root.committed_at = Clock::get()?.unix_timestamp;
+    feedback_root.bump = ctx.bumps.feedback_root;
+
+    emit!(FeedbackRootCommitted {
+        collection,
+        aggregator,
+        root,
+        entry_count,
+    });
+
+    Ok(())
+}
+
+/// Materialize a committed off-chain feedback record into the agent's feedback chain
+///
+/// The seal hash is recomputed from `entry`, so the proof binds every field. Claimed
+/// entries get the next agent-wide index and skip ATOM scoring, like give_feedback_batch.
+/// Identical records from the same client share a leaf and can be claimed only once.
+/// `entry.client` is attested by the aggregator, not signed by the client, so the score
+/// stays out of the client-attributed AgentAccount and TagReputation aggregates.
+pub fn claim_feedback(
+    ctx: Context<ClaimFeedback>,
+    entry: RootFeedbackEntry,
+    proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    let registry = &ctx.accounts.registry_config;
+    require!(
+        accepts_relayed_feedback(&ctx.accounts.agent_account, registry),
+        RegistryError::RelayedFeedbackUnsupported
+    );
+    if !registry.allow_self_feedback {
+        check_not_self_feedback(
+            entry.client,
+            &ctx.accounts.agent_account,
+            &ctx.accounts.asset,
+            ctx.accounts.client_operator.as_deref(),
+        )?;
+    }
+
+    validate_feedback_fields(
+        registry,
+        entry.value_decimals,
+        entry.score,
+        &entry.tag1,
+        &entry.tag2,
+        &entry.endpoint,
+        &entry.feedback_uri,
+    )?;
+
+    let asset = ctx.accounts.asset.key();
+    let seal_hash = compute_seal_hash(
+        entry.value,
+        entry.value_decimals,
+        entry.score,
+        &entry.tag1,
+        &entry.tag2,
+        &entry.endpoint,
+        &entry.feedback_uri,
+        entry.feedback_file_hash,
+    );
+    let root_leaf = compute_root_feedback_leaf(&asset, &entry.client, &seal_hash);
+    let feedback_root = &mut ctx.accounts.feedback_root;
+    require!(
+        verify_sorted_proof(&feedback_root.root, root_leaf, &proof),
+        RegistryError::InvalidFeedbackProof
+    );
+    require!(
+        feedback_root.claimed_count < feedback_root.entry_count,
+        RegistryError::InvalidFeedbackRoot
+    );
+    feedback_root.claimed_count += 1;
+    let root = feedback_root.root;
+    let aggregator = feedback_root.aggregator;
+
+    let feedback_index = ctx.accounts.agent_account.feedback_count;
+    let root_key = ctx.accounts.feedback_root.key();
+    let claim_info = ctx.accounts.feedback_claim.to_account_info();
+    let (expected, bump) = Pubkey::find_program_address(
+        &[SEED_FEEDBACK_CLAIM, root_key.as_ref(), root_leaf.as_ref()],
+        &crate::ID,
+    );
+    require!(claim_info.key() == expected, RegistryError::InvalidFeedbackProof);
+    require!(claim_info.data_is_empty(), RegistryError::FeedbackAlreadyClaimed);
+    create_pda_account(
+        &claim_info,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        FeedbackClaim::DISCRIMINATOR.len() + FeedbackClaim::INIT_SPACE,
+        &[SEED_FEEDBACK_CLAIM, root_key.as_ref(), root_leaf.as_ref(), &[bump]],
+    )?;
+    let claim = FeedbackClaim {
+        feedback_root: root_key,
+        leaf: root_leaf,
         feedback_index,
-        asset,
-        client,
-        score,
-        is_atom_initialized && score.is_some(),
-        update_result.trust_tier
+        bump,
+    };
+    claim.try_serialize(&mut &mut claim_info.try_borrow_mut_data()?[..])?;
+
+    let clock = Clock::get()?;
+    let agent = &mut ctx.accounts.agent_account;
+    let leaf = compute_feedback_leaf_v1(
+        &asset.to_bytes(),
+        &entry.client.to_bytes(),
+        feedback_index,
+        &seal_hash,
+        clock.slot,
     );
+    agent.feedback_digest = chain_hash(&agent.feedback_digest, DOMAIN_FEEDBACK, &leaf);
+    agent.feedback_count = agent.feedback_count.checked_add(1).ok_or(RegistryError::Overflow)?;
+    agent.last_feedback_at = clock.unix_timestamp;
+
+    emit!(NewFeedback {
+        asset,
+        client_address: entry.client,
+        feedback_index,
+        slot: clock.slot,
+        value: entry.value,
+        value_decimals: entry.value_decimals,
+        score: entry.score,
+        feedback_file_hash: entry.feedback_file_hash,
+        seal_hash,
+        atom_enabled: false,
+        new_trust_tier: 0,
+        new_quality_score: 0,
+        new_confidence: 0,
+        new_risk_score: 0,
+        new_diversity_ratio: 0,
+        is_unique_client: false,
+        new_feedback_digest: agent.feedback_digest,
+        new_feedback_count: agent.feedback_count,
+        tag1: entry.tag1,
+        tag2: entry.tag2,
+        endpoint: entry.endpoint,
+        feedback_uri: entry.feedback_uri,
+    });
+
+    emit!(FeedbackClaimed {
+        asset,
+        feedback_index,
+        aggregator,
+        root,
+        leaf: root_leaf,
+    });
 
     Ok(())
 }
 
 /// Revoke feedback calls CPI to atom-engine to update stats (optional)
 /// SEAL v1: Client must provide the seal_hash (can be recomputed using the same algorithm)
+///
+/// A score recorded in FeedbackScore is removed from the AgentAccount aggregates whether or
+/// not ATOM is enabled; the record must match seal_hash.
 pub fn revoke_feedback(
     ctx: Context<RevokeFeedback>,
     feedback_index: u64,
     seal_hash: [u8; 32],
+) -> Result<()> {
+    revoke_feedback_inner(ctx, feedback_index, seal_hash, None)
+}
+
+/// Revoke feedback and remove its score from the TagReputation of `tag1`
+///
+/// `tag1` must be the tag the FeedbackScore record was counted under; the removed score is
+/// the recorded one. revoke_feedback performs the same removal without restating the tag.
+pub fn revoke_feedback_with_tag(
+    ctx: Context<RevokeFeedback>,
+    feedback_index: u64,
+    seal_hash: [u8; 32],
+    tag1: String,
+) -> Result<()> {
+    require!(tag1.len() <= MAX_TAG_LENGTH, RegistryError::TagTooLong);
+    revoke_feedback_inner(ctx, feedback_index, seal_hash, Some(tag1))
+}
+
+fn revoke_feedback_inner(
+    ctx: Context<RevokeFeedback>,
+    feedback_index: u64,
+    seal_hash: [u8; 32],
+    tag1: Option<String>,
 ) -> Result<()> {
     let asset = ctx.accounts.asset.key();
     let client = ctx.accounts.client.key();
@@ -308,9 +2026,41 @@ pub fn revoke_feedback(
         }
     };
 
+    let recorded = take_feedback_score(
+        &ctx.accounts.feedback_score.to_account_info(),
+        &ctx.accounts.client.to_account_info(),
+        &seal_hash,
+    )?;
+    let tagged = recorded.as_ref().filter(|record| record.tag_hash != [0u8; 32]);
+    if let Some(tag1) = tag1 {
+        require!(
+            tagged.is_some_and(|record| record.tag_hash == keccak::hash(tag1.as_bytes()).0),
+            RegistryError::InvalidTagReputation
+        );
+    }
+    if let Some(record) = tagged {
+        let tag_reputation = ctx
+            .accounts
+            .tag_reputation
+            .as_ref()
+            .ok_or(RegistryError::InvalidTagReputation)?;
+        update_tag_reputation(
+            &tag_reputation.to_account_info(),
+            &ctx.accounts.client.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &ctx.accounts.agent_account,
+            record.tag_hash,
+            record.score,
+            true,
+        )?;
+    }
+
     let slot = Clock::get()?.slot;
     let leaf = compute_revoke_leaf(&asset, &client, feedback_index, &seal_hash, slot);
     let agent = &mut ctx.accounts.agent_account;
+    if let Some(record) = recorded {
+        agent.remove_score(record.score, record.decay_halvings);
+    }
     agent.revoke_digest = chain_hash(&agent.revoke_digest, DOMAIN_REVOKE, &leaf);
     agent.revoke_count = agent.revoke_count.checked_add(1).ok_or(RegistryError::Overflow)?;
     emit!(FeedbackRevoked {
@@ -349,6 +2099,87 @@ pub fn append_response(
     response_uri: String,
     response_hash: [u8; 32],
     seal_hash: [u8; 32],
+) -> Result<()> {
+    append_response_inner(
+        ctx,
+        client_address,
+        feedback_index,
+        None,
+        false,
+        response_uri,
+        response_hash,
+        seal_hash,
+    )
+}
+
+/// Append an official response on behalf of the agent (owner or SCOPE_RESPONSES operator)
+///
+/// The leaf is wrapped with DOMAIN_OFFICIAL_LEAF_V1 so the response digest records which
+/// responses were official; OfficialResponse carries the same flag for indexers.
+pub fn append_official_response(
+    ctx: Context<AppendResponse>,
+    client_address: Pubkey,
+    feedback_index: u64,
+    response_uri: String,
+    response_hash: [u8; 32],
+    seal_hash: [u8; 32],
+) -> Result<()> {
+    verify_owner_or_operator(
+        &ctx.accounts.asset,
+        &ctx.accounts.responder.key(),
+        ctx.accounts.agent_operator.as_deref(),
+        AgentOperator::SCOPE_RESPONSES,
+    )?;
+    append_response_inner(
+        ctx,
+        client_address,
+        feedback_index,
+        None,
+        true,
+        response_uri,
+        response_hash,
+        seal_hash,
+    )
+}
+
+/// Reply to an earlier response on the same feedback (threaded exchanges)
+///
+/// parent_response_index is the agent-wide response index (ResponseAppended
+/// new_response_count - 1); the reply leaf commits to it in the response digest.
+pub fn append_reply(
+    ctx: Context<AppendResponse>,
+    client_address: Pubkey,
+    feedback_index: u64,
+    parent_response_index: u64,
+    response_uri: String,
+    response_hash: [u8; 32],
+    seal_hash: [u8; 32],
+) -> Result<()> {
+    require!(
+        parent_response_index < ctx.accounts.agent_account.response_count,
+        RegistryError::InvalidResponseIndex
+    );
+    append_response_inner(
+        ctx,
+        client_address,
+        feedback_index,
+        Some(parent_response_index),
+        false,
+        response_uri,
+        response_hash,
+        seal_hash,
+    )
+}
+
+fn append_response_inner(
+    ctx: Context<AppendResponse>,
+    client_address: Pubkey,
+    feedback_index: u64,
+    parent_response_index: Option<u64>,
+    official: bool,
+    response_uri: String,
+    response_hash: [u8; 32],
+    seal_hash: [u8; 32],
 ) -> Result<()> {
     let asset_key = ctx.accounts.asset.key();
     let responder = ctx.accounts.responder.key();
@@ -363,6 +2194,7 @@ pub fn append_response(
         response_uri.len() <= MAX_URI_LENGTH,
         RegistryError::ResponseUriTooLong
     );
+    validate_uri_scheme(&response_uri, ctx.accounts.registry_config.allowed_uri_schemes)?;
 
     let slot = Clock::get()?.slot;
     let leaf = compute_response_leaf(
@@ -374,6 +2206,11 @@ pub fn append_response(
         &seal_hash,
         slot,
     );
+    let leaf = match parent_response_index {
+        Some(parent) => compute_reply_leaf(&leaf, parent),
+        None => leaf,
+    };
+    let leaf = if official { compute_official_leaf(&leaf) } else { leaf };
     let agent = &mut ctx.accounts.agent_account;
     agent.response_digest = chain_hash(&agent.response_digest, DOMAIN_RESPONSE, &leaf);
     agent.response_count = agent.response_count.checked_add(1).ok_or(RegistryError::Overflow)?;
@@ -391,5 +2228,342 @@ pub fn append_response(
         response_uri,
     });
 
+    if official {
+        emit!(OfficialResponse {
+            asset: asset_key,
+            response_index: agent.response_count - 1,
+            responder,
+        });
+    }
+
+    if let Some(parent_response_index) = parent_response_index {
+        emit!(ResponseThreaded {
+            asset: asset_key,
+            response_index: agent.response_count - 1,
+            parent_response_index,
+        });
+    }
+
+    Ok(())
+}
+
+/// Mirror trust tier and feedback count from atom-engine get_summary into AgentAccount
+/// so wallets reading only the agent account see reputation (permissionless)
+pub fn sync_reputation(ctx: Context<SyncReputation>) -> Result<()> {
+    let cpi_accounts = atom_engine::cpi::accounts::GetSummary {
+        asset: ctx.accounts.asset.to_account_info(),
+        stats: ctx.accounts.atom_stats.to_account_info(),
+    };
+    let summary = atom_engine::cpi::get_summary(CpiContext::new(
+        ctx.accounts.atom_engine_program.to_account_info(),
+        cpi_accounts,
+    ))?
+    .get();
+    require!(
+        summary.asset == ctx.accounts.asset.key(),
+        RegistryError::InvalidAtomStatsAccount
+    );
+
+    let slot = Clock::get()?.slot;
+    let agent = &mut ctx.accounts.agent_account;
+    agent.trust_tier = summary.trust_tier;
+    agent.atom_feedback_count = summary.feedback_count;
+    agent.reputation_synced_slot = slot;
+
+    emit!(ReputationSynced {
+        asset: agent.asset,
+        trust_tier: summary.trust_tier,
+        feedback_count: summary.feedback_count,
+        slot,
+    });
+
+    Ok(())
+}
+
+/// Combined reputation view: naive score average from AgentAccount plus, when the ATOM
+/// accounts are passed, the atom-engine get_summary tier, quality, risk and confidence
+pub fn get_reputation_summary(
+    ctx: Context<GetReputationSummary>,
+) -> Result<ReputationSummaryView> {
+    let agent = &ctx.accounts.agent_account;
+    let mut view = ReputationSummaryView {
+        asset: agent.asset,
+        feedback_count: agent.feedback_count,
+        revoke_count: agent.revoke_count,
+        scored_count: agent.scored_count,
+        average_score: agent.average_score(),
+        decayed_average_score: agent.decayed_average_score(),
+        median_score: agent.score_quantile(50),
+        p90_score: agent.score_quantile(90),
+        atom_included: false,
+        trust_tier: 0,
+        quality_score: 0,
+        risk_score: 0,
+        confidence: 0,
+        atom_feedback_count: 0,
+    };
+
+    if let (Some(atom_stats), Some(atom_engine_program)) = (
+        ctx.accounts.atom_stats.as_ref(),
+        ctx.accounts.atom_engine_program.as_ref(),
+    ) {
+        let cpi_accounts = atom_engine::cpi::accounts::GetSummary {
+            asset: ctx.accounts.asset.to_account_info(),
+            stats: atom_stats.to_account_info(),
+        };
+        let summary = atom_engine::cpi::get_summary(CpiContext::new(
+            atom_engine_program.to_account_info(),
+            cpi_accounts,
+        ))?
+        .get();
+        require!(
+            summary.asset == agent.asset,
+            RegistryError::InvalidAtomStatsAccount
+        );
+        view.atom_included = true;
+        view.trust_tier = summary.trust_tier;
+        view.quality_score = summary.quality_score;
+        view.risk_score = summary.risk_score;
+        view.confidence = summary.confidence;
+        view.atom_feedback_count = summary.feedback_count;
+    }
+
+    Ok(view)
+}
+
+/// Halve the agent's decayed score sum and weight once per elapsed half-life
+/// (permissionless crank, same schedule as decay_tag_reputation)
+pub fn decay_agent_reputation(ctx: Context<DecayAgentReputation>) -> Result<()> {
+    let half_life = ctx.accounts.reputation_config.decay_half_life;
+    require!(half_life > 0, RegistryError::DecayNotDue);
+
+    let now = Clock::get()?.unix_timestamp;
+    let agent = &mut ctx.accounts.agent_account;
+    require!(agent.decayed_weight > 0, RegistryError::DecayNotDue);
+    let halvings = now.saturating_sub(agent.decayed_at) / half_life;
+    require!(halvings > 0, RegistryError::DecayNotDue);
+
+    agent.decay_scores(halvings as u64);
+    agent.decayed_at = agent
+        .decayed_at
+        .saturating_add(halvings.saturating_mul(half_life));
+
+    emit!(AgentReputationDecayed {
+        asset: agent.asset,
+        decayed_weight: agent.decayed_weight,
+        decayed_average_score: agent.decayed_average_score(),
+    });
+
+    Ok(())
+}
+
+/// Set the half-life used by decay_tag_reputation (0 disables decay)
+pub fn set_reputation_decay(ctx: Context<SetReputationDecay>, decay_half_life: i64) -> Result<()> {
+    require!(decay_half_life >= 0, RegistryError::InvalidDecayHalfLife);
+
+    let config = &mut ctx.accounts.reputation_config;
+    config.collection = ctx.accounts.registry_config.collection;
+    config.decay_half_life = decay_half_life;
+    config.bump = ctx.bumps.reputation_config;
+
+    emit!(ReputationDecayUpdated {
+        collection: config.collection,
+        decay_half_life,
+    });
+
+    Ok(())
+}
+
+/// Halve a TagReputation's count and score sum once per elapsed half-life (permissionless)
+///
+/// Older feedback loses weight against new feedback, so average_score tracks recent
+/// performance instead of a lifetime mean.
+pub fn decay_tag_reputation(ctx: Context<DecayTagReputation>) -> Result<()> {
+    let half_life = ctx.accounts.reputation_config.decay_half_life;
+    require!(half_life > 0, RegistryError::DecayNotDue);
+
+    let now = Clock::get()?.unix_timestamp;
+    let tag_reputation = &mut ctx.accounts.tag_reputation;
+    let halvings = now.saturating_sub(tag_reputation.last_decay_at) / half_life;
+    require!(halvings > 0, RegistryError::DecayNotDue);
+
+    let shift = halvings.min(ReputationConfig::MAX_HALVINGS) as u32;
+    tag_reputation.feedback_count >>= shift;
+    tag_reputation.score_sum >>= shift;
+    tag_reputation.average_score = tag_reputation
+        .score_sum
+        .checked_div(tag_reputation.feedback_count)
+        .unwrap_or(0) as u8;
+    tag_reputation.last_decay_at = tag_reputation
+        .last_decay_at
+        .saturating_add(halvings.saturating_mul(half_life));
+
+    emit!(TagReputationUpdated {
+        asset: tag_reputation.asset,
+        tag_hash: tag_reputation.tag_hash,
+        feedback_count: tag_reputation.feedback_count,
+        score_sum: tag_reputation.score_sum,
+        average_score: tag_reputation.average_score,
+    });
+
+    Ok(())
+}
+
+/// Return a feedback bond to the client once its challenge window passed undisputed
+pub fn claim_feedback_bond(ctx: Context<ClaimFeedbackBond>) -> Result<()> {
+    let bond = &ctx.accounts.feedback_bond;
+    require!(
+        !bond.disputed && Clock::get()?.unix_timestamp >= bond.unlocks_at,
+        RegistryError::FeedbackBondLocked
+    );
+
+    emit!(FeedbackBondSettled {
+        asset: bond.asset,
+        client_address: bond.client,
+        feedback_index: bond.feedback_index,
+        amount: bond.amount,
+        slashed: false,
+    });
+
+    Ok(())
+}
+
+/// Flag a feedback as contested (spam, fake, abusive) without opening a dispute
+///
+/// A moderation signal only: the feedback stays in the digest chain and aggregates.
+pub fn flag_feedback(
+    ctx: Context<FlagFeedback>,
+    client_address: Pubkey,
+    feedback_index: u64,
+    seal_hash: [u8; 32],
+    reason: u8,
+    reason_hash: [u8; 32],
+) -> Result<()> {
+    verify_owner_or_operator(
+        &ctx.accounts.asset,
+        &ctx.accounts.owner.key(),
+        ctx.accounts.agent_operator.as_deref(),
+        AgentOperator::SCOPE_RESPONSES,
+    )?;
+    require!(
+        feedback_index < ctx.accounts.agent_account.feedback_count,
+        RegistryError::InvalidFeedbackIndex
+    );
+    require!(
+        reason <= FeedbackFlag::FLAG_REASON_OTHER,
+        RegistryError::InvalidFlagReason
+    );
+
+    let asset = ctx.accounts.asset.key();
+    let flagged_by = ctx.accounts.owner.key();
+    let flag = &mut ctx.accounts.feedback_flag;
+    flag.asset = asset;
+    flag.client = client_address;
+    flag.feedback_index = feedback_index;
+    flag.reason = reason;
+    flag.reason_hash = reason_hash;
+    flag.flagged_by = flagged_by;
+    flag.flagged_at = Clock::get()?.unix_timestamp;
+    flag.counter_flagged = false;
+    flag.counter_hash = [0u8; 32];
+    flag.bump = ctx.bumps.feedback_flag;
+
+    emit!(FeedbackFlagged {
+        asset,
+        client_address,
+        feedback_index,
+        seal_hash,
+        reason,
+        reason_hash,
+        flagged_by,
+    });
+
+    Ok(())
+}
+
+/// Record the client's counter-flag (rebuttal) on a flagged feedback
+pub fn counter_flag_feedback(
+    ctx: Context<CounterFlagFeedback>,
+    counter_hash: [u8; 32],
+) -> Result<()> {
+    let flag = &mut ctx.accounts.feedback_flag;
+    require!(!flag.counter_flagged, RegistryError::FeedbackAlreadyCounterFlagged);
+    flag.counter_flagged = true;
+    flag.counter_hash = counter_hash;
+
+    emit!(FeedbackCounterFlagged {
+        asset: flag.asset,
+        client_address: flag.client,
+        feedback_index: flag.feedback_index,
+        counter_hash,
+    });
+
+    Ok(())
+}
+
+/// Flag a bonded feedback as spam within its challenge window (agent owner or operator)
+pub fn dispute_feedback_bond(ctx: Context<DisputeFeedbackBond>) -> Result<()> {
+    verify_owner_or_operator(
+        &ctx.accounts.asset,
+        &ctx.accounts.owner.key(),
+        ctx.accounts.agent_operator.as_deref(),
+        AgentOperator::SCOPE_RESPONSES,
+    )?;
+
+    let bond = &mut ctx.accounts.feedback_bond;
+    require!(
+        !bond.disputed && Clock::get()?.unix_timestamp < bond.unlocks_at,
+        RegistryError::InvalidFeedbackBondState
+    );
+    bond.disputed = true;
+
+    emit!(FeedbackBondDisputed {
+        asset: bond.asset,
+        client_address: bond.client,
+        feedback_index: bond.feedback_index,
+        disputed_by: ctx.accounts.owner.key(),
+    });
+
+    Ok(())
+}
+
+/// Settle a disputed bond: slash `amount` to the treasury or return it (registry authority)
+///
+/// The account is closed to the client either way, so rent always goes back to them.
+pub fn resolve_feedback_bond(ctx: Context<ResolveFeedbackBond>, slash: bool) -> Result<()> {
+    let bond = &ctx.accounts.feedback_bond;
+    require!(bond.disputed, RegistryError::InvalidFeedbackBondState);
+    let amount = bond.amount;
+
+    if slash {
+        let bond_info = ctx.accounts.feedback_bond.to_account_info();
+        let treasury_info = ctx.accounts.treasury.to_account_info();
+        let bond_balance = bond_info
+            .lamports()
+            .checked_sub(amount)
+            .ok_or(RegistryError::Overflow)?;
+        let treasury_balance = treasury_info
+            .lamports()
+            .checked_add(amount)
+            .ok_or(RegistryError::Overflow)?;
+        **bond_info.try_borrow_mut_lamports()? = bond_balance;
+        **treasury_info.try_borrow_mut_lamports()? = treasury_balance;
+
+        let treasury = &mut ctx.accounts.treasury;
+        treasury.total_collected = treasury
+            .total_collected
+            .checked_add(amount)
+            .ok_or(RegistryError::Overflow)?;
+    }
+
+    let bond = &ctx.accounts.feedback_bond;
+    emit!(FeedbackBondSettled {
+        asset: bond.asset,
+        client_address: bond.client,
+        feedback_index: bond.feedback_index,
+        amount,
+        slashed: slash,
+    });
+
     Ok(())
 }
@@ -5,6 +5,7 @@ pub mod events;
 pub mod formal;
 pub mod instructions;
 pub mod seal;
+pub mod sketch;
 pub mod state;
 
 pub use chain::*;
@@ -12,4 +13,5 @@ pub use contexts::*;
 pub use events::*;
 pub use instructions::*;
 pub use seal::*;
+pub use sketch::*;
 pub use state::*;
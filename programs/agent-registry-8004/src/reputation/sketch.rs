@@ -0,0 +1,82 @@
+//! Fixed-bucket score histogram for on-chain median / p90.
+//!
+//! Scores 0-100 fall into `SKETCH_BUCKETS` equal-width buckets. A quantile is
+//! answered with the midpoint of the bucket holding the requested rank, so the
+//! result is within 3 points of the exact value while the sketch stays a fixed
+//! 64 bytes per agent.
+//!
+//! ```text
+//! bucket(score) = score * SKETCH_BUCKETS / 101
+//! ```
+
+/// Number of histogram buckets
+pub const SKETCH_BUCKETS: usize = 16;
+
+/// Bucket of a 0-100 score
+pub fn score_bucket(score: u8) -> usize {
+    score.min(100) as usize * SKETCH_BUCKETS / 101
+}
+
+/// Middle score of a bucket
+fn bucket_midpoint(bucket: usize) -> u8 {
+    let low = (bucket * 101).div_ceil(SKETCH_BUCKETS);
+    let high = ((bucket + 1) * 101).div_ceil(SKETCH_BUCKETS) - 1;
+    ((low + high) / 2) as u8
+}
+
+/// Approximate `pct`-th percentile score (0 for an empty histogram)
+pub fn score_quantile(histogram: &[u32; SKETCH_BUCKETS], pct: u8) -> u8 {
+    let total: u64 = histogram.iter().map(|&count| count as u64).sum();
+    if total == 0 {
+        return 0;
+    }
+    let rank = (total * pct.min(100) as u64).div_ceil(100).max(1);
+    let mut seen = 0u64;
+    for (bucket, &count) in histogram.iter().enumerate() {
+        seen += count as u64;
+        if seen >= rank {
+            return bucket_midpoint(bucket);
+        }
+    }
+    bucket_midpoint(SKETCH_BUCKETS - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn histogram(scores: &[u8]) -> [u32; SKETCH_BUCKETS] {
+        let mut histogram = [0u32; SKETCH_BUCKETS];
+        for &score in scores {
+            histogram[score_bucket(score)] += 1;
+        }
+        histogram
+    }
+
+    #[test]
+    fn test_buckets_cover_score_range() {
+        assert_eq!(score_bucket(0), 0);
+        assert_eq!(score_bucket(100), SKETCH_BUCKETS - 1);
+        assert_eq!(score_bucket(255), SKETCH_BUCKETS - 1);
+        for score in 0..=100u8 {
+            let midpoint = bucket_midpoint(score_bucket(score));
+            assert!(midpoint.abs_diff(score) <= 3, "score {score} -> {midpoint}");
+        }
+    }
+
+    #[test]
+    fn test_quantiles_ignore_outliers() {
+        let mut scores = vec![80u8; 9];
+        scores.push(0);
+        let histogram = histogram(&scores);
+
+        assert_eq!(score_quantile(&histogram, 50), 79);
+        assert_eq!(score_quantile(&histogram, 90), 79);
+        assert_eq!(score_quantile(&histogram, 10), 3);
+    }
+
+    #[test]
+    fn test_empty_histogram() {
+        assert_eq!(score_quantile(&[0u32; SKETCH_BUCKETS], 50), 0);
+    }
+}
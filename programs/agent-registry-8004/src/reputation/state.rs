@@ -1,4 +1,477 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+
 pub const MAX_TAG_LENGTH: usize = 32;
 pub const MAX_URI_LENGTH: usize = 250;
 pub const MAX_ENDPOINT_LENGTH: usize = 250;
 pub const MAX_VALUE_DECIMALS: u8 = 18;
+pub const MAX_BATCH_FEEDBACK: usize = 10;
+/// remaining_accounts per batch entry: agent_account, asset, registry_config,
+/// client AgentOperator PDA
+pub const BATCH_ACCOUNTS_PER_ENTRY: usize = 4;
+/// Score dimensions: accuracy, latency, cost, communication
+pub const SCORE_DIMENSIONS: usize = 4;
+/// Dimension value meaning "not rated" (skipped by the aggregate)
+pub const DIMENSION_UNRATED: u8 = u8::MAX;
+
+/// Per client-agent pair feedback sequence (per-client indexing mode and feedback cooldown)
+/// Seeds: [b"client_index", asset.key(), client.key()]
+#[account]
+#[derive(InitSpace)]
+pub struct ClientIndex {
+    pub asset: Pubkey,
+    pub client: Pubkey,
+    /// Index assigned to this client's next feedback
+    pub next_index: u64,
+    pub bump: u8,
+    /// Slot of this client's latest feedback (feedback cooldown)
+    pub last_feedback_slot: u64,
+}
+
+/// Aggregate of scored feedback for one agent under one tag1 value
+/// Seeds: [b"tag_rep", agent_id.to_le_bytes(), keccak256(tag)]
+#[account]
+#[derive(InitSpace)]
+pub struct TagReputation {
+    pub asset: Pubkey,
+    pub agent_id: u64,
+    pub tag_hash: [u8; 32],
+    /// Scored feedback currently counted under this tag
+    pub feedback_count: u64,
+    /// Sum of their scores (0-100 each)
+    pub score_sum: u64,
+    /// score_sum / feedback_count (0 when empty)
+    pub average_score: u8,
+    /// Start of the current decay period (see ReputationConfig.decay_half_life)
+    pub last_decay_at: i64,
+    pub bump: u8,
+}
+
+/// Lamports a client bonded on one feedback
+/// Seeds: [b"feedback_bond", asset.key(), client.key(), feedback_index.to_le_bytes()]
+///
+/// Returned to the client after the challenge window; if the agent owner disputes and the
+/// registry authority upholds the dispute, `amount` is slashed to the registry treasury.
+#[account]
+#[derive(InitSpace)]
+pub struct FeedbackBond {
+    pub asset: Pubkey,
+    pub client: Pubkey,
+    pub collection: Pubkey,
+    pub feedback_index: u64,
+    /// Bonded lamports held on top of rent
+    pub amount: u64,
+    /// End of the challenge window
+    pub unlocks_at: i64,
+    pub disputed: bool,
+    pub bump: u8,
+}
+
+/// Score of one feedback counted in the AgentAccount aggregates
+/// Seeds: [b"feedback_score", asset.key(), client.key(), feedback_index.to_le_bytes()]
+///
+/// Scores enter score_sum, the decayed average and the quantile sketch only together with
+/// this record, and revoke_feedback removes exactly the recorded score (closing the record).
+#[account]
+#[derive(InitSpace)]
+pub struct FeedbackScore {
+    pub asset: Pubkey,
+    pub client: Pubkey,
+    pub feedback_index: u64,
+    /// SEAL v1 hash of the feedback; revoke_feedback must cite the same hash
+    pub seal_hash: [u8; 32],
+    /// keccak256(tag1) when the score was added to that TagReputation, zeroes otherwise
+    pub tag_hash: [u8; 32],
+    pub score: u8,
+    /// AgentAccount.decay_halvings when the score was recorded
+    pub decay_halvings: u64,
+    pub bump: u8,
+}
+
+/// Reputation settings of a registry
+/// Seeds: [b"reputation_config", collection.key()]
+#[account]
+#[derive(InitSpace)]
+pub struct ReputationConfig {
+    pub collection: Pubkey,
+    /// Seconds after which aggregate counts and sums are halved by the decay crank (0 = off)
+    pub decay_half_life: i64,
+    pub bump: u8,
+}
+
+impl ReputationConfig {
+    /// Halvings beyond this empty any aggregate
+    pub const MAX_HALVINGS: i64 = 63;
+}
+
+/// One scored review in give_feedback_batch (value = score, no decimals or endpoint)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BatchFeedbackEntry {
+    pub score: u8,
+    pub feedback_file_hash: Option<[u8; 32]>,
+    pub tag1: String,
+    pub tag2: String,
+    pub feedback_uri: String,
+}
+
+/// Canonical feedback tag counter of a registry
+/// Seeds: [b"tag_registry", collection.key()]
+#[account]
+#[derive(InitSpace)]
+pub struct TagRegistry {
+    pub collection: Pubkey,
+    /// Canonical tags registered so far (ids start at 1; 0 marks a non-canonical tag)
+    pub tag_count: u32,
+    pub bump: u8,
+}
+
+/// Canonical feedback tag registered by the registry authority
+/// Seeds: [b"canonical_tag", collection.key(), keccak256(tag)]
+#[account]
+#[derive(InitSpace)]
+pub struct CanonicalTag {
+    pub collection: Pubkey,
+    pub tag_id: u32,
+    pub tag_hash: [u8; 32],
+    #[max_len(32)]
+    pub tag: String,
+    pub bump: u8,
+}
+
+/// Feedback listed in a TagFeedbackIndex page
+///
+/// `feedback_index` is in the registry's index space: agent-wide in global mode, the
+/// client's ClientIndex sequence in per-client mode, so only the pair is unique.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct TagFeedbackEntry {
+    pub client: Pubkey,
+    pub feedback_index: u64,
+}
+
+/// Page of feedback given under one tag1 value of an agent
+/// Seeds: [b"tag_fb_index", asset.key(), keccak256(tag), page.to_le_bytes()]
+///
+/// Appended by give_feedback when the current page is passed. Pages are created with
+/// init_tag_feedback_index; once a page is full the next page must be created.
+#[account]
+#[derive(InitSpace)]
+pub struct TagFeedbackIndex {
+    pub asset: Pubkey,
+    pub tag_hash: [u8; 32],
+
+    /// Page number (0-based)
+    pub page: u16,
+
+    /// PDA bump seed
+    pub bump: u8,
+
+    /// (client, feedback index) pairs, in submission order
+    #[max_len(32)]
+    pub entries: Vec<TagFeedbackEntry>,
+}
+
+impl TagFeedbackIndex {
+    /// Maximum entries per page
+    pub const MAX_ENTRIES: usize = 32;
+
+    pub fn is_full(&self) -> bool {
+        self.entries.len() >= Self::MAX_ENTRIES
+    }
+}
+
+/// Moderation signal raised by the agent on a feedback, with the client's counter-flag
+/// Seeds: [b"feedback_flag", asset.key(), client, feedback_index.to_le_bytes()]
+#[account]
+#[derive(InitSpace)]
+pub struct FeedbackFlag {
+    pub asset: Pubkey,
+    pub client: Pubkey,
+    pub feedback_index: u64,
+    /// FLAG_REASON_*
+    pub reason: u8,
+    /// Hash of the off-chain flag explanation (zero = none)
+    pub reason_hash: [u8; 32],
+    pub flagged_by: Pubkey,
+    pub flagged_at: i64,
+    pub counter_flagged: bool,
+    /// Hash of the client's off-chain rebuttal (zero until counter-flagged)
+    pub counter_hash: [u8; 32],
+    pub bump: u8,
+}
+
+impl FeedbackFlag {
+    pub const FLAG_REASON_SPAM: u8 = 0;
+    pub const FLAG_REASON_FAKE: u8 = 1;
+    pub const FLAG_REASON_ABUSIVE: u8 = 2;
+    pub const FLAG_REASON_OTHER: u8 = 3;
+}
+
+/// Per-dimension aggregate of give_feedback_with_dimensions scores
+/// Seeds: [b"dim_rep", asset.key()]
+#[account]
+#[derive(InitSpace)]
+pub struct DimensionReputation {
+    pub asset: Pubkey,
+    /// Rated feedback per dimension (accuracy, latency, cost, communication)
+    pub counts: [u64; SCORE_DIMENSIONS],
+    pub score_sums: [u64; SCORE_DIMENSIONS],
+    /// score_sums / counts per dimension (0 when unrated)
+    pub averages: [u8; SCORE_DIMENSIONS],
+    pub bump: u8,
+}
+
+/// Snapshot of an agent's reputation counters as of its last write in an epoch
+/// Seeds: [b"rep_checkpoint", asset.key(), epoch.to_le_bytes()]
+#[account]
+#[derive(InitSpace)]
+pub struct ReputationCheckpoint {
+    pub asset: Pubkey,
+    pub epoch: u64,
+    pub feedback_count: u64,
+    pub revoke_count: u64,
+    pub scored_count: u64,
+    pub average_score: u8,
+    /// Slot of the last write
+    pub slot: u64,
+    pub bump: u8,
+}
+
+/// Combined reputation returned by get_reputation_summary (Borsh return data)
+///
+/// ATOM fields are zero and atom_included is false when no ATOM accounts were passed.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ReputationSummaryView {
+    pub asset: Pubkey,
+    pub feedback_count: u64,
+    pub revoke_count: u64,
+    pub scored_count: u64,
+    /// Naive mean of counted scores (0-100)
+    pub average_score: u8,
+    /// Time-decayed mean (0-100), see decay_agent_reputation
+    pub decayed_average_score: u8,
+    /// Approximate median and 90th percentile of counted scores (reputation::sketch)
+    pub median_score: u8,
+    pub p90_score: u8,
+    pub atom_included: bool,
+    pub trust_tier: u8,
+    pub quality_score: u16,
+    pub risk_score: u8,
+    pub confidence: u16,
+    pub atom_feedback_count: u64,
+}
+
+/// Domain separator for hidden feedback commitments (exactly 16 bytes)
+pub const DOMAIN_FEEDBACK_COMMIT_V1: &[u8; 16] = b"8004_FB_COMMIT_1";
+
+/// Hidden feedback awaiting reveal_feedback
+/// Seeds: [b"feedback_commit", asset.key(), client.key(), commitment]
+#[account]
+#[derive(InitSpace)]
+pub struct FeedbackCommitment {
+    pub asset: Pubkey,
+    pub client: Pubkey,
+    /// keccak256(DOMAIN_FEEDBACK_COMMIT_V1 || seal_hash || salt)
+    pub commitment: [u8; 32],
+    pub committed_at: i64,
+    /// Unix timestamp after which the client may reveal without consent
+    pub reveal_after: i64,
+    /// Set by the agent owner or an operator to allow an early reveal
+    pub owner_consent: bool,
+    pub bump: u8,
+}
+
+impl FeedbackCommitment {
+    pub fn compute(seal_hash: &[u8; 32], salt: &[u8; 32]) -> [u8; 32] {
+        keccak::hashv(&[DOMAIN_FEEDBACK_COMMIT_V1, seal_hash, salt]).0
+    }
+}
+
+/// Aggregator approved by the registry authority to commit feedback roots
+/// Seeds: [b"feedback_aggregator", collection.key(), aggregator]
+#[account]
+#[derive(InitSpace)]
+pub struct FeedbackAggregator {
+    pub collection: Pubkey,
+    pub aggregator: Pubkey,
+    pub added_at: i64,
+    pub bump: u8,
+}
+
+/// Merkle root over off-chain feedback records posted by an aggregator
+/// Seeds: [b"feedback_root", collection.key(), root]
+///
+/// Leaves are `compute_root_feedback_leaf(asset, client, seal_hash)`, hashed in sorted
+/// pairs like the registration allowlist.
+#[account]
+#[derive(InitSpace)]
+pub struct FeedbackRoot {
+    pub collection: Pubkey,
+    pub aggregator: Pubkey,
+    pub root: [u8; 32],
+    /// Number of leaves the aggregator declared for the tree
+    pub entry_count: u64,
+    pub claimed_count: u64,
+    pub committed_at: i64,
+    pub bump: u8,
+}
+
+/// Marker preventing a root leaf from being claimed twice
+/// Seeds: [b"feedback_claim", feedback_root.key(), leaf]
+#[account]
+#[derive(InitSpace)]
+pub struct FeedbackClaim {
+    pub feedback_root: Pubkey,
+    pub leaf: [u8; 32],
+    /// Agent-wide feedback index assigned on claim
+    pub feedback_index: u64,
+    pub bump: u8,
+}
+
+/// Off-chain feedback record materialized by claim_feedback
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RootFeedbackEntry {
+    pub client: Pubkey,
+    pub value: i128,
+    pub value_decimals: u8,
+    pub score: Option<u8>,
+    pub feedback_file_hash: Option<[u8; 32]>,
+    pub tag1: String,
+    pub tag2: String,
+    pub endpoint: String,
+    pub feedback_uri: String,
+}
+
+/// Message prefix for relayed feedback permits
+pub const FEEDBACK_PERMIT_MESSAGE_PREFIX: &[u8] = b"8004_FEEDBACK_PERMIT:";
+
+/// Off-chain client authorization for give_feedback_with_permit (relayer pays fees and rent)
+///
+/// Signed by `client` with Ed25519 over `message(asset, seal_hash)`; the Ed25519 verify
+/// instruction must directly precede give_feedback_with_permit in the transaction.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct FeedbackPermit {
+    pub client: Pubkey,
+    /// Unix timestamp after which the permit is void
+    pub expiry: i64,
+    /// Must equal the client's PermitNonce.next_nonce
+    pub nonce: u64,
+}
+
+impl FeedbackPermit {
+    /// `FEEDBACK_PERMIT_MESSAGE_PREFIX || asset || client || seal_hash || expiry || nonce`
+    pub fn message(&self, asset: &Pubkey, seal_hash: &[u8; 32]) -> Vec<u8> {
+        let mut message = Vec::with_capacity(FEEDBACK_PERMIT_MESSAGE_PREFIX.len() + 32 * 3 + 16);
+        message.extend_from_slice(FEEDBACK_PERMIT_MESSAGE_PREFIX);
+        message.extend_from_slice(asset.as_ref());
+        message.extend_from_slice(self.client.as_ref());
+        message.extend_from_slice(seal_hash);
+        message.extend_from_slice(&self.expiry.to_le_bytes());
+        message.extend_from_slice(&self.nonce.to_le_bytes());
+        message
+    }
+}
+
+/// Sequential permit nonce of a client (replay protection for relayed feedback)
+/// Seeds: [b"permit_nonce", client]
+#[account]
+#[derive(InitSpace)]
+pub struct PermitNonce {
+    pub client: Pubkey,
+    pub next_nonce: u64,
+    pub bump: u8,
+}
+
+/// EIP-712 domain of EVM feedback permits (salt = this program's id)
+pub const EIP712_DOMAIN_TYPE: &[u8] =
+    b"EIP712Domain(string name,string version,uint256 chainId,bytes32 salt)";
+pub const EIP712_DOMAIN_NAME: &[u8] = b"8004";
+pub const EIP712_DOMAIN_VERSION: &[u8] = b"1";
+/// EIP-712 primary type of EVM feedback permits
+pub const EVM_FEEDBACK_PERMIT_TYPE: &[u8] =
+    b"FeedbackPermit(bytes32 agent,uint8 score,bytes32 sealHash,uint64 expiry,uint64 nonce)";
+
+/// FeedbackPermit for EVM-native clients, signed with eth_signTypedData_v4
+///
+/// `agent` is the asset, `score` is 255 for unscored feedback and `chainId` is the chain
+/// the signing wallet is connected to. The client is recorded on Solana as `client()`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct EvmFeedbackPermit {
+    pub evm_address: [u8; 20],
+    pub chain_id: u64,
+    /// Unix timestamp after which the permit is void
+    pub expiry: i64,
+    /// Must equal the EVM client's PermitNonce.next_nonce
+    pub nonce: u64,
+    pub signature: [u8; 64],
+    /// 0/1 or 27/28
+    pub recovery_id: u8,
+}
+
+impl EvmFeedbackPermit {
+    /// Solana client key of the EVM address: `[0u8; 12] || evm_address` (no Ed25519 signer)
+    pub fn client(&self) -> Pubkey {
+        let mut key = [0u8; 32];
+        key[12..].copy_from_slice(&self.evm_address);
+        Pubkey::new_from_array(key)
+    }
+
+    /// `keccak256(0x1901 || domainSeparator || hashStruct(FeedbackPermit))`
+    pub fn digest(&self, asset: &Pubkey, score: Option<u8>, seal_hash: &[u8; 32]) -> [u8; 32] {
+        let domain_separator = keccak::hashv(&[
+            &keccak::hash(EIP712_DOMAIN_TYPE).0,
+            &keccak::hash(EIP712_DOMAIN_NAME).0,
+            &keccak::hash(EIP712_DOMAIN_VERSION).0,
+            &abi_uint(self.chain_id),
+            crate::ID.as_ref(),
+        ]);
+        let struct_hash = keccak::hashv(&[
+            &keccak::hash(EVM_FEEDBACK_PERMIT_TYPE).0,
+            asset.as_ref(),
+            &abi_uint(score.unwrap_or(u8::MAX) as u64),
+            seal_hash,
+            &abi_uint(self.expiry as u64),
+            &abi_uint(self.nonce),
+        ]);
+        keccak::hashv(&[b"\x19\x01", &domain_separator.0, &struct_hash.0]).0
+    }
+}
+
+/// ABI-encode an unsigned integer as a 32-byte big-endian word
+fn abi_uint(value: u64) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[24..].copy_from_slice(&value.to_be_bytes());
+    word
+}
+
+/// Message prefix for FeedbackAuth signatures
+pub const FEEDBACK_AUTH_MESSAGE_PREFIX: &[u8] = b"8004_FEEDBACK_AUTH:";
+
+/// ERC-8004 feedbackAuth: the agent owner (or agent wallet) pre-authorizes a client
+///
+/// Signed with Ed25519 over `message()`; the Ed25519 verify instruction must directly
+/// precede give_feedback_with_auth in the transaction.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct FeedbackAuth {
+    pub asset: Pubkey,
+    pub client_address: Pubkey,
+    /// Feedback is accepted while the assigned feedback_index is below this limit
+    pub index_limit: u64,
+    /// Unix timestamp after which the authorization is void
+    pub expiry: i64,
+    /// Core owner or agent_wallet that signed the authorization
+    pub signer_address: Pubkey,
+}
+
+impl FeedbackAuth {
+    /// `FEEDBACK_AUTH_MESSAGE_PREFIX || asset || client || index_limit || expiry || signer`
+    pub fn message(&self) -> Vec<u8> {
+        let mut message = Vec::with_capacity(FEEDBACK_AUTH_MESSAGE_PREFIX.len() + 32 * 3 + 16);
+        message.extend_from_slice(FEEDBACK_AUTH_MESSAGE_PREFIX);
+        message.extend_from_slice(self.asset.as_ref());
+        message.extend_from_slice(self.client_address.as_ref());
+        message.extend_from_slice(&self.index_limit.to_le_bytes());
+        message.extend_from_slice(&self.expiry.to_le_bytes());
+        message.extend_from_slice(self.signer_address.as_ref());
+        message
+    }
+}
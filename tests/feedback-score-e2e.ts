@@ -0,0 +1,395 @@
+/**
+ * E2E Test: FeedbackScore aggregates
+ * Verifies that on every score-bearing give path:
+ * - A score counts in AgentAccount (and TagReputation for tag1) only with a FeedbackScore PDA
+ * - revokeFeedback removes exactly the recorded score and closes the record
+ */
+import * as anchor from "@coral-xyz/anchor";
+import { BN, Program } from "@coral-xyz/anchor";
+import { AgentRegistry8004 } from "../target/types/agent_registry_8004";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+import { keccak256 } from "js-sha3";
+
+import {
+  MPL_CORE_PROGRAM_ID,
+  getRootConfigPda,
+  getRegistryConfigPda,
+  getAgentPda,
+  expectAnchorError,
+  fundKeypair,
+} from "./utils/helpers";
+
+const DOMAIN_SEAL_V1 = Buffer.from("8004_SEAL_V1____");
+const DOMAIN_FEEDBACK_COMMIT_V1 = Buffer.from("8004_FB_COMMIT_1");
+
+const ENDPOINT = "https://api.example.com";
+
+function keccak256Buf(data: Buffer): Buffer {
+  return Buffer.from(keccak256.arrayBuffer(data));
+}
+
+function computeSealHash(
+  value: BN,
+  valueDecimals: number,
+  score: number | null,
+  tag1: string,
+  tag2: string,
+  endpoint: string,
+  feedbackUri: string,
+): Buffer {
+  const parts: Buffer[] = [];
+  parts.push(DOMAIN_SEAL_V1);
+  parts.push(value.toTwos(128).toArrayLike(Buffer, "le", 16));
+  parts.push(Buffer.from([valueDecimals]));
+  parts.push(score !== null ? Buffer.from([1, score]) : Buffer.from([0, 0]));
+  parts.push(Buffer.from([0])); // no feedback file hash
+  for (const s of [tag1, tag2, endpoint, feedbackUri]) {
+    const bytes = Buffer.from(s, "utf-8");
+    const lenBuf = Buffer.alloc(2);
+    lenBuf.writeUInt16LE(bytes.length);
+    parts.push(lenBuf);
+    parts.push(bytes);
+  }
+  return keccak256Buf(Buffer.concat(parts));
+}
+
+describe("E2E FeedbackScore aggregates", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.AgentRegistry8004 as Program<AgentRegistry8004>;
+
+  let rootConfigPda: PublicKey;
+  let registryConfigPda: PublicKey;
+  let collectionPubkey: PublicKey;
+
+  let agentAsset: Keypair;
+  let agentPda: PublicKey;
+  let agentId: BN;
+  let client: Keypair;
+
+  function getFeedbackScorePda(feedbackIndex: BN): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("feedback_score"),
+        agentAsset.publicKey.toBuffer(),
+        client.publicKey.toBuffer(),
+        feedbackIndex.toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    )[0];
+  }
+
+  function getTagReputationPda(tag: string): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("tag_rep"),
+        agentId.toArrayLike(Buffer, "le", 8),
+        keccak256Buf(Buffer.from(tag, "utf-8")),
+      ],
+      program.programId
+    )[0];
+  }
+
+  function feedbackAccounts(feedbackIndex: BN, tag?: string) {
+    return {
+      client: client.publicKey,
+      asset: agentAsset.publicKey,
+      collection: collectionPubkey,
+      agentAccount: agentPda,
+      systemProgram: SystemProgram.programId,
+      feedbackScore: getFeedbackScorePda(feedbackIndex),
+      tagReputation: tag ? getTagReputationPda(tag) : null,
+    };
+  }
+
+  async function nextFeedbackIndex(): Promise<BN> {
+    return (await program.account.agentAccount.fetch(agentPda)).feedbackCount;
+  }
+
+  async function snapshot(tag: string) {
+    const agent = await program.account.agentAccount.fetch(agentPda);
+    const tagInfo = await provider.connection.getAccountInfo(getTagReputationPda(tag));
+    const tagReputation = tagInfo
+      ? await program.account.tagReputation.fetch(getTagReputationPda(tag))
+      : null;
+    return {
+      scoreSum: agent.scoreSum.toNumber(),
+      scoredCount: agent.scoredCount.toNumber(),
+      tagScoreSum: tagReputation ? tagReputation.scoreSum.toNumber() : 0,
+      tagCount: tagReputation ? tagReputation.feedbackCount.toNumber() : 0,
+    };
+  }
+
+  async function revoke(feedbackIndex: BN, sealHash: Buffer, tag?: string) {
+    await program.methods
+      .revokeFeedback(feedbackIndex, Array.from(sealHash))
+      .accountsPartial({
+        client: client.publicKey,
+        asset: agentAsset.publicKey,
+        agentAccount: agentPda,
+        systemProgram: SystemProgram.programId,
+        feedbackScore: getFeedbackScorePda(feedbackIndex),
+        tagReputation: tag ? getTagReputationPda(tag) : null,
+      })
+      .signers([client])
+      .rpc();
+  }
+
+  async function expectRoundTrip(
+    tag: string,
+    score: number,
+    give: (feedbackIndex: BN) => Promise<Buffer>
+  ) {
+    const before = await snapshot(tag);
+    const feedbackIndex = await nextFeedbackIndex();
+    const sealHash = await give(feedbackIndex);
+
+    const record = await program.account.feedbackScore.fetch(getFeedbackScorePda(feedbackIndex));
+    expect(record.score).to.equal(score);
+    expect(Buffer.from(record.sealHash).equals(sealHash)).to.equal(true);
+    expect(Buffer.from(record.tagHash).equals(keccak256Buf(Buffer.from(tag)))).to.equal(true);
+
+    const given = await snapshot(tag);
+    expect(given.scoreSum).to.equal(before.scoreSum + score);
+    expect(given.scoredCount).to.equal(before.scoredCount + 1);
+    expect(given.tagScoreSum).to.equal(before.tagScoreSum + score);
+    expect(given.tagCount).to.equal(before.tagCount + 1);
+
+    await revoke(feedbackIndex, sealHash, tag);
+
+    expect(await snapshot(tag)).to.deep.equal(before);
+    const recordInfo = await provider.connection.getAccountInfo(
+      getFeedbackScorePda(feedbackIndex)
+    );
+    expect(recordInfo).to.equal(null);
+  }
+
+  before(async () => {
+    [rootConfigPda] = getRootConfigPda(program.programId);
+    const rootConfig = await program.account.rootConfig.fetch(rootConfigPda);
+    collectionPubkey = rootConfig.baseCollection;
+    [registryConfigPda] = getRegistryConfigPda(collectionPubkey, program.programId);
+
+    client = Keypair.generate();
+    await fundKeypair(provider, client, 0.5 * anchor.web3.LAMPORTS_PER_SOL);
+
+    // ATOM disabled: the aggregates under test are the registry's own
+    agentAsset = Keypair.generate();
+    [agentPda] = getAgentPda(agentAsset.publicKey, program.programId);
+    await program.methods
+      .registerWithOptions("https://example.com/agent/feedback-score", false)
+      .accountsPartial({
+        rootConfig: rootConfigPda,
+        registryConfig: registryConfigPda,
+        agentAccount: agentPda,
+        asset: agentAsset.publicKey,
+        collection: collectionPubkey,
+        owner: provider.wallet.publicKey,
+        payer: provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+        mplCoreProgram: MPL_CORE_PROGRAM_ID,
+      })
+      .signers([agentAsset])
+      .rpc();
+
+    agentId = (await program.account.agentAccount.fetch(agentPda)).agentId;
+  });
+
+  it("giveFeedback with a FeedbackScore counts the score and revokeFeedback removes it", async () => {
+    const uri = "https://example.com/feedback/score-give";
+    await expectRoundTrip("quality", 80, async (feedbackIndex) => {
+      await program.methods
+        .giveFeedback(new BN(80), 0, 80, null, "quality", "e2e", ENDPOINT, uri)
+        .accountsPartial(feedbackAccounts(feedbackIndex, "quality"))
+        .signers([client])
+        .rpc();
+      return computeSealHash(new BN(80), 0, 80, "quality", "e2e", ENDPOINT, uri);
+    });
+  });
+
+  it("giveFeedback without a FeedbackScore leaves the aggregates unchanged", async () => {
+    const uri = "https://example.com/feedback/score-unrecorded";
+    const before = await snapshot("quality");
+    const feedbackIndex = await nextFeedbackIndex();
+
+    await program.methods
+      .giveFeedback(new BN(40), 0, 40, null, "quality", "e2e", ENDPOINT, uri)
+      .accountsPartial({ ...feedbackAccounts(feedbackIndex), feedbackScore: null })
+      .signers([client])
+      .rpc();
+    expect(await snapshot("quality")).to.deep.equal(before);
+
+    const sealHash = computeSealHash(new BN(40), 0, 40, "quality", "e2e", ENDPOINT, uri);
+    await revoke(feedbackIndex, sealHash);
+    expect(await snapshot("quality")).to.deep.equal(before);
+  });
+
+  it("giveFeedback rejects a TagReputation without a FeedbackScore", async () => {
+    const feedbackIndex = await nextFeedbackIndex();
+    await expectAnchorError(
+      program.methods
+        .giveFeedback(
+          new BN(60),
+          0,
+          60,
+          null,
+          "quality",
+          "e2e",
+          ENDPOINT,
+          "https://example.com/feedback/score-tag-only"
+        )
+        .accountsPartial({ ...feedbackAccounts(feedbackIndex, "quality"), feedbackScore: null })
+        .signers([client])
+        .rpc(),
+      "InvalidFeedbackScore"
+    );
+  });
+
+  it("revokeFeedbackWithTag rejects a tag other than the recorded one", async () => {
+    const uri = "https://example.com/feedback/score-wrong-tag";
+    const feedbackIndex = await nextFeedbackIndex();
+    await program.methods
+      .giveFeedback(new BN(70), 0, 70, null, "latency", "e2e", ENDPOINT, uri)
+      .accountsPartial(feedbackAccounts(feedbackIndex, "latency"))
+      .signers([client])
+      .rpc();
+    const sealHash = computeSealHash(new BN(70), 0, 70, "latency", "e2e", ENDPOINT, uri);
+
+    await expectAnchorError(
+      program.methods
+        .revokeFeedbackWithTag(feedbackIndex, Array.from(sealHash), "quality")
+        .accountsPartial({
+          client: client.publicKey,
+          asset: agentAsset.publicKey,
+          agentAccount: agentPda,
+          systemProgram: SystemProgram.programId,
+          feedbackScore: getFeedbackScorePda(feedbackIndex),
+          tagReputation: getTagReputationPda("quality"),
+        })
+        .signers([client])
+        .rpc(),
+      "InvalidTagReputation"
+    );
+
+    await revoke(feedbackIndex, sealHash, "latency");
+  });
+
+  it("giveFeedbackWithDimensions round trips its score", async () => {
+    const uri = "https://example.com/feedback/score-dimensions";
+    const [dimensionReputation] = PublicKey.findProgramAddressSync(
+      [Buffer.from("dim_rep"), agentAsset.publicKey.toBuffer()],
+      program.programId
+    );
+    await expectRoundTrip("quality", 65, async (feedbackIndex) => {
+      await program.methods
+        .giveFeedbackWithDimensions(
+          new BN(65),
+          0,
+          65,
+          null,
+          "quality",
+          "e2e",
+          ENDPOINT,
+          uri,
+          [70, 60, 255, 50]
+        )
+        .accountsPartial({
+          feedback: feedbackAccounts(feedbackIndex, "quality"),
+          dimensionReputation,
+        })
+        .signers([client])
+        .rpc();
+      return computeSealHash(new BN(65), 0, 65, "quality", "e2e", ENDPOINT, uri);
+    });
+  });
+
+  it("amendFeedback round trips the amended score", async () => {
+    const uri = "https://example.com/feedback/score-original";
+    const originalIndex = await nextFeedbackIndex();
+    await program.methods
+      .giveFeedback(new BN(30), 0, 30, null, "quality", "e2e", ENDPOINT, uri)
+      .accountsPartial(feedbackAccounts(originalIndex, "quality"))
+      .signers([client])
+      .rpc();
+    const originalSeal = computeSealHash(new BN(30), 0, 30, "quality", "e2e", ENDPOINT, uri);
+
+    const amendedUri = "https://example.com/feedback/score-amended";
+    await expectRoundTrip("quality", 90, async (feedbackIndex) => {
+      await program.methods
+        .amendFeedback(
+          new BN(90),
+          0,
+          90,
+          null,
+          "quality",
+          "e2e",
+          ENDPOINT,
+          amendedUri,
+          originalIndex,
+          Array.from(originalSeal)
+        )
+        .accountsPartial(feedbackAccounts(feedbackIndex, "quality"))
+        .signers([client])
+        .rpc();
+      return computeSealHash(new BN(90), 0, 90, "quality", "e2e", ENDPOINT, amendedUri);
+    });
+
+    await revoke(originalIndex, originalSeal, "quality");
+  });
+
+  it("revealFeedback round trips the revealed score", async () => {
+    const uri = "https://example.com/feedback/score-reveal";
+    const salt = keccak256Buf(Buffer.from(uri));
+    const sealHash = computeSealHash(new BN(55), 0, 55, "quality", "e2e", ENDPOINT, uri);
+    const commitment = keccak256Buf(
+      Buffer.concat([DOMAIN_FEEDBACK_COMMIT_V1, sealHash, salt])
+    );
+    const [feedbackCommitment] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("feedback_commit"),
+        agentAsset.publicKey.toBuffer(),
+        client.publicKey.toBuffer(),
+        commitment,
+      ],
+      program.programId
+    );
+
+    await program.methods
+      .giveFeedbackCommitted(Array.from(commitment))
+      .accountsPartial({
+        client: client.publicKey,
+        agentAccount: agentPda,
+        asset: agentAsset.publicKey,
+        registryConfig: registryConfigPda,
+        feedbackCommitment,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([client])
+      .rpc();
+
+    // Default registry reveal delay is 0, so the commitment opens immediately
+    await expectRoundTrip("quality", 55, async (feedbackIndex) => {
+      await program.methods
+        .revealFeedback(
+          new BN(55),
+          0,
+          55,
+          null,
+          "quality",
+          "e2e",
+          ENDPOINT,
+          uri,
+          Array.from(salt)
+        )
+        .accountsPartial({
+          feedback: feedbackAccounts(feedbackIndex, "quality"),
+          feedbackCommitment,
+        })
+        .signers([client])
+        .rpc();
+      return sealHash;
+    });
+  });
+});
@@ -17,12 +17,14 @@ import {
   getRootConfigPda,
   getRegistryConfigPda,
   getAgentPda,
+  getAgentIdIndexPda,
   getMetadataEntryPda,
   computeKeyHash,
   buildWalletSetMessage,
   stringOfLength,
   uriOfLength,
   expectAnchorError,
+  fundKeypair,
 } from "./utils/helpers";
 import { Ed25519Program, SYSVAR_INSTRUCTIONS_PUBKEY } from "@solana/web3.js";
 import * as nacl from "tweetnacl";
@@ -63,14 +65,16 @@ async function getTransactionWithRetry(
 
 /**
  * Execute a direct mpl-core TransferV1 (outside registry program) so AgentAccount owner cache
- * remains stale until syncOwner() is called.
+ * remains stale until syncOwner() is called. `oracle` lists a collection Oracle plugin account
+ * (e.g. the registry TransferGate) the way Core SDKs do.
  */
 async function transferCoreAssetExternally(
   provider: anchor.AnchorProvider,
   asset: PublicKey,
   collection: PublicKey,
   authority: PublicKey,
-  newOwner: PublicKey
+  newOwner: PublicKey,
+  oracle?: PublicKey
 ): Promise<string> {
   // mpl-core TransferV1: discriminator=14, args.compression_proof=None (0)
   const transferIx = new anchor.web3.TransactionInstruction({
@@ -84,6 +88,7 @@ async function transferCoreAssetExternally(
       { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
       // Optional log wrapper omitted by using mpl-core sentinel account.
       { pubkey: MPL_CORE_PROGRAM_ID, isSigner: false, isWritable: false },
+      ...(oracle ? [{ pubkey: oracle, isSigner: false, isWritable: false }] : []),
     ],
     data: Buffer.from([14, 0]),
   });
@@ -91,6 +96,34 @@ async function transferCoreAssetExternally(
   return provider.sendAndConfirm(new anchor.web3.Transaction().add(transferIx));
 }
 
+/**
+ * Execute a direct mpl-core BurnV1 (outside registry program), leaving the AgentAccount
+ * behind for deregister's burned-asset path.
+ */
+async function burnCoreAssetExternally(
+  provider: anchor.AnchorProvider,
+  asset: PublicKey,
+  collection: PublicKey,
+  authority: PublicKey
+): Promise<string> {
+  // mpl-core BurnV1: discriminator=12, args.compression_proof=None (0)
+  const burnIx = new anchor.web3.TransactionInstruction({
+    programId: MPL_CORE_PROGRAM_ID,
+    keys: [
+      { pubkey: asset, isSigner: false, isWritable: true },
+      { pubkey: collection, isSigner: false, isWritable: true },
+      { pubkey: authority, isSigner: true, isWritable: true }, // payer
+      { pubkey: authority, isSigner: true, isWritable: false }, // authority
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      // Optional log wrapper omitted by using mpl-core sentinel account.
+      { pubkey: MPL_CORE_PROGRAM_ID, isSigner: false, isWritable: false },
+    ],
+    data: Buffer.from([12, 0]),
+  });
+
+  return provider.sendAndConfirm(new anchor.web3.Transaction().add(burnIx));
+}
+
 describe("Identity Module Tests", () => {
   const provider = anchor.AnchorProvider.env();
   anchor.setProvider(provider);
@@ -161,6 +194,65 @@ describe("Identity Module Tests", () => {
       expect(agent.agentUri).to.equal(uri);
     });
 
+    it("register() allocates the registry's next agent id", async () => {
+      const assetKeypair = Keypair.generate();
+      const [agentPda] = getAgentPda(assetKeypair.publicKey, program.programId);
+      const before = await program.account.registryConfig.fetch(registryConfigPda);
+      const [agentIdIndexPda] = getAgentIdIndexPda(before.nextAgentId, program.programId);
+
+      await program.methods
+        .register("https://example.com/agent/identity-test-id")
+        .accounts({
+          rootConfig: rootConfigPda,
+          registryConfig: registryConfigPda,
+          agentAccount: agentPda,
+          agentIdIndex: agentIdIndexPda,
+          asset: assetKeypair.publicKey,
+          collection: collectionPubkey,
+          owner: provider.wallet.publicKey,
+          payer: provider.wallet.publicKey,
+          systemProgram: SystemProgram.programId,
+          mplCoreProgram: MPL_CORE_PROGRAM_ID,
+        })
+        .signers([assetKeypair])
+        .rpc();
+
+      const agent = await program.account.agentAccount.fetch(agentPda);
+      const after = await program.account.registryConfig.fetch(registryConfigPda);
+      const index = await program.account.agentIdIndex.fetch(agentIdIndexPda);
+      expect(agent.agentId.toString()).to.equal(before.nextAgentId.toString());
+      expect(agent.agentId.shrn(32).toString()).to.equal(before.registryId.toString());
+      expect(after.nextAgentId.toString()).to.equal(before.nextAgentId.addn(1).toString());
+      expect(index.asset.toBase58()).to.equal(assetKeypair.publicKey.toBase58());
+
+      // A registration built against the consumed id fails and succeeds on a fresh read
+      const retryKeypair = Keypair.generate();
+      const [retryAgentPda] = getAgentPda(retryKeypair.publicKey, program.programId);
+      const registerRetry = (agentIdIndex: PublicKey) =>
+        program.methods
+          .register("https://example.com/agent/identity-test-id-retry")
+          .accounts({
+            rootConfig: rootConfigPda,
+            registryConfig: registryConfigPda,
+            agentAccount: retryAgentPda,
+            agentIdIndex,
+            asset: retryKeypair.publicKey,
+            collection: collectionPubkey,
+            owner: provider.wallet.publicKey,
+            payer: provider.wallet.publicKey,
+            systemProgram: SystemProgram.programId,
+            mplCoreProgram: MPL_CORE_PROGRAM_ID,
+          })
+          .signers([retryKeypair])
+          .rpc();
+      await expectAnchorError(registerRetry(agentIdIndexPda), "ConstraintSeeds");
+
+      const [freshIndexPda] = getAgentIdIndexPda(after.nextAgentId, program.programId);
+      await registerRetry(freshIndexPda);
+      const retried = await program.account.agentAccount.fetch(retryAgentPda);
+      expect(retried.agentId.toString()).to.equal(after.nextAgentId.toString());
+    });
+
     it("register() with empty URI", async () => {
       const assetKeypair = Keypair.generate();
       const [agentPda] = getAgentPda(assetKeypair.publicKey, program.programId);
@@ -1839,4 +1931,373 @@ describe("Identity Module Tests", () => {
       );
     });
   });
+
+  // ============================================================================
+  // TRANSFER GATE TESTS
+  // ============================================================================
+  describe("Transfer Gate", () => {
+    // Gated user registry, so the base collection used by the other suites stays open
+    const gatedCollection = Keypair.generate();
+    let gatedRegistryPda: PublicKey;
+    let transferGatePda: PublicKey;
+    let assetKeypair: Keypair;
+    let agentPda: PublicKey;
+    let recipient: Keypair;
+
+    async function coreOwner(asset: PublicKey): Promise<PublicKey> {
+      // BaseAssetV1: key (1) + owner (32)
+      const info = await provider.connection.getAccountInfo(asset);
+      return new PublicKey(info!.data.subarray(1, 33));
+    }
+
+    async function transfersOutside(newOwner: PublicKey): Promise<boolean> {
+      try {
+        await transferCoreAssetExternally(
+          provider,
+          assetKeypair.publicKey,
+          gatedCollection.publicKey,
+          provider.wallet.publicKey,
+          newOwner,
+          transferGatePda
+        );
+        return true;
+      } catch {
+        return false;
+      }
+    }
+
+    function setTransferGate(enabled: boolean) {
+      return program.methods
+        .setTransferGate(enabled)
+        .accountsPartial({
+          registryConfig: gatedRegistryPda,
+          transferGate: transferGatePda,
+          collection: gatedCollection.publicKey,
+          authority: provider.wallet.publicKey,
+          systemProgram: SystemProgram.programId,
+          mplCoreProgram: MPL_CORE_PROGRAM_ID,
+        })
+        .rpc();
+    }
+
+    before(async () => {
+      [gatedRegistryPda] = getRegistryConfigPda(gatedCollection.publicKey, program.programId);
+      [transferGatePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("transfer_gate"), gatedCollection.publicKey.toBuffer()],
+        program.programId
+      );
+      recipient = Keypair.generate();
+      await fundKeypair(provider, recipient, 0.1 * anchor.web3.LAMPORTS_PER_SOL);
+
+      await program.methods
+        .createUserRegistry("Gated Registry", "https://example.com/registry/gated")
+        .accountsPartial({
+          rootConfig: rootConfigPda,
+          registryConfig: gatedRegistryPda,
+          collection: gatedCollection.publicKey,
+          owner: provider.wallet.publicKey,
+          systemProgram: SystemProgram.programId,
+          mplCoreProgram: MPL_CORE_PROGRAM_ID,
+        })
+        .signers([gatedCollection])
+        .rpc();
+
+      assetKeypair = Keypair.generate();
+      [agentPda] = getAgentPda(assetKeypair.publicKey, program.programId);
+      await program.methods
+        .register("https://example.com/agent/gated")
+        .accountsPartial({
+          rootConfig: rootConfigPda,
+          registryConfig: gatedRegistryPda,
+          agentAccount: agentPda,
+          asset: assetKeypair.publicKey,
+          collection: gatedCollection.publicKey,
+          owner: provider.wallet.publicKey,
+          payer: provider.wallet.publicKey,
+          systemProgram: SystemProgram.programId,
+          mplCoreProgram: MPL_CORE_PROGRAM_ID,
+        })
+        .signers([assetKeypair])
+        .rpc();
+    });
+
+    it("setTransferGate(true) makes Core reject transfers outside the registry", async () => {
+      await setTransferGate(true);
+
+      const gate = await program.account.transferGate.fetch(transferGatePda);
+      expect(gate.transfer).to.equal(1); // ExternalValidationResult::Rejected
+
+      expect(await transfersOutside(recipient.publicKey)).to.equal(false);
+      expect((await coreOwner(assetKeypair.publicKey)).toBase58()).to.equal(
+        provider.wallet.publicKey.toBase58()
+      );
+    });
+
+    it("transferAgent() and acceptTransfer() move gated assets", async () => {
+      await program.methods
+        .transferAgent()
+        .accountsPartial({
+          asset: assetKeypair.publicKey,
+          agentAccount: agentPda,
+          collection: gatedCollection.publicKey,
+          owner: provider.wallet.publicKey,
+          newOwner: recipient.publicKey,
+          mplCoreProgram: MPL_CORE_PROGRAM_ID,
+          transferGate: transferGatePda,
+          fromOwnerIndex: null,
+          toOwnerIndex: null,
+        })
+        .rpc();
+      expect((await coreOwner(assetKeypair.publicKey)).toBase58()).to.equal(
+        recipient.publicKey.toBase58()
+      );
+
+      const [pendingTransferPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("pending_transfer"), assetKeypair.publicKey.toBuffer()],
+        program.programId
+      );
+      await program.methods
+        .proposeTransfer(provider.wallet.publicKey)
+        .accountsPartial({
+          pendingTransfer: pendingTransferPda,
+          agentAccount: agentPda,
+          asset: assetKeypair.publicKey,
+          collection: gatedCollection.publicKey,
+          owner: recipient.publicKey,
+          systemProgram: SystemProgram.programId,
+          mplCoreProgram: MPL_CORE_PROGRAM_ID,
+        })
+        .signers([recipient])
+        .rpc();
+      await program.methods
+        .acceptTransfer()
+        .accountsPartial({
+          pendingTransfer: pendingTransferPda,
+          agentAccount: agentPda,
+          asset: assetKeypair.publicKey,
+          collection: gatedCollection.publicKey,
+          newOwner: provider.wallet.publicKey,
+          proposer: recipient.publicKey,
+          systemProgram: SystemProgram.programId,
+          mplCoreProgram: MPL_CORE_PROGRAM_ID,
+          transferGate: transferGatePda,
+          fromOwnerIndex: null,
+          toOwnerIndex: null,
+        })
+        .rpc();
+
+      expect((await coreOwner(assetKeypair.publicKey)).toBase58()).to.equal(
+        provider.wallet.publicKey.toBase58()
+      );
+      const agent = await program.account.agentAccount.fetch(agentPda);
+      expect(agent.owner.toBase58()).to.equal(provider.wallet.publicKey.toBase58());
+
+      // The gate is closed again after each registry transfer
+      const gate = await program.account.transferGate.fetch(transferGatePda);
+      expect(gate.transfer).to.equal(1);
+      expect(await transfersOutside(recipient.publicKey)).to.equal(false);
+    });
+
+    it("setTransferGate(false) restores transfers outside the registry", async () => {
+      await setTransferGate(false);
+
+      const gate = await program.account.transferGate.fetch(transferGatePda);
+      expect(gate.transfer).to.equal(2); // ExternalValidationResult::Pass
+
+      expect(await transfersOutside(recipient.publicKey)).to.equal(true);
+      expect((await coreOwner(assetKeypair.publicKey)).toBase58()).to.equal(
+        recipient.publicKey.toBase58()
+      );
+    });
+  });
+
+  // ============================================================================
+  // DEREGISTER TESTS
+  // ============================================================================
+  describe("Deregister", () => {
+    let treasuryPda: PublicKey;
+
+    async function registerAgent(soulbound = false) {
+      const asset = Keypair.generate();
+      const [agentPda] = getAgentPda(asset.publicKey, program.programId);
+      const uri = "https://example.com/agent/deregister";
+      const builder = soulbound
+        ? program.methods.registerSoulbound(uri, false)
+        : program.methods.register(uri);
+      await builder
+        .accountsPartial({
+          rootConfig: rootConfigPda,
+          registryConfig: registryConfigPda,
+          agentAccount: agentPda,
+          asset: asset.publicKey,
+          collection: collectionPubkey,
+          owner: provider.wallet.publicKey,
+          payer: provider.wallet.publicKey,
+          systemProgram: SystemProgram.programId,
+          mplCoreProgram: MPL_CORE_PROGRAM_ID,
+        })
+        .signers([asset])
+        .rpc();
+      const agent = await program.account.agentAccount.fetch(agentPda);
+      const [agentIdIndexPda] = getAgentIdIndexPda(agent.agentId, program.programId);
+      return { asset, agentPda, agentIdIndexPda };
+    }
+
+    function deregister(
+      agent: { asset: Keypair; agentPda: PublicKey; agentIdIndexPda: PublicKey },
+      treasury: PublicKey | null = null
+    ) {
+      return program.methods
+        .deregister()
+        .accountsPartial({
+          agentAccount: agent.agentPda,
+          agentIdIndex: agent.agentIdIndexPda,
+          asset: agent.asset.publicKey,
+          collection: collectionPubkey,
+          owner: provider.wallet.publicKey,
+          systemProgram: SystemProgram.programId,
+          mplCoreProgram: MPL_CORE_PROGRAM_ID,
+          registryConfig: registryConfigPda,
+          ownerIndex: null,
+          treasury,
+        })
+        .rpc();
+    }
+
+    async function expectClosed(agent: { agentPda: PublicKey; agentIdIndexPda: PublicKey }) {
+      expect(await provider.connection.getAccountInfo(agent.agentPda)).to.equal(null);
+      expect(await provider.connection.getAccountInfo(agent.agentIdIndexPda)).to.equal(null);
+    }
+
+    before(async () => {
+      [treasuryPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("treasury"), collectionPubkey.toBuffer()],
+        program.programId
+      );
+      if (!(await provider.connection.getAccountInfo(treasuryPda))) {
+        await program.methods
+          .initializeTreasury()
+          .accountsPartial({
+            registryConfig: registryConfigPda,
+            treasury: treasuryPda,
+            authority: provider.wallet.publicKey,
+          })
+          .rpc();
+      }
+    });
+
+    it("deregister() burns a live asset and closes the agent and its id index", async () => {
+      const before = await program.account.registryConfig.fetch(registryConfigPda);
+      const agent = await registerAgent();
+      const registered = await program.account.registryConfig.fetch(registryConfigPda);
+      expect(registered.totalAgents.toString()).to.equal(before.totalAgents.addn(1).toString());
+
+      await deregister(agent);
+
+      await expectClosed(agent);
+      const assetInfo = await provider.connection.getAccountInfo(agent.asset.publicKey);
+      expect(assetInfo === null || assetInfo.data.length <= 1).to.equal(true);
+      const after = await program.account.registryConfig.fetch(registryConfigPda);
+      expect(after.totalAgents.toString()).to.equal(before.totalAgents.toString());
+    });
+
+    it("deregister() of an already-burned asset pays the rent to the treasury", async () => {
+      const agent = await registerAgent();
+      const rent =
+        (await provider.connection.getBalance(agent.agentPda)) +
+        (await provider.connection.getBalance(agent.agentIdIndexPda));
+      await burnCoreAssetExternally(
+        provider,
+        agent.asset.publicKey,
+        collectionPubkey,
+        provider.wallet.publicKey
+      );
+
+      await expectAnchorError(deregister(agent), "InvalidTreasury");
+
+      const treasuryBefore = await provider.connection.getBalance(treasuryPda);
+      await deregister(agent, treasuryPda);
+
+      await expectClosed(agent);
+      expect(await provider.connection.getBalance(treasuryPda)).to.equal(treasuryBefore + rent);
+    });
+
+    it("deregister() lifts the soulbound freeze before burning", async () => {
+      const agent = await registerAgent(true);
+      expect((await program.account.agentAccount.fetch(agent.agentPda)).soulbound).to.equal(true);
+
+      await deregister(agent);
+
+      await expectClosed(agent);
+    });
+
+    it("deregister() rejects an agent whose registration bond is still locked", async () => {
+      await program.methods
+        .setRegistrationBond(new anchor.BN(1_000_000), new anchor.BN(3600))
+        .accountsPartial({
+          registryConfig: registryConfigPda,
+          authority: provider.wallet.publicKey,
+        })
+        .rpc();
+      try {
+        const agent = await registerAgent();
+        await expectAnchorError(deregister(agent), "BondLocked");
+      } finally {
+        await program.methods
+          .setRegistrationBond(new anchor.BN(0), new anchor.BN(0))
+          .accountsPartial({
+            registryConfig: registryConfigPda,
+            authority: provider.wallet.publicKey,
+          })
+          .rpc();
+      }
+    });
+
+    it("deregister() waits out the dispute window after feedback", async () => {
+      const setDisputeWindow = (seconds: number) =>
+        program.methods
+          .setDisputeWindow(new anchor.BN(seconds))
+          .accountsPartial({
+            registryConfig: registryConfigPda,
+            authority: provider.wallet.publicKey,
+          })
+          .rpc();
+
+      const agent = await registerAgent();
+      const client = Keypair.generate();
+      await fundKeypair(provider, client, 0.1 * anchor.web3.LAMPORTS_PER_SOL);
+      await program.methods
+        .giveFeedback(
+          new anchor.BN(80),
+          0,
+          80,
+          null,
+          "quality",
+          "deregister",
+          "https://api.example.com",
+          "https://example.com/feedback/deregister"
+        )
+        .accountsPartial({
+          client: client.publicKey,
+          asset: agent.asset.publicKey,
+          collection: collectionPubkey,
+          agentAccount: agent.agentPda,
+          systemProgram: SystemProgram.programId,
+          feedbackScore: null,
+          tagReputation: null,
+        })
+        .signers([client])
+        .rpc();
+
+      await setDisputeWindow(3600);
+      try {
+        await expectAnchorError(deregister(agent), "DisputeWindowOpen");
+      } finally {
+        await setDisputeWindow(0);
+      }
+
+      await deregister(agent);
+      await expectClosed(agent);
+    });
+  });
 });
@@ -82,6 +82,19 @@ export function getAgentPda(
   );
 }
 
+/**
+ * Derive agent id reverse index PDA: ["agent_id", agent_id (u64 LE)]
+ */
+export function getAgentIdIndexPda(
+  agentId: anchor.BN,
+  programId: PublicKey
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [Buffer.from("agent_id"), agentId.toArrayLike(Buffer, "le", 8)],
+    programId
+  );
+}
+
 /**
  * Derive agent reputation PDA: ["agent_reputation", asset.key()]
  * v0.3.0: Uses asset (Pubkey) instead of agent_id